@@ -82,7 +82,8 @@ async function showStats(node) {
 
 function logEvent(event) {
   // Skip noisy events
-  if (event.data.get("event").type == "share_sampling_result") {
+  const type = event.data.get("event").type;
+  if (type == "share_sampling_result" || type == "sampling_progress") {
     return;
   }
 
@@ -1,4 +1,5 @@
 use std::env::current_exe;
+use std::io::Write;
 
 use anyhow::Result;
 use clap::Parser;
@@ -31,7 +32,9 @@ pub async fn run() -> Result<()> {
         CliArgs::parse()
     };
 
-    let _guard = init_tracing();
+    // Keep stdout clean when it's used for events.
+    let logs_to_stderr = matches!(&args, CliArgs::Node(args) if args.events_json);
+    let _guard = init_tracing(logs_to_stderr);
 
     match args {
         CliArgs::Node(args) => native::run(args).await,
@@ -40,8 +43,13 @@ pub async fn run() -> Result<()> {
     }
 }
 
-fn init_tracing() -> tracing_appender::non_blocking::WorkerGuard {
-    let (non_blocking, guard) = tracing_appender::non_blocking(std::io::stdout());
+fn init_tracing(to_stderr: bool) -> tracing_appender::non_blocking::WorkerGuard {
+    let writer: Box<dyn Write + Send> = if to_stderr {
+        Box::new(std::io::stderr())
+    } else {
+        Box::new(std::io::stdout())
+    };
+    let (non_blocking, guard) = tracing_appender::non_blocking(writer);
 
     let filter = tracing_subscriber::EnvFilter::builder()
         .with_default_directive(tracing_subscriber::filter::LevelFilter::INFO.into())
@@ -0,0 +1,352 @@
+//! Runtime control of the native node through a Unix socket.
+//!
+//! The protocol is line based: each line sent by the client is a command
+//! and each command is answered with a single line.
+//!
+//! Supported commands:
+//!
+//! * `pause` - Pause header synchronization and data sampling.
+//! * `resume` - Resume header synchronization and data sampling.
+//! * `status` - Report whether node is paused and its current sync height.
+//! * `peers` - List known peers with their agent and supported protocols.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use blockstore::Blockstore;
+use lumina_node::node::{Node, PeerDetails};
+use lumina_node::store::Store;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tracing::{debug, warn};
+
+/// A command accepted on the control socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Command {
+    Pause,
+    Resume,
+    Status,
+    Peers,
+}
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "pause" => Ok(Command::Pause),
+            "resume" => Ok(Command::Resume),
+            "status" => Ok(Command::Status),
+            "peers" => Ok(Command::Peers),
+            cmd => bail!("Unknown command: {cmd}"),
+        }
+    }
+}
+
+/// Response to the `status` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Status {
+    pub(crate) paused: bool,
+    pub(crate) sync_height: u64,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = if self.paused { "paused" } else { "running" };
+        write!(f, "{state} height={}", self.sync_height)
+    }
+}
+
+/// Format peers as a single line response to the `peers` command.
+fn format_peers(peers: &[PeerDetails]) -> String {
+    if peers.is_empty() {
+        return "no peers".to_string();
+    }
+
+    peers
+        .iter()
+        .map(|peer| {
+            let protocols = peer
+                .protocols
+                .iter()
+                .map(|p| p.as_ref())
+                .collect::<Vec<_>>()
+                .join(",");
+
+            format!(
+                "{} connected={} trusted={} agent={} protocols={protocols}",
+                peer.id,
+                peer.connected,
+                peer.trusted,
+                peer.agent_version.as_deref().unwrap_or("unknown"),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Node that can be controlled through the control socket.
+pub(crate) trait Controllable {
+    async fn pause(&self) -> Result<()>;
+    async fn resume(&self) -> Result<()>;
+    async fn status(&self) -> Result<Status>;
+    async fn peers(&self) -> Result<Vec<PeerDetails>>;
+}
+
+impl<B, S> Controllable for Node<B, S>
+where
+    B: Blockstore + 'static,
+    S: Store + 'static,
+{
+    async fn pause(&self) -> Result<()> {
+        Ok(Node::pause(self).await?)
+    }
+
+    async fn resume(&self) -> Result<()> {
+        Ok(Node::resume(self).await?)
+    }
+
+    async fn status(&self) -> Result<Status> {
+        let info = self.syncer_info().await?;
+
+        Ok(Status {
+            paused: self.is_paused(),
+            sync_height: info.stored_headers.head().unwrap_or(0),
+        })
+    }
+
+    async fn peers(&self) -> Result<Vec<PeerDetails>> {
+        Ok(Node::peers(self).await?)
+    }
+}
+
+/// Unix socket accepting control commands.
+///
+/// Socket file is removed when server is dropped.
+pub(crate) struct ControlServer {
+    listener: UnixListener,
+    path: PathBuf,
+}
+
+impl ControlServer {
+    /// Bind the control socket to the given path.
+    ///
+    /// Stale socket file from previous run is replaced.
+    pub(crate) fn bind(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let listener = UnixListener::bind(&path)?;
+
+        Ok(ControlServer { listener, path })
+    }
+
+    /// Serve control connections forever.
+    ///
+    /// Connections are handled one by one.
+    pub(crate) async fn serve<N>(&self, node: &N) -> io::Result<()>
+    where
+        N: Controllable,
+    {
+        loop {
+            let (stream, _) = self.listener.accept().await?;
+
+            if let Err(e) = handle_connection(stream, node).await {
+                warn!("Control connection failed: {e}");
+            }
+        }
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+async fn handle_connection<N>(stream: UnixStream, node: &N) -> io::Result<()>
+where
+    N: Controllable,
+{
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        debug!("Control command: {line}");
+
+        let response = match execute(&line, node).await {
+            Ok(response) => response,
+            Err(e) => format!("error: {e}"),
+        };
+
+        writer.write_all(response.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+    }
+
+    Ok(())
+}
+
+async fn execute<N>(line: &str, node: &N) -> Result<String>
+where
+    N: Controllable,
+{
+    match line.parse()? {
+        Command::Pause => {
+            node.pause().await?;
+            Ok("ok".to_string())
+        }
+        Command::Resume => {
+            node.resume().await?;
+            Ok("ok".to_string())
+        }
+        Command::Status => Ok(node.status().await?.to_string()),
+        Command::Peers => Ok(format_peers(&node.peers().await?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use libp2p::{PeerId, StreamProtocol};
+    use tokio::io::Lines;
+    use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MockNode {
+        paused: AtomicBool,
+        peers: Vec<PeerDetails>,
+    }
+
+    impl Controllable for MockNode {
+        async fn pause(&self) -> Result<()> {
+            self.paused.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn resume(&self) -> Result<()> {
+            self.paused.store(false, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn status(&self) -> Result<Status> {
+            Ok(Status {
+                paused: self.paused.load(Ordering::SeqCst),
+                sync_height: 42,
+            })
+        }
+
+        async fn peers(&self) -> Result<Vec<PeerDetails>> {
+            Ok(self.peers.clone())
+        }
+    }
+
+    async fn request(
+        lines: &mut Lines<BufReader<OwnedReadHalf>>,
+        writer: &mut OwnedWriteHalf,
+        cmd: &str,
+    ) -> String {
+        writer
+            .write_all(format!("{cmd}\n").as_bytes())
+            .await
+            .unwrap();
+        lines.next_line().await.unwrap().unwrap()
+    }
+
+    #[test]
+    fn parse_command() {
+        assert_eq!("pause".parse::<Command>().unwrap(), Command::Pause);
+        assert_eq!("resume\n".parse::<Command>().unwrap(), Command::Resume);
+        assert_eq!(" status ".parse::<Command>().unwrap(), Command::Status);
+        assert_eq!("peers".parse::<Command>().unwrap(), Command::Peers);
+        "stop".parse::<Command>().unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn list_peers() {
+        let mut node = MockNode::default();
+        assert_eq!(execute("peers", &node).await.unwrap(), "no peers");
+
+        let peer1 = PeerId::random();
+        let peer2 = PeerId::random();
+        node.peers = vec![
+            PeerDetails {
+                id: peer1,
+                addrs: Vec::new(),
+                protocols: vec![
+                    StreamProtocol::new("/ipfs/id/1.0.0"),
+                    StreamProtocol::new("/ipfs/kad/1.0.0"),
+                ],
+                agent_version: Some("celestia-node/1.0.0".to_string()),
+                connected: true,
+                trusted: true,
+            },
+            PeerDetails {
+                id: peer2,
+                addrs: Vec::new(),
+                protocols: Vec::new(),
+                agent_version: None,
+                connected: false,
+                trusted: false,
+            },
+        ];
+
+        assert_eq!(
+            execute("peers", &node).await.unwrap(),
+            format!(
+                "{peer1} connected=true trusted=true agent=celestia-node/1.0.0 \
+                 protocols=/ipfs/id/1.0.0,/ipfs/kad/1.0.0; \
+                 {peer2} connected=false trusted=false agent=unknown protocols="
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn pause_status_resume() {
+        let path = std::env::temp_dir().join(format!("lumina-control-{}.sock", std::process::id()));
+        let server = ControlServer::bind(&path).unwrap();
+        let node = MockNode::default();
+
+        let client = async {
+            let stream = UnixStream::connect(&path).await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+
+            assert_eq!(
+                request(&mut lines, &mut writer, "status").await,
+                "running height=42"
+            );
+            assert_eq!(request(&mut lines, &mut writer, "pause").await, "ok");
+            assert_eq!(
+                request(&mut lines, &mut writer, "status").await,
+                "paused height=42"
+            );
+            assert_eq!(request(&mut lines, &mut writer, "resume").await, "ok");
+            assert_eq!(
+                request(&mut lines, &mut writer, "status").await,
+                "running height=42"
+            );
+            assert!(request(&mut lines, &mut writer, "foo")
+                .await
+                .starts_with("error:"));
+        };
+
+        tokio::select! {
+            _ = client => {}
+            res = server.serve(&node) => panic!("Server stopped: {res:?}"),
+        }
+
+        drop(server);
+        assert!(!path.exists());
+    }
+}
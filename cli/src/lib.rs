@@ -2,6 +2,9 @@
 #![cfg(not(target_arch = "wasm32"))]
 
 mod common;
+#[cfg(unix)]
+mod control;
+pub mod light_verify;
 mod native;
 #[cfg(feature = "browser-node")]
 mod server;
@@ -0,0 +1,135 @@
+//! Verify-only light client mode.
+//!
+//! Headers are pulled from a trusted RPC endpoint instead of p2p network,
+//! verified against their predecessors and sampled using shares with
+//! inclusion proofs.
+
+use std::env;
+
+use anyhow::{bail, Context, Result};
+use celestia_rpc::prelude::*;
+use celestia_rpc::Client;
+use celestia_types::ExtendedHeader;
+use tracing::{info, warn};
+
+/// Default amount of headers verified when it isn't specified.
+pub const DEFAULT_VERIFY_AMOUNT: u64 = 16;
+
+/// Amount of shares sampled for each verified height.
+const SAMPLES_PER_HEIGHT: usize = 4;
+
+/// Result of verification of a single height.
+#[derive(Debug)]
+pub struct HeightReport {
+    /// The verified height.
+    pub height: u64,
+    /// The result of the verification.
+    pub result: Result<()>,
+}
+
+/// Verify the headers in range and report the result of each height.
+///
+/// If `from` isn't provided, the last `amount` headers up to network head are verified.
+pub(crate) async fn run(url: &str, from: Option<u64>, amount: u64) -> Result<()> {
+    let token = env::var("CELESTIA_NODE_AUTH_TOKEN_READ").ok();
+    let client = Client::new(url, token.as_deref())
+        .await
+        .with_context(|| format!("Failed to connect to {url}"))?;
+
+    let from = match from {
+        Some(from) => from,
+        None => {
+            let head = client.header_network_head().await?;
+            head.height()
+                .value()
+                .saturating_sub(amount.saturating_sub(1))
+                .max(1)
+        }
+    };
+
+    let reports = verify_range(&client, from, amount).await?;
+    let failed = reports
+        .iter()
+        .filter(|report| report.result.is_err())
+        .count();
+
+    for report in &reports {
+        match &report.result {
+            Ok(()) => info!("height {}: pass", report.height),
+            Err(e) => warn!("height {}: fail: {e:#}", report.height),
+        }
+    }
+
+    if failed > 0 {
+        bail!(
+            "{failed} out of {} heights failed verification",
+            reports.len()
+        );
+    }
+
+    info!("All {} heights passed verification", reports.len());
+
+    Ok(())
+}
+
+/// Verify `amount` consecutive headers starting from `from`.
+///
+/// Each header is validated, verified against the previous one and sampled.
+/// Headers are fetched from the trusted endpoint, so the header preceding `from`
+/// is used as an anchor.
+pub async fn verify_range(client: &Client, from: u64, amount: u64) -> Result<Vec<HeightReport>> {
+    if from == 0 {
+        bail!("Height 0 doesn't exist");
+    }
+
+    if amount == 0 {
+        bail!("Amount of headers to verify must be greater than 0");
+    }
+
+    let mut prev = if from > 1 {
+        Some(client.header_get_by_height(from - 1).await?)
+    } else {
+        None
+    };
+
+    let mut reports = Vec::with_capacity(amount as usize);
+
+    for height in from..from + amount {
+        let header = client
+            .header_get_by_height(height)
+            .await
+            .with_context(|| format!("Failed to fetch header {height}"))?;
+
+        let result = verify_height(client, prev.as_ref(), &header).await;
+
+        reports.push(HeightReport { height, result });
+        prev = Some(header);
+    }
+
+    Ok(reports)
+}
+
+async fn verify_height(
+    client: &Client,
+    prev: Option<&ExtendedHeader>,
+    header: &ExtendedHeader,
+) -> Result<()> {
+    header.validate().context("Header validation failed")?;
+
+    if let Some(prev) = prev {
+        prev.verify(header)
+            .context("Header verification against previous header failed")?;
+    }
+
+    sample(client, header).await
+}
+
+/// Sample random shares of the original data square, verifying their inclusion proofs.
+async fn sample(client: &Client, header: &ExtendedHeader) -> Result<()> {
+    client
+        .share_shares_available_sampled(header, SAMPLES_PER_HEIGHT)
+        .await
+        .context("Sampling failed")?;
+
+    Ok(())
+}
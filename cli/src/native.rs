@@ -1,4 +1,5 @@
 use std::env;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -7,18 +8,28 @@ use anyhow::{bail, Context, Result};
 use blockstore::EitherBlockstore;
 use celestia_rpc::prelude::*;
 use celestia_rpc::Client;
+use celestia_types::p2p::addr::{require_peer_id, Transport};
 use clap::{value_parser, Parser};
 use directories::ProjectDirs;
 use libp2p::multiaddr::{Multiaddr, Protocol};
+use libp2p::PeerId;
 use lumina_node::blockstore::{InMemoryBlockstore, RedbBlockstore};
 use lumina_node::events::NodeEvent;
 use lumina_node::network::Network;
-use lumina_node::node::{Node, MIN_PRUNING_DELAY, MIN_SAMPLING_WINDOW};
+use lumina_node::node::{
+    Node, TransportLimits, DEFAULT_SAMPLING_WINDOW, MIN_PRUNING_DELAY, MIN_SAMPLING_WINDOW,
+};
 use lumina_node::store::{EitherStore, InMemoryStore, RedbStore, Store as _};
+#[cfg(unix)]
+use tokio::select;
 use tokio::task::spawn_blocking;
 use tracing::info;
 use tracing::warn;
 
+#[cfg(unix)]
+use crate::control::ControlServer;
+use crate::light_verify;
+
 const CELESTIA_LOCAL_BRIDGE_RPC_ADDR: &str = "ws://localhost:36658";
 
 type Blockstore = EitherBlockstore<InMemoryBlockstore, RedbBlockstore>;
@@ -40,11 +51,16 @@ pub(crate) struct Params {
     pub(crate) bootnodes: Vec<Multiaddr>,
 
     /// Persistent header store path.
-    #[arg(short, long)]
+    ///
+    /// If it points to an existing directory, the store is kept in a `db` file within it.
+    #[arg(short, long, alias = "store-path")]
     pub(crate) store: Option<PathBuf>,
 
     /// Use in-memory store.
-    #[arg(long)]
+    ///
+    /// Nothing is written to disk, so all headers and samples are lost when the
+    /// node stops. Useful for quick experiments and CI runs.
+    #[arg(long, alias = "in-memory", conflicts_with = "store")]
     pub(crate) in_memory_store: bool,
 
     /// Sampling window defines maximum age of a block considered for syncing and sampling.
@@ -52,26 +68,79 @@ pub(crate) struct Params {
     #[clap(value_parser = parse_duration::parse)]
     pub(crate) sampling_window: Option<Duration>,
 
+    /// Prune window is an alias of `--sampling-window`, named after its effect on storage.
+    ///
+    /// Blocks older than the window are neither synced nor sampled, and are pruned once
+    /// the pruning delay passes. Use a bigger value to keep more history or a smaller
+    /// one on constrained devices.
+    #[arg(long, conflicts_with = "sampling_window")]
+    #[clap(value_parser = parse_duration::parse)]
+    pub(crate) prune_window: Option<Duration>,
+
     /// Pruning delay defines how much time the pruner should wait after sampling window in
     /// order to prune the block.
     #[arg(long)]
     #[clap(value_parser = parse_duration::parse)]
     pub(crate) pruning_delay: Option<Duration>,
+
+    /// Maximum number of concurrent substreams within a single connection.
+    #[arg(long)]
+    pub(crate) max_concurrent_substreams: Option<usize>,
+
+    /// Maximum number of inbound connections that are still being negotiated.
+    ///
+    /// Use 0 to disable the limit.
+    #[arg(long)]
+    pub(crate) max_pending_incoming: Option<u32>,
+
+    /// Maximum number of established connections with a single peer.
+    ///
+    /// Use 0 to disable the limit.
+    #[arg(long)]
+    pub(crate) max_connections_per_peer: Option<u32>,
+
+    /// Print node events to stdout as JSON lines.
+    ///
+    /// Logs are written to stderr in this mode, so the two streams don't mix.
+    #[arg(long)]
+    pub(crate) events_json: bool,
+
+    /// Verify headers and sample them using a trusted RPC endpoint, without joining p2p network.
+    ///
+    /// Each height is reported as passed or failed. Authorization token is read from
+    /// `CELESTIA_NODE_AUTH_TOKEN_READ` environment variable, if present.
+    #[arg(long, value_name = "RPC_URL")]
+    pub(crate) light_verify: Option<String>,
+
+    /// First height verified in `--light-verify` mode. Defaults to the most recent headers.
+    #[arg(long, requires = "light_verify")]
+    pub(crate) light_verify_from: Option<u64>,
+
+    /// Amount of headers verified in `--light-verify` mode.
+    #[arg(long, requires = "light_verify", default_value_t = light_verify::DEFAULT_VERIFY_AMOUNT)]
+    pub(crate) light_verify_amount: u64,
+
+    /// Path of the Unix socket used to control the running node.
+    ///
+    /// Accepts `pause`, `resume`, `status` and `peers` commands, one per line.
+    #[cfg(unix)]
+    #[arg(long)]
+    pub(crate) control_socket: Option<PathBuf>,
 }
 
 pub(crate) async fn run(args: Params) -> Result<()> {
-    let (blockstore, store) = if args.in_memory_store {
-        open_in_memory_stores()
-    } else {
-        open_db_stores(args.store, args.network.id()).await?
-    };
+    if let Some(url) = &args.light_verify {
+        return light_verify::run(url, args.light_verify_from, args.light_verify_amount).await;
+    }
+
+    let (blockstore, store) = open_stores(&args).await?;
 
     let mut node_builder = Node::builder()
         .store(store)
         .blockstore(blockstore)
         .network(args.network.clone());
 
-    if let Some(sampling_window) = args.sampling_window {
+    if let Some(sampling_window) = sampling_window(&args)? {
         node_builder = node_builder.sampling_window(sampling_window);
     } else if args.in_memory_store {
         // In-memory stores are memory hungry, so we lower sampling window.
@@ -85,6 +154,8 @@ pub(crate) async fn run(args: Params) -> Result<()> {
         node_builder = node_builder.pruning_delay(MIN_PRUNING_DELAY);
     }
 
+    node_builder = node_builder.transport_limits(transport_limits(&args));
+
     if args.bootnodes.is_empty() {
         if args.network.is_custom() {
             let bootnodes = fetch_bridge_multiaddrs(CELESTIA_LOCAL_BRIDGE_RPC_ADDR).await?;
@@ -98,23 +169,105 @@ pub(crate) async fn run(args: Params) -> Result<()> {
         node_builder = node_builder.listen(args.listen_addrs);
     }
 
-    let (_node, mut events) = node_builder
+    let (node, mut events) = node_builder
         .start_subscribed()
         .await
         .context("Failed to start node")?;
 
-    while let Ok(ev) = events.recv().await {
-        match ev.event {
-            // Skip noisy events
-            NodeEvent::ShareSamplingResult { .. } => continue,
-            event if event.is_error() => warn!("{event}"),
-            event => info!("{event}"),
+    let events_json = args.events_json;
+
+    let log_events = async {
+        while let Ok(ev) = events.recv().await {
+            if events_json {
+                if let Err(e) = write_event_json(&mut io::stdout().lock(), &ev.event) {
+                    warn!("Failed to write event: {e}");
+                }
+                continue;
+            }
+
+            match ev.event {
+                // Skip noisy events
+                NodeEvent::ShareSamplingResult { .. } | NodeEvent::SamplingProgress { .. } => {
+                    continue
+                }
+                event if event.is_error() => warn!("{event}"),
+                event => info!("{event}"),
+            }
+        }
+    };
+
+    #[cfg(unix)]
+    if let Some(path) = args.control_socket {
+        let control = ControlServer::bind(&path).context("Failed to bind control socket")?;
+        info!("Listening for control commands on {}", path.display());
+
+        select! {
+            _ = log_events => {}
+            res = control.serve(&node) => res.context("Control socket failed")?,
         }
+
+        return Ok(());
     }
 
+    log_events.await;
+
     Ok(())
 }
 
+/// Writes the event as a single line of JSON.
+fn write_event_json(writer: &mut impl Write, event: &NodeEvent) -> Result<()> {
+    serde_json::to_writer(&mut *writer, event)?;
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Returns the sampling window requested with `--sampling-window` or `--prune-window`.
+fn sampling_window(args: &Params) -> Result<Option<Duration>> {
+    let (name, window) = match (args.prune_window, args.sampling_window) {
+        (Some(window), _) => ("Prune window", window),
+        (None, Some(window)) => ("Sampling window", window),
+        (None, None) => return Ok(None),
+    };
+
+    if window < MIN_SAMPLING_WINDOW {
+        bail!("{name} is {window:?} but cannot be smaller than {MIN_SAMPLING_WINDOW:?}");
+    }
+
+    if window < DEFAULT_SAMPLING_WINDOW {
+        warn!("{name} of {window:?} is smaller than recommended {DEFAULT_SAMPLING_WINDOW:?}");
+    }
+
+    Ok(Some(window))
+}
+
+/// Returns the transport limits, with the defaults overridden by the arguments.
+fn transport_limits(args: &Params) -> TransportLimits {
+    let mut limits = TransportLimits::default();
+
+    if let Some(max) = args.max_concurrent_substreams {
+        limits.max_concurrent_substreams = max;
+    }
+
+    if let Some(max) = args.max_pending_incoming {
+        limits.max_pending_incoming = (max > 0).then_some(max);
+    }
+
+    if let Some(max) = args.max_connections_per_peer {
+        limits.max_established_per_peer = (max > 0).then_some(max);
+    }
+
+    limits
+}
+
+async fn open_stores(args: &Params) -> Result<(Blockstore, Store)> {
+    if args.in_memory_store {
+        Ok(open_in_memory_stores())
+    } else {
+        open_db_stores(args.store.clone(), args.network.id()).await
+    }
+}
+
 fn open_in_memory_stores() -> (Blockstore, Store) {
     info!("Initializing in-memory store");
     let store = InMemoryStore::new();
@@ -124,8 +277,16 @@ fn open_in_memory_stores() -> (Blockstore, Store) {
 
 async fn open_db_stores(path: Option<PathBuf>, network_id: &str) -> Result<(Blockstore, Store)> {
     info!("Initializing store");
-    let db = open_db(path, network_id).await?;
-    let store = RedbStore::new(db.clone()).await?;
+    let (db, path) = open_db(path, network_id).await?;
+    info!("Using store at {}", path.display());
+
+    let store = RedbStore::new(db.clone()).await.with_context(|| {
+        format!(
+            "Failed to open store at {}. If it was created by a newer version of lumina, \
+             upgrade lumina or pass a different location with --store.",
+            path.display()
+        )
+    })?;
     let blockstore = RedbBlockstore::new(db);
 
     let stored_ranges = store.get_stored_header_ranges().await?;
@@ -141,15 +302,19 @@ async fn open_db_stores(path: Option<PathBuf>, network_id: &str) -> Result<(Bloc
     ))
 }
 
-async fn open_db(path: Option<PathBuf>, network_id: &str) -> Result<Arc<redb::Database>> {
+async fn open_db(
+    path: Option<PathBuf>,
+    network_id: &str,
+) -> Result<(Arc<redb::Database>, PathBuf)> {
     let network_id = network_id.to_owned();
 
     spawn_blocking(move || {
         use std::fs;
 
         if let Some(path) = path {
-            let db = redb::Database::create(path)?;
-            return Ok(Arc::new(db));
+            let path = if path.is_dir() { path.join("db") } else { path };
+            let db = redb::Database::create(&path)?;
+            return Ok((Arc::new(db), path));
         }
 
         let cache_dir = ProjectDirs::from("co", "eiger", "lumina")
@@ -178,9 +343,9 @@ async fn open_db(path: Option<PathBuf>, network_id: &str) -> Result<Arc<redb::Da
         fs::create_dir_all(&cache_dir)?;
 
         let path = cache_dir.join("db");
-        let db = redb::Database::create(path)?;
+        let db = redb::Database::create(&path)?;
 
-        Ok(Arc::new(db))
+        Ok((Arc::new(db), path))
     })
     .await?
 }
@@ -195,26 +360,251 @@ async fn fetch_bridge_multiaddrs(ws_url: &str) -> Result<Vec<Multiaddr>> {
     let auth_token = env::var("CELESTIA_NODE_AUTH_TOKEN_ADMIN")
         .context("Missing CELESTIA_NODE_AUTH_TOKEN_ADMIN environment variable")?;
     let client = Client::new(ws_url, Some(&auth_token)).await?;
-    let bridge_info = client.p2p_info().await?;
+    let bridge_info = client.p2p_info_resolved().await?;
 
     info!("bridge id: {:?}", bridge_info.id);
     info!("bridge listens on: {:?}", bridge_info.addrs);
 
-    let addrs = bridge_info
-        .addrs
+    bridge_multiaddrs(bridge_info.addrs, bridge_info.id.into())
+}
+
+/// Select the addresses of the bridge that the node can dial, preferring QUIC over TCP.
+fn bridge_multiaddrs(addrs: Vec<Multiaddr>, peer_id: PeerId) -> Result<Vec<Multiaddr>> {
+    let (mut quic, tcp): (Vec<_>, Vec<_>) = addrs
         .into_iter()
-        .filter(|ma| ma.protocol_stack().any(|protocol| protocol == "tcp"))
+        .filter(|ma| Transport::Quic.is_used_by(ma) || Transport::Tcp.is_used_by(ma))
         .map(|mut ma| {
-            if !ma.protocol_stack().any(|protocol| protocol == "p2p") {
-                ma.push(Protocol::P2p(bridge_info.id.into()))
+            if require_peer_id(&ma).is_err() {
+                ma.push(Protocol::P2p(peer_id))
             }
             ma
         })
-        .collect::<Vec<_>>();
+        .partition(|ma| Transport::Quic.is_used_by(ma));
+
+    if quic.is_empty() && tcp.is_empty() {
+        bail!("Bridge doesn't listen on quic or tcp");
+    }
+
+    // Keep TCP as a fallback
+    quic.extend(tcp);
+
+    Ok(quic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Params {
+        Params::try_parse_from(["lumina", "--network", "mainnet"].iter().chain(args)).unwrap()
+    }
+
+    #[test]
+    fn prune_window() {
+        let args = parse(&["--prune-window", "48h"]);
+        assert_eq!(
+            sampling_window(&args).unwrap(),
+            Some(Duration::from_secs(48 * 60 * 60))
+        );
+
+        let args = parse(&["--sampling-window", "48h"]);
+        assert_eq!(
+            sampling_window(&args).unwrap(),
+            Some(Duration::from_secs(48 * 60 * 60))
+        );
+
+        let args = parse(&[]);
+        assert_eq!(sampling_window(&args).unwrap(), None);
+    }
+
+    #[test]
+    fn prune_window_too_small() {
+        let args = parse(&["--prune-window", "30s"]);
+        let err = sampling_window(&args).unwrap_err();
+        assert!(err.to_string().starts_with("Prune window"));
+
+        let args = parse(&["--sampling-window", "30s"]);
+        let err = sampling_window(&args).unwrap_err();
+        assert!(err.to_string().starts_with("Sampling window"));
+    }
+
+    #[test]
+    fn transport_limits_args() {
+        let args = parse(&[]);
+        assert_eq!(transport_limits(&args), TransportLimits::default());
+
+        let args = parse(&[
+            "--max-concurrent-substreams",
+            "64",
+            "--max-pending-incoming",
+            "0",
+            "--max-connections-per-peer",
+            "2",
+        ]);
+        assert_eq!(
+            transport_limits(&args),
+            TransportLimits {
+                max_concurrent_substreams: 64,
+                max_pending_incoming: None,
+                max_established_per_peer: Some(2),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_store() {
+        let path = env::temp_dir().join(format!("lumina-store-{}", std::process::id()));
+
+        let mut args = parse(&["--in-memory"]);
+        assert!(args.in_memory_store);
+
+        // In-memory store takes precedence over the path.
+        args.store = Some(path.clone());
+
+        let (blockstore, store) = open_stores(&args).await.unwrap();
+        assert!(matches!(blockstore, EitherBlockstore::Left(_)));
+        assert!(matches!(store, EitherStore::Left(_)));
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn store_path_empty_dir() {
+        let dir = env::temp_dir().join(format!("lumina-store-empty-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let args = parse(&["--store-path", dir.to_str().unwrap()]);
+        let (_blockstore, store) = open_stores(&args).await.unwrap();
 
-    if addrs.is_empty() {
-        bail!("Bridge doesn't listen on tcp");
+        assert!(dir.join("db").is_file());
+        assert!(store.get_stored_header_ranges().await.unwrap().is_empty());
+
+        drop(store);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn store_path_incompatible_version() {
+        const SCHEMA_VERSION_TABLE: redb::TableDefinition<'static, (), u64> =
+            redb::TableDefinition::new("STORE.SCHEMA_VERSION");
+
+        let dir = env::temp_dir().join(format!("lumina-store-newer-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Simulate a store created by a future version
+        let db = redb::Database::create(dir.join("db")).unwrap();
+        let tx = db.begin_write().unwrap();
+        tx.open_table(SCHEMA_VERSION_TABLE)
+            .unwrap()
+            .insert((), u64::MAX)
+            .unwrap();
+        tx.commit().unwrap();
+        drop(db);
+
+        let args = parse(&["--store", dir.to_str().unwrap()]);
+        let err = open_stores(&args).await.err().unwrap();
+
+        assert!(err.to_string().contains(&dir.display().to_string()));
+        assert!(format!("{err:#}").contains("Incompatible database schema"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn in_memory_store_conflicts_with_store_path() {
+        Params::try_parse_from([
+            "lumina",
+            "--network",
+            "mainnet",
+            "--in-memory-store",
+            "--store",
+            "/tmp/lumina",
+        ])
+        .unwrap_err();
+    }
+
+    #[test]
+    fn events_json() {
+        let mut out = Vec::new();
+
+        write_event_json(&mut out, &NodeEvent::ConnectingToBootnodes).unwrap();
+        write_event_json(&mut out, &NodeEvent::PrunedHeaders { to_height: 10 }).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+
+        let ev: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(ev["type"], "connecting_to_bootnodes");
+
+        let ev: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(ev["type"], "pruned_headers");
+        assert_eq!(ev["to_height"], 10);
     }
 
-    Ok(addrs)
+    #[test]
+    fn light_verify_args() {
+        let args = parse(&["--light-verify", "ws://localhost:36658"]);
+        assert_eq!(args.light_verify.as_deref(), Some("ws://localhost:36658"));
+        assert_eq!(args.light_verify_from, None);
+        assert_eq!(
+            args.light_verify_amount,
+            light_verify::DEFAULT_VERIFY_AMOUNT
+        );
+
+        Params::try_parse_from([
+            "lumina",
+            "--network",
+            "mainnet",
+            "--light-verify-from",
+            "10",
+        ])
+        .unwrap_err();
+    }
+
+    #[test]
+    fn bridge_multiaddrs_prefer_quic() {
+        let peer_id = PeerId::random();
+        let addrs = vec![
+            "/ip4/127.0.0.1/tcp/2121".parse().unwrap(),
+            "/ip4/127.0.0.1/udp/2121/quic-v1".parse().unwrap(),
+            "/ip4/127.0.0.1/udp/2121/quic-v1/webtransport"
+                .parse()
+                .unwrap(),
+        ];
+
+        let selected = bridge_multiaddrs(addrs, peer_id).unwrap();
+
+        assert_eq!(selected.len(), 2);
+        assert!(Transport::Quic.is_used_by(&selected[0]));
+        assert!(Transport::Tcp.is_used_by(&selected[1]));
+        for ma in &selected {
+            assert_eq!(require_peer_id(ma).unwrap(), peer_id);
+        }
+    }
+
+    #[test]
+    fn bridge_multiaddrs_tcp_only() {
+        let peer_id = PeerId::random();
+        let addrs = vec!["/ip4/127.0.0.1/tcp/2121".parse().unwrap()];
+
+        let selected = bridge_multiaddrs(addrs, peer_id).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert!(Transport::Tcp.is_used_by(&selected[0]));
+
+        bridge_multiaddrs(Vec::new(), peer_id).unwrap_err();
+    }
+
+    #[test]
+    fn prune_window_conflicts_with_sampling_window() {
+        Params::try_parse_from([
+            "lumina",
+            "--network",
+            "mainnet",
+            "--prune-window",
+            "48h",
+            "--sampling-window",
+            "48h",
+        ])
+        .unwrap_err();
+    }
 }
@@ -1,16 +1,21 @@
+use std::future::Future;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use axum::body::Body;
 use axum::extract::Path;
 use axum::http::{header, StatusCode};
 use axum::response::Response;
 use axum::routing::get;
 use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
+use axum_server::Handle;
 use clap::Args;
+use rcgen::CertifiedKey;
 use rust_embed::RustEmbed;
 use tokio::net::TcpListener;
-use tracing::info;
+use tracing::{info, warn};
 
 const SERVER_DEFAULT_BIND_ADDR: &str = "127.0.0.1:9876";
 
@@ -23,17 +28,149 @@ pub(crate) struct Params {
     /// Listening address.
     #[arg(short, long = "listen", default_value = SERVER_DEFAULT_BIND_ADDR)]
     pub(crate) listen_addr: SocketAddr,
+
+    /// Path to the PEM encoded TLS certificate chain. Enables HTTPS.
+    #[arg(long, requires = "tls_key")]
+    pub(crate) tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM encoded TLS private key.
+    #[arg(long, requires = "tls_cert")]
+    pub(crate) tls_key: Option<PathBuf>,
+
+    /// Serve over HTTPS using a generated self-signed certificate for localhost.
+    ///
+    /// Meant for local development only, browsers will warn about the certificate.
+    #[arg(long, conflicts_with = "tls_cert")]
+    pub(crate) tls_self_signed: bool,
 }
 
 pub(crate) async fn run(args: Params) -> Result<()> {
-    let app = Router::new()
+    let listener = TcpListener::bind(&args.listen_addr).await?;
+
+    match tls_config(&args).await? {
+        Some(config) => {
+            info!("Address: https://{}", args.listen_addr);
+            serve_tls(listener, config, shutdown_signal()).await
+        }
+        None => {
+            info!("Address: http://{}", args.listen_addr);
+            serve(listener, shutdown_signal()).await
+        }
+    }
+}
+
+/// Build the TLS configuration from the arguments, or `None` if HTTPS isn't enabled.
+async fn tls_config(args: &Params) -> Result<Option<RustlsConfig>> {
+    if args.tls_self_signed {
+        let certified = self_signed_cert()?;
+        let config = RustlsConfig::from_pem(
+            certified.cert.pem().into_bytes(),
+            certified.key_pair.serialize_pem().into_bytes(),
+        )
+        .await
+        .context("Failed to configure TLS with self-signed certificate")?;
+        return Ok(Some(config));
+    }
+
+    match (&args.tls_cert, &args.tls_key) {
+        (Some(cert), Some(key)) => {
+            let config = RustlsConfig::from_pem_file(cert, key)
+                .await
+                .context("Failed to configure TLS")?;
+            Ok(Some(config))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Generate a self-signed certificate for localhost.
+fn self_signed_cert() -> Result<CertifiedKey> {
+    let names = vec![
+        "localhost".to_string(),
+        "127.0.0.1".to_string(),
+        "::1".to_string(),
+    ];
+    rcgen::generate_simple_self_signed(names).context("Failed to generate self-signed certificate")
+}
+
+fn router() -> Router {
+    Router::new()
         .route("/", get(serve_index_html))
-        .route("/*path", get(serve_embedded_path::<StaticResources>));
+        .route("/*path", get(serve_embedded_path::<StaticResources>))
+}
 
-    let listener = TcpListener::bind(&args.listen_addr).await?;
-    info!("Address: http://{}", args.listen_addr);
+/// Serve the browser node until `shutdown` completes.
+///
+/// In-flight requests are drained before returning.
+async fn serve<F>(listener: TcpListener, shutdown: F) -> Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    axum::serve(listener, router().into_make_service())
+        .with_graceful_shutdown(async move {
+            shutdown.await;
+            info!("Shutting down server");
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Serve the browser node over HTTPS until `shutdown` completes.
+///
+/// In-flight requests are drained before returning.
+async fn serve_tls<F>(listener: TcpListener, config: RustlsConfig, shutdown: F) -> Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let handle = Handle::new();
+    let shutdown_handle = handle.clone();
+
+    tokio::spawn(async move {
+        shutdown.await;
+        info!("Shutting down server");
+        shutdown_handle.graceful_shutdown(None);
+    });
+
+    axum_server::from_tcp_rustls(listener.into_std()?, config)
+        .handle(handle)
+        .serve(router().into_make_service())
+        .await?;
+
+    Ok(())
+}
+
+/// Completes on SIGINT or, on unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for SIGINT: {e}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
 
-    Ok(axum::serve(listener, app.into_make_service()).await?)
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to listen for SIGTERM: {e}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
 }
 
 async fn serve_index_html() -> Result<Response, StatusCode> {
@@ -53,3 +190,81 @@ async fn serve_embedded_path<Source: RustEmbed>(
         Err(StatusCode::NOT_FOUND)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio::sync::oneshot;
+    use tokio_rustls::rustls::pki_types::ServerName;
+    use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+    use tokio_rustls::TlsConnector;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn graceful_shutdown() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (tx, rx) = oneshot::channel::<()>();
+
+        let server = tokio::spawn(serve(listener, async move {
+            let _ = rx.await;
+        }));
+
+        tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn self_signed_https() {
+        let params = Params {
+            listen_addr: "127.0.0.1:0".parse().unwrap(),
+            tls_cert: None,
+            tls_key: None,
+            tls_self_signed: true,
+        };
+        assert!(tls_config(&params).await.unwrap().is_some());
+
+        let certified = self_signed_cert().unwrap();
+        let config = RustlsConfig::from_pem(
+            certified.cert.pem().into_bytes(),
+            certified.key_pair.serialize_pem().into_bytes(),
+        )
+        .await
+        .unwrap();
+
+        let listener = TcpListener::bind(params.listen_addr).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = oneshot::channel::<()>();
+
+        let server = tokio::spawn(serve_tls(listener, config, async move {
+            let _ = rx.await;
+        }));
+
+        // trust the generated certificate
+        let mut roots = RootCertStore::empty();
+        roots.add(certified.cert.der().clone()).unwrap();
+        let client_config = ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut stream = TlsConnector::from(Arc::new(client_config))
+            .connect(ServerName::try_from("localhost").unwrap(), stream)
+            .await
+            .unwrap();
+
+        stream
+            .write_all(b"GET /missing HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await.unwrap();
+        assert!(response.starts_with(b"HTTP/1.1 404"));
+
+        tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,40 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::env;
+
+use celestia_rpc::prelude::*;
+use celestia_rpc::Client;
+use lumina_cli::light_verify::verify_range;
+
+const CELESTIA_RPC_URL: &str = "ws://localhost:36658";
+
+async fn new_test_client() -> Client {
+    let _ = dotenvy::dotenv();
+    let token = env::var("CELESTIA_NODE_AUTH_TOKEN_READ").ok();
+    let url = env::var("CELESTIA_RPC_URL").unwrap_or_else(|_| CELESTIA_RPC_URL.to_owned());
+
+    let client = Client::new(&url, token.as_deref()).await.unwrap();
+    client.header_wait_for_height(4).await.unwrap();
+    client
+}
+
+#[tokio::test]
+async fn verify_short_range() {
+    let client = new_test_client().await;
+
+    let reports = verify_range(&client, 1, 4).await.unwrap();
+
+    assert_eq!(reports.len(), 4);
+    for (report, height) in reports.iter().zip(1..) {
+        assert_eq!(report.height, height);
+        report.result.as_ref().unwrap();
+    }
+}
+
+#[tokio::test]
+async fn verify_empty_range() {
+    let client = new_test_client().await;
+
+    verify_range(&client, 1, 0).await.unwrap_err();
+    verify_range(&client, 0, 1).await.unwrap_err();
+}
@@ -60,12 +60,13 @@ impl GrpcMethod {
             pub #signature {
                 let mut client = #grpc_client_struct :: new(
                     self.transport.clone(),
-                );
+                )
+                .max_decoding_message_size(self.config.max_decoding_message_size);
 
                 let param = crate::grpc::IntoGrpcParam::into_parameter(( #( #params ),* ));
-                let request = ::tonic::Request::new(param);
-                let response = client. #grpc_method_name (request).await;
-                crate::grpc::FromGrpcResponse::try_from_response(response?.into_inner())
+                let request = self.make_request(param);
+                let response = self.call(client. #grpc_method_name (request)).await;
+                crate::grpc::FromGrpcResponse::try_from_response(response?)
             }
         };
 
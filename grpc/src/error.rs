@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use celestia_types::{hash::Hash, state::ErrorCode};
 use k256::ecdsa::signature::Error as SignatureError;
 use tonic::Status;
@@ -16,6 +18,14 @@ pub enum Error {
     #[error(transparent)]
     TonicError(#[from] Status),
 
+    /// Request didn't complete within the configured timeout
+    #[error("Request timed out after {0:?}")]
+    RequestTimeout(Duration),
+
+    /// Response exceeded the configured maximum decoding message size
+    #[error("Response exceeded maximum message size of {0} bytes")]
+    ResponseTooLarge(usize),
+
     /// Transport error
     #[error("Transport: {0}")]
     TransportError(String),
@@ -1,6 +1,8 @@
 //! Types and client for the celestia grpc
 
 use std::fmt;
+use std::future::Future;
+use std::time::Duration;
 
 use bytes::Bytes;
 use celestia_grpc_macros::grpc_method;
@@ -20,8 +22,10 @@ use celestia_types::state::{Address, Coin, TxResponse};
 use http_body::Body;
 use tonic::body::BoxBody;
 use tonic::client::GrpcService;
+use tonic::{Code, Request, Response, Status};
 
-use crate::Result;
+use crate::utils::timeout;
+use crate::{Error, Result};
 
 // cosmos.auth
 mod auth;
@@ -50,9 +54,40 @@ pub use crate::grpc::bank::JsCoin;
 /// Error convertible to std, used by grpc transports
 pub type StdError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
+/// Default timeout of a single gRPC request.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default maximum size of a decoded gRPC response.
+///
+/// It is larger than tonic's default of 4MiB, so that the biggest blocks can be retrieved.
+pub const DEFAULT_MAX_DECODING_MESSAGE_SIZE: usize = 32 * 1024 * 1024;
+
+/// Configuration of the requests made by [`GrpcClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrpcConfig {
+    /// Maximum duration of a single request, `None` for no timeout.
+    ///
+    /// **Default value:** [`DEFAULT_REQUEST_TIMEOUT`]
+    pub request_timeout: Option<Duration>,
+    /// Maximum size of a decoded response in bytes.
+    ///
+    /// **Default value:** [`DEFAULT_MAX_DECODING_MESSAGE_SIZE`]
+    pub max_decoding_message_size: usize,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        GrpcConfig {
+            request_timeout: Some(DEFAULT_REQUEST_TIMEOUT),
+            max_decoding_message_size: DEFAULT_MAX_DECODING_MESSAGE_SIZE,
+        }
+    }
+}
+
 /// Struct wrapping all the tonic types and doing type conversion behind the scenes.
 pub struct GrpcClient<T> {
     transport: T,
+    config: GrpcConfig,
 }
 
 impl<T> GrpcClient<T> {
@@ -60,6 +95,49 @@ impl<T> GrpcClient<T> {
     pub fn into_inner(self) -> T {
         self.transport
     }
+
+    /// Get the configuration of the requests.
+    pub fn config(&self) -> &GrpcConfig {
+        &self.config
+    }
+
+    fn make_request<P>(&self, param: P) -> Request<P> {
+        let mut request = Request::new(param);
+
+        // let the server know about the deadline too
+        if let Some(timeout) = self.config.request_timeout {
+            request.set_timeout(timeout);
+        }
+
+        request
+    }
+
+    async fn call<F, R>(&self, response: F) -> Result<R>
+    where
+        F: Future<Output = Result<Response<R>, Status>>,
+    {
+        let response = match self.config.request_timeout {
+            Some(dur) => timeout(dur, response)
+                .await
+                .map_err(|_| Error::RequestTimeout(dur))?,
+            None => response.await,
+        };
+
+        response
+            .map(Response::into_inner)
+            .map_err(|status| self.map_status(status))
+    }
+
+    fn map_status(&self, status: Status) -> Error {
+        // tonic reports too big messages with `OutOfRange` when decoding
+        if status.code() == Code::OutOfRange
+            && status.message().contains("message length too large")
+        {
+            Error::ResponseTooLarge(self.config.max_decoding_message_size)
+        } else {
+            Error::TonicError(status)
+        }
+    }
 }
 
 impl<T> GrpcClient<T>
@@ -71,7 +149,12 @@ where
 {
     /// Create a new client wrapping given transport
     pub fn new(transport: T) -> Self {
-        Self { transport }
+        Self::with_config(transport, GrpcConfig::default())
+    }
+
+    /// Create a new client wrapping given transport, with custom requests configuration
+    pub fn with_config(transport: T, config: GrpcConfig) -> Self {
+        Self { transport, config }
     }
 
     // cosmos.auth
@@ -155,7 +238,7 @@ impl GrpcClient<tonic::transport::Channel> {
     /// settings of [`tonic::transport::Channel`].
     pub fn with_url(url: impl Into<String>) -> Result<Self, tonic::transport::Error> {
         let channel = tonic::transport::Endpoint::from_shared(url.into())?.connect_lazy();
-        Ok(Self { transport: channel })
+        Ok(Self::new(channel))
     }
 }
 
@@ -164,9 +247,7 @@ impl GrpcClient<tonic_web_wasm_client::Client> {
     /// Create a new client connected to the given `url` with default
     /// settings of [`tonic_web_wasm_client::Client`].
     pub fn with_grpcweb_url(url: impl Into<String>) -> Self {
-        Self {
-            transport: tonic_web_wasm_client::Client::new(url.into()),
-        }
+        Self::new(tonic_web_wasm_client::Client::new(url.into()))
     }
 }
 
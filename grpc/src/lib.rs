@@ -8,5 +8,5 @@ mod tx;
 mod utils;
 
 pub use crate::error::{Error, Result};
-pub use crate::grpc::GrpcClient;
+pub use crate::grpc::{GrpcClient, GrpcConfig};
 pub use crate::tx::{TxClient, TxConfig};
@@ -7,8 +7,7 @@ use std::time::Duration;
 use bytes::Bytes;
 use celestia_proto::cosmos::crypto::secp256k1;
 use celestia_proto::cosmos::tx::v1beta1::SignDoc;
-use celestia_types::blob::{Blob, MsgPayForBlobs, RawBlobTx, RawMsgPayForBlobs};
-use celestia_types::consts::appconsts;
+use celestia_types::blob::{self, Blob, MsgPayForBlobs, RawBlobTx, RawMsgPayForBlobs};
 use celestia_types::hash::Hash;
 use celestia_types::state::auth::BaseAccount;
 use celestia_types::state::{
@@ -31,25 +30,6 @@ use crate::grpc::{Account, BroadcastMode, GrpcClient, StdError, TxStatus};
 use crate::utils::Interval;
 use crate::{Error, Result};
 
-// source https://github.com/celestiaorg/celestia-app/blob/v3.0.2/x/blob/types/payforblob.go#L21
-// PFBGasFixedCost is a rough estimate for the "fixed cost" in the gas cost
-// formula: gas cost = gas per byte * bytes per share * shares occupied by
-// blob + "fixed cost". In this context, "fixed cost" accounts for the gas
-// consumed by operations outside the blob's GasToConsume function (i.e.
-// signature verification, tx size, read access to accounts).
-//
-// Since the gas cost of these operations is not easy to calculate, linear
-// regression was performed on a set of observed data points to derive an
-// approximate formula for gas cost. Assuming gas per byte = 8 and bytes per
-// share = 512, we can solve for "fixed cost" and arrive at 65,000. gas cost
-// = 8 * 512 * number of shares occupied by the blob + 65,000 has a
-// correlation coefficient of 0.996. To be conservative, we round up "fixed
-// cost" to 75,000 because the first tx always takes up 10,000 more gas than
-// subsequent txs.
-const PFB_GAS_FIXED_COST: u64 = 75000;
-// BytesPerBlobInfo is a rough estimation for the amount of extra bytes in
-// information a blob adds to the size of the underlying transaction.
-const BYTES_PER_BLOB_INFO: u64 = 70;
 const DEFAULT_GAS_MULTIPLIER: f64 = 1.1;
 // source https://github.com/celestiaorg/celestia-core/blob/v1.43.0-tm-v0.34.35/pkg/consts/consts.go#L19
 const BLOB_TX_TYPE_ID: &str = "BLOB";
@@ -90,6 +70,20 @@ where
         signer: S,
     ) -> Result<Self> {
         let client = GrpcClient::new(transport);
+        Self::with_grpc_client(client, account_address, account_pubkey, signer).await
+    }
+
+    /// Create a new transaction client using an existing [`GrpcClient`].
+    ///
+    /// This allows e.g. using a client created with a custom [`GrpcConfig`].
+    ///
+    /// [`GrpcConfig`]: crate::GrpcConfig
+    pub async fn with_grpc_client(
+        client: GrpcClient<T>,
+        account_address: &Address,
+        account_pubkey: VerifyingKey,
+        signer: S,
+    ) -> Result<Self> {
         let account = client.get_account(account_address).await?;
         if let Some(pubkey) = account.pub_key {
             if pubkey != PublicKey::Secp256k1(account_pubkey) {
@@ -634,14 +628,6 @@ pub async fn sign_tx(
 }
 
 fn estimate_gas(blobs: &[Blob], app_version: AppVersion, gas_multiplier: f64) -> u64 {
-    let gas_per_blob_byte = appconsts::gas_per_blob_byte(app_version);
-    let tx_size_cost_per_byte = appconsts::tx_size_cost_per_byte(app_version);
-
-    let blobs_bytes =
-        blobs.iter().map(Blob::shares_len).sum::<usize>() as u64 * appconsts::SHARE_SIZE as u64;
-
-    let gas = blobs_bytes * gas_per_blob_byte
-        + (tx_size_cost_per_byte * BYTES_PER_BLOB_INFO * blobs.len() as u64)
-        + PFB_GAS_FIXED_COST;
+    let gas = blob::estimate_gas(blobs, app_version);
     (gas as f64 * gas_multiplier) as u64
 }
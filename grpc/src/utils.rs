@@ -2,6 +2,7 @@ pub(crate) use imp::*;
 
 #[cfg(not(target_arch = "wasm32"))]
 mod imp {
+    use std::future::Future;
     use std::time::Duration;
     use tokio::time::interval;
     pub(crate) struct Interval(tokio::time::Interval);
@@ -22,13 +23,21 @@ mod imp {
             self.0.tick().await;
         }
     }
+
+    /// Await the future, returning `Err(())` if it didn't finish within `dur`.
+    pub(crate) async fn timeout<F: Future>(dur: Duration, fut: F) -> Result<F::Output, ()> {
+        tokio::time::timeout(dur, fut).await.map_err(|_| ())
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
 mod imp {
+    use futures::future::{select, Either};
     use futures::StreamExt;
-    use gloo_timers::future::IntervalStream;
+    use gloo_timers::future::{IntervalStream, TimeoutFuture};
     use send_wrapper::SendWrapper;
+    use std::future::Future;
+    use std::pin::pin;
     use std::time::Duration;
 
     pub(crate) struct Interval(SendWrapper<IntervalStream>);
@@ -46,6 +55,17 @@ mod imp {
             self.0.next().await;
         }
     }
+
+    /// Await the future, returning `Err(())` if it didn't finish within `dur`.
+    pub(crate) async fn timeout<F: Future>(dur: Duration, fut: F) -> Result<F::Output, ()> {
+        let millis = u32::try_from(dur.as_millis().max(1)).unwrap_or(u32::MAX);
+        let sleep = SendWrapper::new(TimeoutFuture::new(millis));
+
+        match select(pin!(fut), sleep).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(()),
+        }
+    }
 }
 
 /// Create a new javascript `Object` with given properties
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use celestia_grpc::{Error, TxConfig};
+use celestia_grpc::{Error, GrpcClient, GrpcConfig, TxClient, TxConfig};
 use celestia_proto::cosmos::bank::v1beta1::MsgSend;
 use celestia_types::nmt::Namespace;
 use celestia_types::state::{Coin, ErrorCode};
@@ -85,6 +85,58 @@ async fn get_block() {
     assert_eq!(block.header, latest_block.header);
 }
 
+#[async_test]
+async fn response_too_large() {
+    let config = GrpcConfig {
+        max_decoding_message_size: 16,
+        ..GrpcConfig::default()
+    };
+    let client = GrpcClient::with_config(new_grpc_client().into_inner(), config);
+
+    let err = client.get_latest_block().await.unwrap_err();
+    assert!(matches!(err, Error::ResponseTooLarge(16)));
+}
+
+#[async_test]
+async fn tx_client_with_grpc_client() {
+    let creds = load_account();
+    let config = GrpcConfig {
+        max_decoding_message_size: 16,
+        ..GrpcConfig::default()
+    };
+    let client = GrpcClient::with_config(new_grpc_client().into_inner(), config);
+
+    // config of the provided client is used for the requests made on creation
+    let err = TxClient::with_grpc_client(
+        client,
+        &creds.address,
+        creds.verifying_key,
+        creds.signing_key,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, Error::ResponseTooLarge(16)));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_test]
+async fn request_timeout() {
+    use std::time::Duration;
+
+    // accepts connections, but never responds
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let config = GrpcConfig {
+        request_timeout: Some(Duration::from_millis(100)),
+        ..GrpcConfig::default()
+    };
+    let client = GrpcClient::with_config(GrpcClient::with_url(url).unwrap().into_inner(), config);
+
+    let err = client.get_min_gas_price().await.unwrap_err();
+    assert!(matches!(err, Error::RequestTimeout(dur) if dur == Duration::from_millis(100)));
+}
+
 #[async_test]
 async fn get_blob_params() {
     let client = new_grpc_client();
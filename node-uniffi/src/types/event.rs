@@ -1,9 +1,13 @@
 use libp2p::PeerId as Libp2pPeerId;
-use lumina_node::events::NodeEvent as LuminaNodeEvent;
+use lumina_node::events::{
+    NodeEvent as LuminaNodeEvent, PeerScoreReason as LuminaPeerScoreReason,
+    SamplingSkipReason as LuminaSamplingSkipReason,
+};
 use std::str::FromStr;
 use uniffi::Record;
 
 #[derive(Record, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
 pub struct PeerId {
     /// The peer ID stored as base58 string.
     pub peer_id: String,
@@ -30,13 +34,71 @@ impl From<Libp2pPeerId> for PeerId {
 }
 
 #[derive(Record)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ShareCoordinate {
     row: u16,
     column: u16,
 }
 
+/// The reason of skipping the sampling of a block.
+#[derive(uniffi::Enum)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum SamplingSkipReason {
+    /// Block is outside of the sampling window.
+    OutOfWindow,
+    /// Block was already sampled and accepted.
+    AlreadySampled,
+    /// Block was pruned before it was sampled.
+    Pruned,
+}
+
+impl From<LuminaSamplingSkipReason> for SamplingSkipReason {
+    fn from(reason: LuminaSamplingSkipReason) -> Self {
+        match reason {
+            LuminaSamplingSkipReason::OutOfWindow => SamplingSkipReason::OutOfWindow,
+            LuminaSamplingSkipReason::AlreadySampled => SamplingSkipReason::AlreadySampled,
+            LuminaSamplingSkipReason::Pruned => SamplingSkipReason::Pruned,
+        }
+    }
+}
+
+/// The reason of changing the score of a peer.
+#[derive(uniffi::Enum)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum PeerScoreReason {
+    /// Peer propagated an invalid gossip message.
+    GossipMisbehavior,
+    /// Peer didn't respond to a request in time.
+    HighLatency,
+    /// Peer delivered the requested headers.
+    HeaderDelivered,
+}
+
+impl From<LuminaPeerScoreReason> for PeerScoreReason {
+    fn from(reason: LuminaPeerScoreReason) -> Self {
+        match reason {
+            LuminaPeerScoreReason::GossipMisbehavior => PeerScoreReason::GossipMisbehavior,
+            LuminaPeerScoreReason::HighLatency => PeerScoreReason::HighLatency,
+            LuminaPeerScoreReason::HeaderDelivered => PeerScoreReason::HeaderDelivered,
+        }
+    }
+}
+
 /// Events emitted by the node.
 #[derive(uniffi::Enum)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
 pub enum NodeEvent {
     /// Node is connecting to bootnodes
     ConnectingToBootnodes,
@@ -47,11 +109,30 @@ pub enum NodeEvent {
         /// Whether peer was in the trusted list or not.
         trusted: bool,
     },
+    /// Peer just disconnected
     PeerDisconnected {
         /// The ID of the peer.
         id: PeerId,
         /// Whether peer was in the trusted list or not.
         trusted: bool,
+        /// The reason of the disconnection, if known.
+        reason: Option<String>,
+    },
+    /// Reconnection to a peer was scheduled.
+    PeerReconnectScheduled {
+        /// The ID of the peer.
+        id: PeerId,
+        /// Delay in milliseconds after which the peer will be dialed again.
+        in_ms: u64,
+    },
+    /// Score of a peer was changed.
+    PeerScoreUpdated {
+        /// The ID of the peer.
+        id: PeerId,
+        /// The new score of the peer.
+        score: i32,
+        /// The reason of the change.
+        reason: PeerScoreReason,
     },
     /// Sampling just started.
     SamplingStarted {
@@ -75,6 +156,17 @@ pub enum NodeEvent {
         /// The result of the sampling of the share.
         accepted: bool,
     },
+    /// Progress of the sampling of a block.
+    SamplingProgress {
+        /// The block height that is being sampled.
+        height: u64,
+        /// How many shares were sampled so far.
+        sampled: u64,
+        /// How many shares will be sampled in total.
+        total: u64,
+        /// The square width of the block.
+        square_width: u16,
+    },
     /// Sampling just finished.
     SamplingFinished {
         /// The block height that was sampled.
@@ -84,6 +176,13 @@ pub enum NodeEvent {
         /// How much time sampling took in milliseconds.
         took_ms: u64,
     },
+    /// Sampling of a block was skipped.
+    SamplingSkipped {
+        /// The block height that was skipped.
+        height: u64,
+        /// The reason of skipping the block.
+        reason: SamplingSkipReason,
+    },
     /// Data sampling fatal error.
     FatalDaserError {
         /// A human readable error.
@@ -164,10 +263,28 @@ impl From<LuminaNodeEvent> for NodeEvent {
                 id: PeerId::from_libp2p(&id),
                 trusted,
             },
-            LuminaNodeEvent::PeerDisconnected { id, trusted } => NodeEvent::PeerDisconnected {
+            LuminaNodeEvent::PeerDisconnected {
+                id,
+                trusted,
+                reason,
+            } => NodeEvent::PeerDisconnected {
                 id: PeerId::from_libp2p(&id),
                 trusted,
+                reason,
             },
+            LuminaNodeEvent::PeerReconnectScheduled { id, delay } => {
+                NodeEvent::PeerReconnectScheduled {
+                    id: PeerId::from_libp2p(&id),
+                    in_ms: delay.as_millis() as u64,
+                }
+            }
+            LuminaNodeEvent::PeerScoreUpdated { id, score, reason } => {
+                NodeEvent::PeerScoreUpdated {
+                    id: PeerId::from_libp2p(&id),
+                    score,
+                    reason: reason.into(),
+                }
+            }
             LuminaNodeEvent::SamplingStarted {
                 height,
                 square_width,
@@ -193,6 +310,17 @@ impl From<LuminaNodeEvent> for NodeEvent {
                 column,
                 accepted,
             },
+            LuminaNodeEvent::SamplingProgress {
+                height,
+                sampled,
+                total,
+                square_width,
+            } => NodeEvent::SamplingProgress {
+                height,
+                sampled,
+                total,
+                square_width,
+            },
             LuminaNodeEvent::SamplingFinished {
                 height,
                 accepted,
@@ -202,6 +330,10 @@ impl From<LuminaNodeEvent> for NodeEvent {
                 accepted,
                 took_ms: took.as_millis() as u64,
             },
+            LuminaNodeEvent::SamplingSkipped { height, reason } => NodeEvent::SamplingSkipped {
+                height,
+                reason: reason.into(),
+            },
             LuminaNodeEvent::FatalDaserError { error } => NodeEvent::FatalDaserError { error },
             LuminaNodeEvent::AddedHeaderFromHeaderSub { height } => {
                 NodeEvent::AddedHeaderFromHeaderSub { height }
@@ -249,3 +381,72 @@ impl From<LuminaNodeEvent> for NodeEvent {
         }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn serialize_sampling_finished() {
+        let event: NodeEvent = LuminaNodeEvent::SamplingFinished {
+            height: 123,
+            accepted: true,
+            took: Duration::from_millis(1500),
+        }
+        .into();
+
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "type": "sampling_finished",
+                "height": 123,
+                "accepted": true,
+                "took_ms": 1500,
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_peer_id_as_string() {
+        let peer_id = Libp2pPeerId::random();
+        let event: NodeEvent = LuminaNodeEvent::PeerConnected {
+            id: peer_id,
+            trusted: false,
+        }
+        .into();
+
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "type": "peer_connected",
+                "id": peer_id.to_string(),
+                "trusted": false,
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_peer_score_down() {
+        let peer_id = Libp2pPeerId::random();
+        let event: NodeEvent = LuminaNodeEvent::PeerScoreUpdated {
+            id: peer_id,
+            score: -20,
+            reason: LuminaPeerScoreReason::GossipMisbehavior,
+        }
+        .into();
+
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "type": "peer_score_updated",
+                "id": peer_id.to_string(),
+                "score": -20,
+                "reason": "gossip_misbehavior",
+            })
+        );
+    }
+}
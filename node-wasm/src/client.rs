@@ -19,8 +19,8 @@ use crate::commands::{CheckableResponseExt, NodeCommand, SingleHeaderQuery};
 use crate::error::{Context, Result};
 use crate::ports::WorkerClient;
 use crate::utils::{
-    is_safari, js_value_from_display, request_storage_persistence, resolve_dnsaddr_multiaddress,
-    timeout, Network,
+    browser_dialable_addrs, is_safari, js_value_from_display, request_storage_persistence,
+    resolve_dnsaddr_multiaddress, timeout, Network,
 };
 use crate::worker::{WasmBlockstore, WasmStore};
 use crate::wrapper::libp2p::NetworkInfoSnapshot;
@@ -279,6 +279,34 @@ impl NodeClient {
         response.into_blobs().check_variant()?
     }
 
+    /// Start watching blobs of the provided namespaces in every new block.
+    ///
+    /// Returns a [`BroadcastChannel`] on which `{ height, namespace, blobs }` objects are
+    /// posted for each watched namespace with blobs in a new block. The same channel is
+    /// shared by all watched namespaces, and namespaces can be added with subsequent calls
+    /// without affecting those already watched.
+    #[wasm_bindgen(js_name = watchNamespaces)]
+    pub async fn watch_namespaces(&self, namespaces: Vec<Namespace>) -> Result<BroadcastChannel> {
+        let command = NodeCommand::WatchNamespaces(namespaces);
+        let response = self.worker.exec(command).await?;
+        let name = response.into_blobs_channel_name().check_variant()?;
+
+        Ok(BroadcastChannel::new(&name).unwrap())
+    }
+
+    /// Stop watching blobs of the provided namespaces.
+    ///
+    /// Other watched namespaces keep being delivered on the channel returned by
+    /// [`NodeClient::watch_namespaces`].
+    #[wasm_bindgen(js_name = unwatchNamespaces)]
+    pub async fn unwatch_namespaces(&self, namespaces: Vec<Namespace>) -> Result<()> {
+        let command = NodeCommand::UnwatchNamespaces(namespaces);
+        let response = self.worker.exec(command).await?;
+        response.into_namespaces_unwatched().check_variant()?;
+
+        Ok(())
+    }
+
     /// Get current header syncing info.
     #[wasm_bindgen(js_name = syncerInfo)]
     pub async fn syncer_info(&self) -> Result<SyncingInfoSnapshot> {
@@ -414,7 +442,7 @@ impl WasmNodeConfig {
                 .parse()
                 .with_context(|| format!("invalid multiaddr: '{addr}"))?;
             let resolved_addrs = resolve_dnsaddr_multiaddress(addr).await?;
-            bootnodes.extend(resolved_addrs.into_iter());
+            bootnodes.extend(browser_dialable_addrs(resolved_addrs));
         }
 
         builder = builder.bootnodes(bootnodes);
@@ -437,6 +465,7 @@ impl WasmNodeConfig {
 mod tests {
     use super::*;
 
+    use std::collections::BTreeMap;
     use std::time::Duration;
 
     use celestia_rpc::{prelude::*, Client, TxConfig};
@@ -445,9 +474,11 @@ mod tests {
     use gloo_timers::future::sleep;
     use libp2p::{multiaddr::Protocol, Multiaddr};
     use rexie::Rexie;
+    use serde_wasm_bindgen::from_value;
+    use tokio::sync::mpsc;
     use wasm_bindgen_futures::spawn_local;
     use wasm_bindgen_test::wasm_bindgen_test;
-    use web_sys::MessageChannel;
+    use web_sys::{MessageChannel, MessageEvent};
 
     use crate::worker::NodeWorker;
 
@@ -533,6 +564,67 @@ mod tests {
         assert_eq!(blob.namespace, namespace);
     }
 
+    #[wasm_bindgen_test]
+    async fn watch_namespaces() {
+        remove_database().await.expect("failed to clear db");
+        let rpc_client = Client::new(WS_URL).await.unwrap();
+        let bridge_ma = fetch_bridge_webtransport_multiaddr(&rpc_client).await;
+        let client = spawn_connected_node(vec![bridge_ma.to_string()]).await;
+
+        let first = Namespace::new_v0(&[0xAB, 0xBA, 0xAB, 0xBA, 0xAB, 0xBA]).unwrap();
+        let second = Namespace::new_v0(&[0xCD, 0xDC, 0xCD, 0xDC, 0xCD, 0xDC]).unwrap();
+        let unwatched = Namespace::new_v0(&[0xBA, 0xAB, 0xBA, 0xAB, 0xBA, 0xAB]).unwrap();
+
+        let channel = client
+            .watch_namespaces(vec![first, unwatched])
+            .await
+            .unwrap();
+        // adding a namespace keeps the already watched ones
+        client.watch_namespaces(vec![second]).await.unwrap();
+        client.unwatch_namespaces(vec![unwatched]).await.unwrap();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let onmessage = Closure::<dyn Fn(MessageEvent)>::new(move |ev: MessageEvent| {
+            let _ = tx.send(ev.data());
+        });
+        channel.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        let blobs = vec![
+            Blob::new(first, b"first".to_vec(), AppVersion::V3).unwrap(),
+            Blob::new(second, b"second".to_vec(), AppVersion::V3).unwrap(),
+            Blob::new(unwatched, b"unwatched".to_vec(), AppVersion::V3).unwrap(),
+        ];
+        let submitted_height = rpc_client
+            .blob_submit(&blobs, TxConfig::default())
+            .await
+            .expect("successful submission");
+
+        let mut received = BTreeMap::new();
+        timeout(30_000, async {
+            while received.len() < 2 {
+                let ev: NamespaceBlobs = from_value(rx.recv().await.unwrap()).unwrap();
+                if ev.height == submitted_height {
+                    assert_ne!(ev.namespace, unwatched);
+                    received.insert(ev.namespace, ev.blobs);
+                }
+            }
+        })
+        .await
+        .expect("blobs of watched namespaces");
+
+        assert_eq!(received[&first].len(), 1);
+        assert_eq!(received[&first][0].data, b"first");
+        assert_eq!(received[&second].len(), 1);
+        assert_eq!(received[&second][0].data, b"second");
+    }
+
+    #[derive(Deserialize)]
+    struct NamespaceBlobs {
+        height: u64,
+        namespace: Namespace,
+        blobs: Vec<Blob>,
+    }
+
     async fn spawn_connected_node(bootnodes: Vec<String>) -> NodeClient {
         let message_channel = MessageChannel::new().unwrap();
         let mut worker = NodeWorker::new(message_channel.port1().into());
@@ -58,6 +58,8 @@ pub(crate) enum NodeCommand {
         namespace: Namespace,
         timeout_secs: Option<f64>,
     },
+    WatchNamespaces(Vec<Namespace>),
+    UnwatchNamespaces(Vec<Namespace>),
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -88,6 +90,8 @@ pub(crate) enum WorkerResponse {
     LastSeenNetworkHead(Result<Option<ExtendedHeader>, Error>),
     SamplingMetadata(Result<Option<SamplingMetadata>>),
     Blobs(Result<Vec<Blob>>),
+    BlobsChannelName(String),
+    NamespacesUnwatched(()),
 }
 
 pub(crate) trait CheckableResponseExt {
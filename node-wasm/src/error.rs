@@ -10,6 +10,50 @@ use wasm_bindgen::{JsCast, JsValue};
 /// Alias for a `Result` with the error type [`Error`].
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Kind of the [`Error`].
+///
+/// It is exposed to JavaScript as a `kind` property of the error, so that
+/// callers can branch on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Network request failed.
+    Network,
+    /// Storage is not available or failed.
+    Storage,
+    /// Data couldn't be parsed or had unexpected format.
+    Parse,
+    /// Any other error.
+    Other,
+}
+
+impl ErrorKind {
+    /// Stable string representation of the kind, as seen from JavaScript.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Network => "network",
+            ErrorKind::Storage => "storage",
+            ErrorKind::Parse => "parse",
+            ErrorKind::Other => "other",
+        }
+    }
+
+    fn from_js_value(value: &JsValue) -> Option<ErrorKind> {
+        match value.as_string()?.as_str() {
+            "network" => Some(ErrorKind::Network),
+            "storage" => Some(ErrorKind::Storage),
+            "parse" => Some(ErrorKind::Parse),
+            "other" => Some(ErrorKind::Other),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// An error that can cross the WASM ABI border.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Error(#[serde(with = "serde_wasm_bindgen::preserve")] JsValue);
@@ -40,13 +84,41 @@ impl Error {
     }
 
     /// Add more context to the `Error`.
+    ///
+    /// The kind of the `Error` is preserved.
     pub fn context<C>(self, context: C) -> Error
     where
         C: Display,
     {
+        let kind = self.kind();
         let e = js_sys::Error::new(&context.to_string());
         e.set_cause(&self.0);
-        Error(e.into())
+        Error(e.into()).with_kind(kind)
+    }
+
+    /// Set the kind of the `Error`.
+    pub fn with_kind(self, kind: ErrorKind) -> Error {
+        let error = if self.0.is_object() {
+            self
+        } else {
+            // Primitive values can't have properties, so wrap them.
+            Error::new(&self.to_string())
+        };
+
+        // Frozen objects can't be modified, there is nothing we can do about them.
+        let _ = js_sys::Reflect::set(&error.0, &"kind".into(), &kind.as_str().into());
+
+        error
+    }
+
+    /// Get the kind of the `Error`.
+    ///
+    /// Returns [`ErrorKind::Other`] if the kind was never set.
+    pub fn kind(&self) -> ErrorKind {
+        js_sys::Reflect::get(&self.0, &"kind".into())
+            .ok()
+            .and_then(|kind| ErrorKind::from_js_value(&kind))
+            .unwrap_or(ErrorKind::Other)
     }
 }
 
@@ -160,6 +232,12 @@ pub trait Context<T> {
     }
 }
 
+/// Utility to set the [`ErrorKind`] of the [`Error`].
+pub trait WithKind<T> {
+    /// Sets the [`ErrorKind`] of the [`Error`].
+    fn with_kind(self, kind: ErrorKind) -> Result<T, Error>;
+}
+
 impl<T, E> Context<T> for Result<T, E>
 where
     E: Into<Error>,
@@ -172,6 +250,15 @@ where
     }
 }
 
+impl<T, E> WithKind<T> for Result<T, E>
+where
+    E: Into<Error>,
+{
+    fn with_kind(self, kind: ErrorKind) -> Result<T, Error> {
+        self.map_err(|e| e.into().with_kind(kind))
+    }
+}
+
 impl<T> Context<T> for Option<T> {
     fn context<C>(self, context: C) -> Result<T, Error>
     where
@@ -180,3 +267,9 @@ impl<T> Context<T> for Option<T> {
         self.ok_or_else(|| Error::new(&context.to_string()))
     }
 }
+
+impl<T> WithKind<T> for Option<T> {
+    fn with_kind(self, kind: ErrorKind) -> Result<T, Error> {
+        self.ok_or_else(|| Error::new("value is missing").with_kind(kind))
+    }
+}
@@ -1,30 +1,37 @@
 //! Various utilities for interacting with node from wasm.
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::{self, Debug};
 use std::future::Future;
+use std::io;
 use std::net::{IpAddr, Ipv4Addr};
+use std::rc::Rc;
+use std::str::FromStr;
 
 use gloo_timers::future::TimeoutFuture;
-use js_sys::{Math, Promise};
+use js_sys::{Date, Math, Promise};
 use libp2p::multiaddr::Protocol;
 use libp2p::{Multiaddr, PeerId};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_repr::{Deserialize_repr, Serialize_repr};
-use tracing::{info, warn};
+use tracing::{info, warn, Level, Metadata};
 use tracing_subscriber::filter::LevelFilter;
 use tracing_subscriber::fmt::time::UtcTime;
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::prelude::*;
-use tracing_web::MakeConsoleWriter;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{
-    DedicatedWorkerGlobalScope, MessageEvent, Request, RequestInit, RequestMode, Response,
+    console, DedicatedWorkerGlobalScope, MessageEvent, Request, RequestInit, RequestMode, Response,
     ServiceWorker, ServiceWorkerGlobalScope, SharedWorker, SharedWorkerGlobalScope, Worker,
 };
 
+use celestia_types::p2p::addr::require_peer_id;
 use lumina_node::network;
 
-use crate::error::{Context, Error, Result};
+use crate::error::{Context, Error, ErrorKind, Result, WithKind};
 
 /// Supported Celestia networks.
 #[wasm_bindgen]
@@ -41,6 +48,89 @@ pub enum Network {
     Private,
 }
 
+/// Console method used to log an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConsoleChannel {
+    Error,
+    Warn,
+    Log,
+    Debug,
+}
+
+impl ConsoleChannel {
+    fn for_level(level: &Level) -> Self {
+        match *level {
+            Level::ERROR => ConsoleChannel::Error,
+            Level::WARN => ConsoleChannel::Warn,
+            Level::INFO => ConsoleChannel::Log,
+            Level::DEBUG | Level::TRACE => ConsoleChannel::Debug,
+        }
+    }
+
+    fn write(self, msg: &str) {
+        let msg = JsValue::from_str(msg);
+
+        match self {
+            ConsoleChannel::Error => console::error_1(&msg),
+            ConsoleChannel::Warn => console::warn_1(&msg),
+            ConsoleChannel::Log => console::log_1(&msg),
+            ConsoleChannel::Debug => console::debug_1(&msg),
+        }
+    }
+}
+
+/// [`MakeWriter`] writing events to the console method matching their level.
+///
+/// This keeps errors and warnings filterable in the browser's devtools.
+struct MakeConsoleWriter;
+
+/// Buffers a single event and writes it to the console when dropped.
+struct ConsoleWriter {
+    channel: ConsoleChannel,
+    buffer: Vec<u8>,
+}
+
+impl<'a> MakeWriter<'a> for MakeConsoleWriter {
+    type Writer = ConsoleWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        ConsoleWriter::new(ConsoleChannel::Log)
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        ConsoleWriter::new(ConsoleChannel::for_level(meta.level()))
+    }
+}
+
+impl ConsoleWriter {
+    fn new(channel: ConsoleChannel) -> Self {
+        ConsoleWriter {
+            channel,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl io::Write for ConsoleWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for ConsoleWriter {
+    fn drop(&mut self) {
+        if !self.buffer.is_empty() {
+            let msg = String::from_utf8_lossy(&self.buffer);
+            self.channel.write(msg.trim_end());
+        }
+    }
+}
+
 /// Set up a logging layer that direct logs to the browser's console.
 #[wasm_bindgen(start)]
 pub fn setup_logging() {
@@ -55,6 +145,39 @@ pub fn setup_logging() {
     tracing_subscriber::registry().with(fmt_layer).init();
 }
 
+impl Network {
+    /// Names accepted when parsing the network.
+    const NAMES: [&'static str; 4] = ["mainnet", "arabica", "mocha", "private"];
+}
+
+impl FromStr for Network {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Network, Error> {
+        // custom network ids are case sensitive, so the private one is matched first
+        if s.eq_ignore_ascii_case("private") {
+            return Ok(Network::Private);
+        }
+
+        s.parse::<network::Network>()
+            .ok()
+            .and_then(|network| network.try_into().ok())
+            .ok_or_else(|| {
+                Error::new(&format!(
+                    "Unknown network '{s}', expected one of: {}",
+                    Network::NAMES.join(", ")
+                ))
+                .with_kind(ErrorKind::Parse)
+            })
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&network::Network::from(*self), f)
+    }
+}
+
 impl From<Network> for network::Network {
     fn from(network: Network) -> network::Network {
         match network {
@@ -76,7 +199,7 @@ impl TryFrom<network::Network> for Network {
             network::Network::Mocha => Ok(Network::Mocha),
             network::Network::Custom(id) => match id.as_ref() {
                 "private" => Ok(Network::Private),
-                _ => Err(Error::new("Unsupported network id: {id}")),
+                _ => Err(Error::new(&format!("Unsupported network id: {id}"))),
             },
         }
     }
@@ -141,7 +264,8 @@ pub(crate) async fn request_storage_persistence() -> Result<(), Error> {
         warn!("ServiceWorker doesn't have access to StorageManager");
         return Ok(());
     } else {
-        return Err(Error::new("`navigator.storage` not found in global scope"));
+        return Err(Error::new("`navigator.storage` not found in global scope")
+            .with_kind(ErrorKind::Storage));
     };
 
     let fullfiled = Closure::once(move |granted: JsValue| {
@@ -156,7 +280,10 @@ pub(crate) async fn request_storage_persistence() -> Result<(), Error> {
     });
 
     // don't drop the promise, we'll log the result and hope the user clicked the right button
-    let _promise = storage_manager.persist()?.then2(&fullfiled, &rejected);
+    let _promise = storage_manager
+        .persist()
+        .with_kind(ErrorKind::Storage)?
+        .then2(&fullfiled, &rejected);
 
     // stop rust from dropping them
     fullfiled.forget();
@@ -222,29 +349,239 @@ extern "C" {
     fn fetch_with_request(input: &Request) -> Promise;
 }
 
-async fn fetch(url: &str, opts: &RequestInit, headers: &[(&str, &str)]) -> Result<Response, Error> {
-    let request = Request::new_with_str_and_init(url, opts)
-        .with_context(|| format!("failed to create a request to {url}"))?;
+/// A builder for HTTP requests issued with the `fetch` api.
+///
+/// `fetch` is resolved from the global scope, so it works the same in the window
+/// and in any kind of worker.
+pub(crate) struct HttpRequest {
+    url: String,
+    init: RequestInit,
+    headers: Vec<(String, String)>,
+}
+
+impl HttpRequest {
+    /// Create a new `GET` request to the given url.
+    pub(crate) fn new(url: impl Into<String>) -> Self {
+        let init = RequestInit::new();
+        init.set_method("GET");
 
-    for (name, value) in headers {
-        request
-            .headers()
-            .set(name, value)
-            .with_context(|| format!("failed setting header: '{name}: {value}'"))?;
+        HttpRequest {
+            url: url.into(),
+            init,
+            headers: Vec::new(),
+        }
     }
 
-    let fetch_promise = fetch_with_request(&request);
+    /// Set the HTTP method of the request.
+    #[cfg(test)]
+    pub(crate) fn method(self, method: &str) -> Self {
+        self.init.set_method(method);
+        self
+    }
 
-    JsFuture::from(fetch_promise)
-        .await
-        .with_context(|| format!("failed fetching {url}"))?
-        .dyn_into()
-        .context("`response` is not `Response` type")
+    /// Add a header to the request.
+    pub(crate) fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the mode of the request.
+    pub(crate) fn mode(self, mode: RequestMode) -> Self {
+        self.init.set_mode(mode);
+        self
+    }
+
+    /// Send the request and return the raw response.
+    pub(crate) async fn send(&self) -> Result<Response> {
+        let url = &self.url;
+        let request = Request::new_with_str_and_init(url, &self.init)
+            .with_context(|| format!("failed to create a request to {url}"))
+            .with_kind(ErrorKind::Network)?;
+
+        for (name, value) in &self.headers {
+            request
+                .headers()
+                .set(name, value)
+                .with_context(|| format!("failed setting header: '{name}: {value}'"))
+                .with_kind(ErrorKind::Network)?;
+        }
+
+        let fetch_promise = fetch_with_request(&request);
+
+        JsFuture::from(fetch_promise)
+            .await
+            .with_context(|| format!("failed fetching {url}"))
+            .with_kind(ErrorKind::Network)?
+            .dyn_into()
+            .context("`response` is not `Response` type")
+            .with_kind(ErrorKind::Parse)
+    }
+
+    /// Send the request and deserialize the response body as json.
+    pub(crate) async fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        let response = self.send().await?;
+
+        let json_promise = response
+            .json()
+            .context("`Response::json()` failed")
+            .with_kind(ErrorKind::Parse)?;
+        let json = JsFuture::from(json_promise)
+            .await
+            .context("failed parsing response as json")
+            .with_kind(ErrorKind::Parse)?;
+
+        serde_wasm_bindgen::from_value(json)
+            .with_context(|| format!("failed deserializing response from {}", self.url))
+            .with_kind(ErrorKind::Parse)
+    }
+
+    /// Send the request and return the response body as text.
+    #[cfg(test)]
+    pub(crate) async fn text(&self) -> Result<String> {
+        let response = self.send().await?;
+
+        let text_promise = response
+            .text()
+            .context("`Response::text()` failed")
+            .with_kind(ErrorKind::Parse)?;
+
+        JsFuture::from(text_promise)
+            .await
+            .context("failed reading response as text")
+            .with_kind(ErrorKind::Parse)?
+            .as_string()
+            .context("response body is not a string")
+            .with_kind(ErrorKind::Parse)
+    }
+}
+
+/// Maximum number of dnsaddr hosts kept in the resolution cache.
+const DNSADDR_CACHE_CAPACITY: usize = 32;
+/// TTL of cached resolution if the dns-over-https response doesn't provide one.
+const DNSADDR_DEFAULT_TTL_SECS: u32 = 5 * 60;
+
+thread_local! {
+    static DNSADDR_CACHE: Rc<RefCell<DnsaddrCache>> =
+        Rc::new(RefCell::new(DnsaddrCache::new(DNSADDR_CACHE_CAPACITY)));
+}
+
+/// Bounded cache of dnsaddr resolutions, keyed by the dnsaddr host.
+struct DnsaddrCache {
+    entries: HashMap<String, DnsaddrCacheEntry>,
+    capacity: usize,
+}
+
+struct DnsaddrCacheEntry {
+    addrs: Vec<Multiaddr>,
+    /// Expiration time in milliseconds since the unix epoch.
+    expires_at: f64,
+}
+
+impl DnsaddrCache {
+    fn new(capacity: usize) -> Self {
+        DnsaddrCache {
+            entries: HashMap::new(),
+            capacity,
+        }
+    }
+
+    fn get(&mut self, host: &str, now: f64) -> Option<Vec<Multiaddr>> {
+        let entry = self.entries.get(host)?;
+
+        if entry.expires_at <= now {
+            self.entries.remove(host);
+            return None;
+        }
+
+        Some(entry.addrs.clone())
+    }
+
+    fn insert(&mut self, host: String, addrs: Vec<Multiaddr>, ttl_secs: u32, now: f64) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if !self.entries.contains_key(&host) && self.entries.len() >= self.capacity {
+            self.entries.retain(|_, entry| entry.expires_at > now);
+        }
+
+        if !self.entries.contains_key(&host) && self.entries.len() >= self.capacity {
+            // evict the entry that would expire first
+            let oldest = self
+                .entries
+                .iter()
+                .min_by(|(_, a), (_, b)| a.expires_at.total_cmp(&b.expires_at))
+                .map(|(host, _)| host.clone());
+
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        let expires_at = now + f64::from(ttl_secs) * 1000.0;
+        self.entries
+            .insert(host, DnsaddrCacheEntry { addrs, expires_at });
+    }
+
+    #[cfg(test)]
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
 }
 
 /// If provided multiaddress uses dnsaddr protocol, resolve it using dns-over-https.
 /// Otherwise returns the provided address.
+///
+/// Resolutions are cached for the TTL of the DNS records.
 pub(crate) async fn resolve_dnsaddr_multiaddress(ma: Multiaddr) -> Result<Vec<Multiaddr>> {
+    let cache = DNSADDR_CACHE.with(Rc::clone);
+    resolve_dnsaddr_with_cache(ma, &cache, query_dnsaddr).await
+}
+
+async fn resolve_dnsaddr_with_cache<F, Fut>(
+    ma: Multiaddr,
+    cache: &RefCell<DnsaddrCache>,
+    query: F,
+) -> Result<Vec<Multiaddr>>
+where
+    F: FnOnce(String) -> Fut,
+    Fut: Future<Output = Result<(Vec<Multiaddr>, Option<u32>)>>,
+{
+    let Some(dnsaddr) = get_dnsaddr(&ma) else {
+        // not a dnsaddr multiaddr
+        return Ok(vec![ma]);
+    };
+    let Some(peer_id) = get_peer_id(&ma) else {
+        return Err(Error::new("Peer id not found").with_kind(ErrorKind::Parse));
+    };
+
+    let cached = cache.borrow_mut().get(&dnsaddr, Date::now());
+
+    let addrs = match cached {
+        Some(addrs) => addrs,
+        None => {
+            let (addrs, ttl) = query(dnsaddr.to_string()).await?;
+            cache.borrow_mut().insert(
+                dnsaddr.to_string(),
+                addrs.clone(),
+                ttl.unwrap_or(DNSADDR_DEFAULT_TTL_SECS),
+                Date::now(),
+            );
+            addrs
+        }
+    };
+
+    // only take results with the same peer id
+    Ok(addrs
+        .into_iter()
+        .filter(|ma| Some(peer_id) == get_peer_id(ma))
+        .collect())
+}
+
+/// Query TXT records of the dnsaddr host using dns-over-https.
+///
+/// Returns all the resolved addresses together with the smallest TTL of the records, if any.
+async fn query_dnsaddr(dnsaddr: String) -> Result<(Vec<Multiaddr>, Option<u32>)> {
     const TXT_TYPE: u16 = 16;
     // cloudflare dns
     const DEFAULT_DNS_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1));
@@ -253,6 +590,8 @@ pub(crate) async fn resolve_dnsaddr_multiaddress(ma: Multiaddr) -> Result<Vec<Mu
     struct DohEntry {
         r#type: u16,
         data: String,
+        #[serde(rename = "TTL")]
+        ttl: Option<u32>,
     }
 
     #[derive(Debug, Deserialize)]
@@ -261,31 +600,17 @@ pub(crate) async fn resolve_dnsaddr_multiaddress(ma: Multiaddr) -> Result<Vec<Mu
         answer: Vec<DohEntry>,
     }
 
-    let Some(dnsaddr) = get_dnsaddr(&ma) else {
-        // not a dnsaddr multiaddr
-        return Ok(vec![ma]);
-    };
-    let Some(peer_id) = get_peer_id(&ma) else {
-        return Err(Error::new("Peer id not found"));
-    };
-
-    let opts = RequestInit::new();
-    opts.set_method("GET");
-    opts.set_mode(RequestMode::Cors);
-
     let url =
         format!("https://{DEFAULT_DNS_ADDR}/dns-query?type={TXT_TYPE}&name=_dnsaddr.{dnsaddr}");
-    let response = fetch(&url, &opts, &[("Accept", "application/dns-json")]).await?;
-
-    let json_promise = response.json().context("`Response::json()` failed")?;
-    let json = JsFuture::from(json_promise)
-        .await
-        .context("failed parsing response as json")?;
-
-    let doh_response: DohResponse = serde_wasm_bindgen::from_value(json)
-        .context("failed deserializing dns-over-https response")?;
+    let doh_response: DohResponse = HttpRequest::new(url)
+        .mode(RequestMode::Cors)
+        .header("Accept", "application/dns-json")
+        .json()
+        .await?;
 
     let mut resolved_addrs = Vec::with_capacity(3);
+    let mut ttl: Option<u32> = None;
+
     for entry in doh_response.answer {
         if entry.r#type == TXT_TYPE {
             // we receive data as json encoded strings in this format:
@@ -299,25 +624,20 @@ pub(crate) async fn resolve_dnsaddr_multiaddress(ma: Multiaddr) -> Result<Vec<Mu
             let Ok(ma) = ma.parse() else {
                 continue;
             };
-            // only take results with the same peer id
-            if Some(peer_id) == get_peer_id(&ma) {
-                // TODO: handle recursive dnsaddr queries
-                resolved_addrs.push(ma);
+            // TODO: handle recursive dnsaddr queries
+            resolved_addrs.push(ma);
+
+            if let Some(entry_ttl) = entry.ttl {
+                ttl = Some(ttl.map_or(entry_ttl, |ttl| ttl.min(entry_ttl)));
             }
         }
     }
 
-    Ok(resolved_addrs)
+    Ok((resolved_addrs, ttl))
 }
 
 fn get_peer_id(ma: &Multiaddr) -> Option<PeerId> {
-    ma.iter().find_map(|protocol| {
-        if let Protocol::P2p(peer_id) = protocol {
-            Some(peer_id)
-        } else {
-            None
-        }
-    })
+    require_peer_id(ma).ok()
 }
 
 fn get_dnsaddr(ma: &Multiaddr) -> Option<Cow<'_, str>> {
@@ -330,6 +650,30 @@ fn get_dnsaddr(ma: &Multiaddr) -> Option<Cow<'_, str>> {
     })
 }
 
+/// Keep only the multiaddrs that can be dialed from the browser.
+///
+/// Browsers can only open websocket and WebTransport connections, so any other
+/// multiaddr (e.g. plain tcp or quic) is dropped and logged. The order is preserved.
+pub(crate) fn browser_dialable_addrs(addrs: Vec<Multiaddr>) -> Vec<Multiaddr> {
+    addrs
+        .into_iter()
+        .filter(|ma| {
+            let dialable = ma.iter().any(|p| {
+                matches!(
+                    p,
+                    Protocol::Ws(_) | Protocol::Wss(_) | Protocol::WebTransport
+                )
+            });
+
+            if !dialable {
+                info!("Dropping {ma}, it is not dialable from the browser");
+            }
+
+            dialable
+        })
+        .collect()
+}
+
 pub(crate) async fn timeout<F: Future>(millis: u32, fut: F) -> Result<F::Output, ()> {
     let timeout = TimeoutFuture::new(millis);
     tokio::select! {
@@ -337,3 +681,186 @@ pub(crate) async fn timeout<F: Future>(millis: u32, fut: F) -> Result<F::Output,
         res = fut => Ok(res),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    const PEER_ID: &str = "12D3KooWGqwzdEqM54Dce6LXzfFr97Bnhvm6rN7KM7MFwdomfm4S";
+
+    #[wasm_bindgen_test]
+    fn browser_dialable_addrs_filter() {
+        let addrs: Vec<Multiaddr> = [
+            "/ip4/127.0.0.1/tcp/2121",
+            "/ip4/127.0.0.1/tcp/2122/ws",
+            "/dns/bridge.example.com/tcp/443/wss",
+            "/ip4/127.0.0.1/udp/2121/quic-v1",
+            "/ip4/127.0.0.1/udp/2121/quic-v1/webtransport",
+            "/ip4/127.0.0.1/udp/2121/webrtc-direct",
+        ]
+        .into_iter()
+        .map(|ma| format!("{ma}/p2p/{PEER_ID}").parse().unwrap())
+        .collect();
+
+        let dialable = browser_dialable_addrs(addrs.clone());
+
+        assert_eq!(
+            dialable,
+            vec![addrs[1].clone(), addrs[2].clone(), addrs[4].clone()]
+        );
+        assert!(browser_dialable_addrs(vec![addrs[0].clone()]).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn console_channel_for_level() {
+        tracing::subscriber::with_default(tracing_subscriber::registry(), || {
+            let span = tracing::error_span!("error");
+            let writer = MakeConsoleWriter.make_writer_for(span.metadata().unwrap());
+            assert_eq!(writer.channel, ConsoleChannel::Error);
+
+            let span = tracing::warn_span!("warn");
+            let writer = MakeConsoleWriter.make_writer_for(span.metadata().unwrap());
+            assert_eq!(writer.channel, ConsoleChannel::Warn);
+
+            let span = tracing::info_span!("info");
+            let writer = MakeConsoleWriter.make_writer_for(span.metadata().unwrap());
+            assert_eq!(writer.channel, ConsoleChannel::Log);
+
+            let span = tracing::trace_span!("trace");
+            let writer = MakeConsoleWriter.make_writer_for(span.metadata().unwrap());
+            assert_eq!(writer.channel, ConsoleChannel::Debug);
+        });
+    }
+
+    #[wasm_bindgen_test]
+    fn parse_network() {
+        for network in [
+            Network::Mainnet,
+            Network::Arabica,
+            Network::Mocha,
+            Network::Private,
+        ] {
+            assert_eq!(network.to_string().parse::<Network>().unwrap(), network);
+        }
+
+        assert_eq!("MOCHA".parse::<Network>().unwrap(), Network::Mocha);
+        assert_eq!("private".parse::<Network>().unwrap(), Network::Private);
+        assert_eq!("Private".parse::<Network>().unwrap(), Network::Private);
+        assert_eq!("PRIVATE".parse::<Network>().unwrap(), Network::Private);
+
+        let err = "foo".parse::<Network>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Parse);
+    }
+
+    async fn resolve_counting(
+        cache: &RefCell<DnsaddrCache>,
+        queries: &Cell<usize>,
+        ttl: Option<u32>,
+    ) -> Vec<Multiaddr> {
+        let ma = format!("/dnsaddr/bootstrap.example.com/p2p/{PEER_ID}")
+            .parse()
+            .unwrap();
+
+        resolve_dnsaddr_with_cache(ma, cache, |host| async move {
+            assert_eq!(host, "bootstrap.example.com");
+            queries.set(queries.get() + 1);
+
+            let addrs = vec![
+                format!("/dns/da-bridge-1.example.com/tcp/2121/p2p/{PEER_ID}")
+                    .parse()
+                    .unwrap(),
+                // different peer id, should be filtered out
+                "/dns/da-bridge-2.example.com/tcp/2121/p2p/12D3KooWE3fmRtHgfk9DCuQFfY3H3JYEnTU3xZozv1Xmo8KWrWbK"
+                    .parse()
+                    .unwrap(),
+            ];
+
+            Ok((addrs, ttl))
+        })
+        .await
+        .unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn dnsaddr_resolution_is_cached() {
+        let cache = RefCell::new(DnsaddrCache::new(DNSADDR_CACHE_CAPACITY));
+        let queries = Cell::new(0);
+
+        let first = resolve_counting(&cache, &queries, Some(60)).await;
+        let second = resolve_counting(&cache, &queries, Some(60)).await;
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(first, second);
+        assert_eq!(queries.get(), 1);
+
+        cache.borrow_mut().clear();
+        resolve_counting(&cache, &queries, Some(60)).await;
+        assert_eq!(queries.get(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    async fn dnsaddr_resolution_expires() {
+        let cache = RefCell::new(DnsaddrCache::new(DNSADDR_CACHE_CAPACITY));
+        let queries = Cell::new(0);
+
+        resolve_counting(&cache, &queries, Some(0)).await;
+        resolve_counting(&cache, &queries, Some(0)).await;
+
+        assert_eq!(queries.get(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn dnsaddr_cache_is_bounded() {
+        let mut cache = DnsaddrCache::new(2);
+
+        cache.insert("a".into(), vec![], 10, 0.0);
+        cache.insert("b".into(), vec![], 20, 0.0);
+        cache.insert("c".into(), vec![], 30, 0.0);
+
+        assert_eq!(cache.entries.len(), 2);
+        assert!(cache.get("a", 0.0).is_none());
+        assert!(cache.get("b", 0.0).is_some());
+        assert!(cache.get("c", 0.0).is_some());
+        assert!(cache.get("b", 20_000.0).is_none());
+    }
+
+    #[wasm_bindgen_test]
+    async fn http_request_get() {
+        let text = HttpRequest::new("data:text/plain,hello")
+            .method("GET")
+            .header("Accept", "text/plain")
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(text, "hello");
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Data {
+            answer: u32,
+        }
+
+        let data: Data = HttpRequest::new(r#"data:application/json,{"answer":42}"#)
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(data, Data { answer: 42 });
+    }
+
+    #[wasm_bindgen_test]
+    async fn fetch_failure_is_network_error() {
+        // nothing listens on port 1
+        let err = HttpRequest::new("http://127.0.0.1:1/")
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Network);
+
+        let js_err = JsValue::from(err);
+        let kind = js_sys::Reflect::get(&js_err, &"kind".into()).unwrap();
+        assert_eq!(kind.as_string().as_deref(), Some("network"));
+    }
+}
@@ -1,14 +1,19 @@
+use std::cell::RefCell;
+use std::collections::BTreeSet;
 use std::fmt::Debug;
+use std::rc::Rc;
 use std::time::Duration;
 
 use blockstore::EitherBlockstore;
 use celestia_types::nmt::Namespace;
 use celestia_types::Blob;
+use futures::future::select;
+use futures::pin_mut;
 use libp2p::{Multiaddr, PeerId};
 use serde::{Deserialize, Serialize};
 use serde_wasm_bindgen::to_value;
 use thiserror::Error;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tracing::{error, info, warn};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
@@ -16,7 +21,7 @@ use web_sys::BroadcastChannel;
 
 use celestia_types::ExtendedHeader;
 use lumina_node::blockstore::{InMemoryBlockstore, IndexedDbBlockstore};
-use lumina_node::events::{EventSubscriber, NodeEventInfo};
+use lumina_node::events::{EventSubscriber, NodeEvent, NodeEventInfo};
 use lumina_node::node::{Node, SyncingInfo};
 use lumina_node::store::{EitherStore, InMemoryStore, IndexedDbStore, SamplingMetadata};
 
@@ -30,6 +35,9 @@ use crate::wrapper::libp2p::NetworkInfoSnapshot;
 pub(crate) type WasmBlockstore = EitherBlockstore<InMemoryBlockstore, IndexedDbBlockstore>;
 pub(crate) type WasmStore = EitherStore<InMemoryStore, IndexedDbStore>;
 
+/// Timeout for retrieving blobs of a single watched namespace.
+const WATCHED_BLOBS_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Serialize, Deserialize, Error)]
 pub enum WorkerError {
     /// Worker is initialised, but the node has not been started yet. Use [`NodeDriver::start`].
@@ -60,8 +68,18 @@ pub struct NodeWorker {
 }
 
 struct NodeWorkerInstance {
-    node: Node<WasmBlockstore, WasmStore>,
+    node: Rc<Node<WasmBlockstore, WasmStore>>,
     events_channel_name: String,
+    blobs_watcher: BlobsWatcher,
+}
+
+/// Handle to the task forwarding blobs of the namespaces watched by the clients
+/// from every new header to a single [`BroadcastChannel`].
+struct BlobsWatcher {
+    namespaces: Rc<RefCell<BTreeSet<Namespace>>>,
+    channel_name: String,
+    stop_tx: oneshot::Sender<()>,
+    stopped_rx: oneshot::Receiver<()>,
 }
 
 #[wasm_bindgen]
@@ -137,14 +155,23 @@ impl NodeWorkerInstance {
 
         spawn_local(event_forwarder_task(events_sub, events_channel));
 
+        let node = Rc::new(node);
+        let blobs_watcher = BlobsWatcher::new(node.clone())?;
+
         Ok(Self {
             node,
             events_channel_name: events_channel_name.to_owned(),
+            blobs_watcher,
         })
     }
 
     async fn stop(self) {
-        self.node.stop().await;
+        self.blobs_watcher.stop().await;
+
+        let Ok(node) = Rc::try_unwrap(self.node) else {
+            unreachable!("node is only shared with the stopped blobs watcher");
+        };
+        node.stop().await;
     }
 
     async fn get_syncer_info(&mut self) -> Result<SyncingInfo> {
@@ -297,11 +324,150 @@ impl NodeWorkerInstance {
                 self.request_all_blobs(header, namespace, timeout_secs)
                     .await,
             ),
+            NodeCommand::WatchNamespaces(namespaces) => {
+                self.blobs_watcher
+                    .namespaces
+                    .borrow_mut()
+                    .extend(namespaces);
+                WorkerResponse::BlobsChannelName(self.blobs_watcher.channel_name.clone())
+            }
+            NodeCommand::UnwatchNamespaces(namespaces) => {
+                let mut watched = self.blobs_watcher.namespaces.borrow_mut();
+                for namespace in &namespaces {
+                    watched.remove(namespace);
+                }
+                WorkerResponse::NamespacesUnwatched(())
+            }
             NodeCommand::InternalPing => WorkerResponse::InternalPong,
         }
     }
 }
 
+impl BlobsWatcher {
+    fn new(node: Rc<Node<WasmBlockstore, WasmStore>>) -> Result<Self> {
+        let channel_name = format!("NamespaceBlobsChannel-{}", random_id());
+        let channel =
+            BroadcastChannel::new(&channel_name).context("Failed to allocate BroadcastChannel")?;
+        let namespaces = Rc::new(RefCell::new(BTreeSet::new()));
+        let (stop_tx, stop_rx) = oneshot::channel();
+        let (stopped_tx, stopped_rx) = oneshot::channel();
+
+        spawn_local(blobs_watcher_task(
+            node,
+            namespaces.clone(),
+            channel,
+            stop_rx,
+            stopped_tx,
+        ));
+
+        Ok(Self {
+            namespaces,
+            channel_name,
+            stop_tx,
+            stopped_rx,
+        })
+    }
+
+    /// Stops the task and waits until it releases the node.
+    async fn stop(self) {
+        let _ = self.stop_tx.send(());
+        // Sender is dropped when the task finishes
+        let _ = self.stopped_rx.await;
+    }
+}
+
+async fn blobs_watcher_task(
+    node: Rc<Node<WasmBlockstore, WasmStore>>,
+    namespaces: Rc<RefCell<BTreeSet<Namespace>>>,
+    channel: BroadcastChannel,
+    stop_rx: oneshot::Receiver<()>,
+    _stopped_tx: oneshot::Sender<()>,
+) {
+    let forward = forward_watched_blobs(node, namespaces, channel);
+    pin_mut!(forward);
+
+    select(stop_rx, forward).await;
+}
+
+async fn forward_watched_blobs(
+    node: Rc<Node<WasmBlockstore, WasmStore>>,
+    namespaces: Rc<RefCell<BTreeSet<Namespace>>>,
+    channel: BroadcastChannel,
+) {
+    let mut events_sub = node.event_subscriber();
+    let mut last_height = None;
+
+    while let Ok(ev) = events_sub.recv().await {
+        let NodeEvent::AddedHeaderFromHeaderSub { height } = ev.event else {
+            continue;
+        };
+
+        // Events are dropped when this task lags behind the event channel, so the heights
+        // between the previous and the current header are forwarded as well.
+        let from = match last_height {
+            Some(last) if last < height => last + 1,
+            _ => height,
+        };
+        last_height = Some(height);
+
+        for height in from..=height {
+            // Snapshot, so namespaces can be added or removed while blobs are fetched
+            // without affecting the rest of the watched set.
+            let watched: Vec<_> = namespaces.borrow().iter().copied().collect();
+
+            if watched.is_empty() {
+                break;
+            }
+
+            let header = match node.get_header_by_height(height).await {
+                Ok(header) => header,
+                Err(e) => {
+                    warn!("Failed to get header {height} for watched namespaces: {e}");
+                    continue;
+                }
+            };
+
+            for namespace in watched {
+                match node
+                    .request_all_blobs(&header, namespace, Some(WATCHED_BLOBS_TIMEOUT))
+                    .await
+                {
+                    Ok(blobs) => post_blobs(&channel, height, namespace, blobs),
+                    Err(e) => warn!("Failed to get blobs of {namespace:?} at {height}: {e}"),
+                }
+            }
+        }
+    }
+}
+
+fn post_blobs(channel: &BroadcastChannel, height: u64, namespace: Namespace, blobs: Vec<Blob>) {
+    #[derive(Serialize)]
+    struct NamespaceBlobs {
+        height: u64,
+        namespace: Namespace,
+        blobs: Vec<Blob>,
+    }
+
+    if blobs.is_empty() {
+        return;
+    }
+
+    let ev = NamespaceBlobs {
+        height,
+        namespace,
+        blobs,
+    };
+
+    match to_value(&ev) {
+        Ok(val) => {
+            if channel.post_message(&val).is_err() {
+                error!("Failed to post blobs of {namespace:?} at {height}");
+            }
+        }
+        Err(e) => error!("Failed to serialize blobs of {namespace:?} at {height}: {e}"),
+    }
+}
+
 async fn event_forwarder_task(mut events_sub: EventSubscriber, events_channel: BroadcastChannel) {
     #[derive(Serialize)]
     struct Event {
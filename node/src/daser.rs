@@ -35,21 +35,26 @@ use std::sync::Arc;
 use futures::future::BoxFuture;
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt, StreamExt};
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use tendermint::Time;
 use tokio::select;
+use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, warn};
 use web_time::{Duration, Instant};
 
-use crate::events::{EventPublisher, NodeEvent};
+use crate::events::{EventPublisher, NodeEvent, SamplingSkipReason};
 use crate::executor::{spawn, JoinHandle};
 use crate::p2p::shwap::sample_cid;
 use crate::p2p::{P2p, P2pError};
 use crate::store::{BlockRanges, SamplingStatus, Store, StoreError};
+use crate::utils::otel;
 
 const MAX_SAMPLES_NEEDED: usize = 16;
 const GET_SAMPLE_TIMEOUT: Duration = Duration::from_secs(10);
+/// How many times the progress of a block's sampling is reported at most.
+const SAMPLING_PROGRESS_STEPS: u64 = 4;
 
 type Result<T, E = DaserError> = std::result::Result<T, E>;
 
@@ -69,6 +74,7 @@ pub enum DaserError {
 pub(crate) struct Daser {
     cancellation_token: CancellationToken,
     join_handle: JoinHandle,
+    paused_tx: watch::Sender<bool>,
 }
 
 /// Arguments used to configure the [`Daser`].
@@ -84,6 +90,8 @@ where
     pub(crate) event_pub: EventPublisher,
     /// Size of the sampling window.
     pub(crate) sampling_window: Duration,
+    /// Seed for selecting the shares to sample, random if not set.
+    pub(crate) sampling_seed: Option<u64>,
 }
 
 impl Daser {
@@ -94,7 +102,8 @@ impl Daser {
     {
         let cancellation_token = CancellationToken::new();
         let event_pub = args.event_pub.clone();
-        let mut worker = Worker::new(args, cancellation_token.child_token())?;
+        let (paused_tx, paused_rx) = watch::channel(false);
+        let mut worker = Worker::new(args, cancellation_token.child_token(), paused_rx)?;
 
         let join_handle = spawn(async move {
             if let Err(e) = worker.run().await {
@@ -109,9 +118,23 @@ impl Daser {
         Ok(Daser {
             cancellation_token,
             join_handle,
+            paused_tx,
         })
     }
 
+    /// Pause or resume the sampling.
+    ///
+    /// While paused, no new blocks are scheduled for sampling. Ongoing
+    /// sampling is allowed to finish.
+    pub(crate) fn set_paused(&self, paused: bool) {
+        self.paused_tx.send_replace(paused);
+    }
+
+    /// Returns true if the sampling is paused.
+    pub(crate) fn is_paused(&self) -> bool {
+        *self.paused_tx.borrow()
+    }
+
     /// Stop the worker.
     pub(crate) fn stop(&self) {
         // Singal the Worker to stop.
@@ -145,13 +168,19 @@ where
     ongoing: BlockRanges,
     prev_head: Option<u64>,
     sampling_window: Duration,
+    sampling_seed: Option<u64>,
+    paused_rx: watch::Receiver<bool>,
 }
 
 impl<S> Worker<S>
 where
     S: Store,
 {
-    fn new(args: DaserArgs<S>, cancellation_token: CancellationToken) -> Result<Worker<S>> {
+    fn new(
+        args: DaserArgs<S>,
+        cancellation_token: CancellationToken,
+        paused_rx: watch::Receiver<bool>,
+    ) -> Result<Worker<S>> {
         Ok(Worker {
             cancellation_token,
             event_pub: args.event_pub,
@@ -164,6 +193,8 @@ where
             ongoing: BlockRanges::default(),
             prev_head: None,
             sampling_window: args.sampling_window,
+            sampling_seed: args.sampling_seed,
+            paused_rx,
         })
     }
 
@@ -214,6 +245,7 @@ where
         debug!("Entering connected_event_loop");
 
         let mut peer_tracker_info_watcher = self.p2p.peer_tracker_info_watcher();
+        let mut paused_watcher = self.paused_rx.clone();
 
         // Check if connection status changed before the watcher was created
         if peer_tracker_info_watcher.borrow().num_connected_peers == 0 {
@@ -231,17 +263,21 @@ where
         self.populate_queue().await?;
 
         loop {
-            // If we have a new HEAD queued, schedule it now!
-            if let Some(queue_head) = self.queue.head() {
-                if queue_head > self.prev_head.unwrap_or(0) {
-                    self.schedule_next_sample_block().await?;
-                    self.prev_head = Some(queue_head);
+            // Blocks are not scheduled while paused, but ongoing sampling
+            // is allowed to finish.
+            if !*paused_watcher.borrow_and_update() {
+                // If we have a new HEAD queued, schedule it now!
+                if let Some(queue_head) = self.queue.head() {
+                    if queue_head > self.prev_head.unwrap_or(0) {
+                        self.schedule_next_sample_block().await?;
+                        self.prev_head = Some(queue_head);
+                    }
                 }
-            }
 
-            // If there is no ongoing data sampling, schedule the next one.
-            if self.sampling_futs.is_empty() {
-                self.schedule_next_sample_block().await?;
+                // If there is no ongoing data sampling, schedule the next one.
+                if self.sampling_futs.is_empty() {
+                    self.schedule_next_sample_block().await?;
+                }
             }
 
             select! {
@@ -272,6 +308,9 @@ where
                     self.ongoing.remove_relaxed(height..=height).expect("invalid height");
                     self.done.insert_relaxed(height..=height).expect("invalid height");
                 },
+                Ok(_) = paused_watcher.changed() => {
+                    // Re-evaluate the scheduling.
+                }
                 _ = &mut wait_new_head => {
                     wait_new_head = store.wait_new_head();
                     self.populate_queue().await?;
@@ -296,8 +335,27 @@ where
             };
 
             match self.store.get_by_height(height).await {
-                Ok(header) => break header,
+                Ok(header) => {
+                    if self.is_accepted(height).await? {
+                        // Block was sampled in the meantime.
+                        self.event_pub.send(NodeEvent::SamplingSkipped {
+                            height,
+                            reason: SamplingSkipReason::AlreadySampled,
+                        });
+                        self.done
+                            .insert_relaxed(height..=height)
+                            .expect("invalid height");
+                        continue;
+                    }
+
+                    break header;
+                }
                 Err(StoreError::NotFound) => {
+                    self.event_pub.send(NodeEvent::SamplingSkipped {
+                        height,
+                        reason: SamplingSkipReason::Pruned,
+                    });
+
                     // Height was pruned and our queue is inconsistent.
                     // Repopulate queue and try again.
                     self.populate_queue().await?;
@@ -313,6 +371,10 @@ where
         if !self.in_sampling_window(header.time()) {
             // As soon as we reach a block that is not in the sampling
             // window, it means the rest wouldn't be either.
+            self.event_pub.send(NodeEvent::SamplingSkipped {
+                height,
+                reason: SamplingSkipReason::OutOfWindow,
+            });
             self.queue
                 .remove_relaxed(1..=height)
                 .expect("invalid height");
@@ -323,7 +385,16 @@ where
         }
 
         // Select random shares to be sampled
-        let share_indexes = random_indexes(square_width, self.max_samples_needed);
+        let share_indexes = match self.sampling_seed {
+            Some(seed) => {
+                let mut rng = sampling_rng(seed, height);
+                random_indexes(square_width, self.max_samples_needed, &mut rng)
+            }
+            None => {
+                let mut rng = rand::thread_rng();
+                random_indexes(square_width, self.max_samples_needed, &mut rng)
+            }
+        };
 
         // Update the CID list before we start sampling, otherwise it's possible for us
         // to leak CIDs causing associated blocks to never get cleaned from blockstore.
@@ -343,7 +414,7 @@ where
         let event_pub = self.event_pub.clone();
 
         // Schedule retrival of the CIDs. This will be run later on in the `select!` loop.
-        let fut = async move {
+        let fut = otel::sampling(height, square_width, async move {
             let now = Instant::now();
 
             event_pub.send(NodeEvent::SamplingStarted {
@@ -352,6 +423,9 @@ where
                 shares: share_indexes.iter().copied().collect(),
             });
 
+            let total = share_indexes.len() as u64;
+            let mut sampled = 0;
+
             // Initialize all futures
             let mut futs = share_indexes
                 .into_iter()
@@ -390,6 +464,20 @@ where
                     column,
                     accepted: share_accepted,
                 });
+
+                sampled += 1;
+
+                // report only when the next step of the progress is reached
+                if sampled * SAMPLING_PROGRESS_STEPS / total
+                    > (sampled - 1) * SAMPLING_PROGRESS_STEPS / total
+                {
+                    event_pub.send(NodeEvent::SamplingProgress {
+                        height,
+                        sampled,
+                        total,
+                        square_width,
+                    });
+                }
             }
 
             event_pub.send(NodeEvent::SamplingFinished {
@@ -399,7 +487,7 @@ where
             });
 
             Ok((height, block_accepted))
-        }
+        })
         .boxed();
 
         self.sampling_futs.push(fut);
@@ -425,6 +513,13 @@ where
         Ok(())
     }
 
+    /// Returns true if the block was already sampled and accepted.
+    async fn is_accepted(&self, height: u64) -> Result<bool> {
+        let metadata = self.store.get_sampling_metadata(height).await?;
+
+        Ok(metadata.is_some_and(|metadata| metadata.status == SamplingStatus::Accepted))
+    }
+
     /// Returns true if `time` is within the sampling window.
     fn in_sampling_window(&self, time: Time) -> bool {
         let now = Time::now();
@@ -442,8 +537,23 @@ where
     }
 }
 
+/// Returns a deterministic RNG for selecting the shares of the block at `height`.
+fn sampling_rng(seed: u64, height: u64) -> StdRng {
+    let mut rng_seed = [0u8; 32];
+    rng_seed[..8].copy_from_slice(&seed.to_le_bytes());
+    rng_seed[8..16].copy_from_slice(&height.to_le_bytes());
+    StdRng::from_seed(rng_seed)
+}
+
 /// Returns unique and random indexes that will be used for sampling.
-fn random_indexes(square_width: u16, max_samples_needed: usize) -> HashSet<(u16, u16)> {
+fn random_indexes<R>(
+    square_width: u16,
+    max_samples_needed: usize,
+    rng: &mut R,
+) -> HashSet<(u16, u16)>
+where
+    R: Rng,
+{
     let samples_in_block = usize::from(square_width).pow(2);
 
     // If block size is smaller than `max_samples_needed`, we are going
@@ -455,7 +565,6 @@ fn random_indexes(square_width: u16, max_samples_needed: usize) -> HashSet<(u16,
     }
 
     let mut indexes = HashSet::with_capacity(max_samples_needed);
-    let mut rng = rand::thread_rng();
 
     while indexes.len() < max_samples_needed {
         let row = rng.gen::<u16>() % square_width;
@@ -504,6 +613,7 @@ mod tests {
             p2p: Arc::new(mock),
             store: store.clone(),
             sampling_window: DEFAULT_SAMPLING_WINDOW,
+            sampling_seed: None,
         })
         .unwrap();
 
@@ -531,6 +641,7 @@ mod tests {
             p2p: Arc::new(mock),
             store: store.clone(),
             sampling_window: DEFAULT_SAMPLING_WINDOW,
+            sampling_seed: None,
         })
         .unwrap();
 
@@ -556,6 +667,7 @@ mod tests {
             p2p: Arc::new(mock),
             store: store.clone(),
             sampling_window: DEFAULT_SAMPLING_WINDOW,
+            sampling_seed: None,
         })
         .unwrap();
 
@@ -654,6 +766,99 @@ mod tests {
         handle.expect_no_cmd().await;
     }
 
+    #[async_test]
+    async fn pause_and_resume() {
+        let (mock, mut handle) = P2p::mocked();
+        let store = Arc::new(InMemoryStore::new());
+        let events = EventChannel::new();
+
+        let daser = Daser::start(DaserArgs {
+            event_pub: events.publisher(),
+            p2p: Arc::new(mock),
+            store: store.clone(),
+            sampling_window: DEFAULT_SAMPLING_WINDOW,
+            sampling_seed: None,
+        })
+        .unwrap();
+
+        let mut gen = ExtendedHeaderGenerator::new();
+
+        handle.expect_no_cmd().await;
+        handle.announce_peer_connected();
+        handle.expect_no_cmd().await;
+
+        daser.set_paused(true);
+        assert!(daser.is_paused());
+
+        let eds = generate_dummy_eds(2, AppVersion::V2);
+        let dah = DataAvailabilityHeader::from_eds(&eds);
+        let header = gen.next_with_dah(dah);
+        store.insert(header).await.unwrap();
+
+        // Nothing is sampled while paused
+        handle.expect_no_cmd().await;
+
+        daser.set_paused(false);
+        assert!(!daser.is_paused());
+
+        handle_get_shwap_cid(&mut handle, 1, &eds, false).await;
+        handle.expect_no_cmd().await;
+    }
+
+    #[async_test]
+    async fn skip_blocks_outside_of_sampling_window() {
+        let (mock, mut handle) = P2p::mocked();
+        let store = Arc::new(InMemoryStore::new());
+        let events = EventChannel::new();
+        let mut event_sub = events.subscribe();
+
+        let _daser = Daser::start(DaserArgs {
+            event_pub: events.publisher(),
+            p2p: Arc::new(mock),
+            store: store.clone(),
+            sampling_window: Duration::from_secs(60),
+            sampling_seed: None,
+        })
+        .unwrap();
+
+        let mut gen = ExtendedHeaderGenerator::new();
+        let old_time = (Time::now() - Duration::from_secs(60 * 60)).unwrap();
+        gen.set_time(old_time, Duration::from_secs(1));
+        store.insert(gen.next_many(5)).await.unwrap();
+
+        handle.expect_no_cmd().await;
+        handle.announce_peer_connected();
+
+        // Nothing is sampled
+        handle.expect_no_cmd().await;
+
+        // Skipping the head means skipping all the blocks below it
+        match event_sub.try_recv().unwrap().event {
+            NodeEvent::SamplingSkipped { height, reason } => {
+                assert_eq!(height, 5);
+                assert_eq!(reason, SamplingSkipReason::OutOfWindow);
+            }
+            ev => panic!("Unexpected event: {ev}"),
+        }
+
+        assert!(event_sub.try_recv().is_err());
+    }
+
+    #[test]
+    fn seeded_random_indexes() {
+        let indexes = random_indexes(128, MAX_SAMPLES_NEEDED, &mut sampling_rng(42, 10));
+        assert_eq!(indexes.len(), MAX_SAMPLES_NEEDED);
+
+        let same_seed = random_indexes(128, MAX_SAMPLES_NEEDED, &mut sampling_rng(42, 10));
+        assert_eq!(indexes, same_seed);
+
+        let other_height = random_indexes(128, MAX_SAMPLES_NEEDED, &mut sampling_rng(42, 11));
+        assert_ne!(indexes, other_height);
+
+        let other_seed = random_indexes(128, MAX_SAMPLES_NEEDED, &mut sampling_rng(43, 10));
+        assert_ne!(indexes, other_seed);
+    }
+
     async fn gen_and_sample_block(
         handle: &mut MockP2pHandle,
         gen: &mut ExtendedHeaderGenerator,
@@ -707,8 +912,14 @@ mod tests {
             ev => panic!("Unexpected event: {ev}"),
         };
 
-        // Check if we received `ShareSamplingResult` for each share
-        for i in 1..=remaining_shares.len() {
+        let total = remaining_shares.len() as u64;
+        let mut last_sampled = 0;
+        let mut progress_reports = 0;
+        let mut results = 0;
+
+        // Check if we received `ShareSamplingResult` for each share, with the progress
+        // reported in between
+        while results < total || last_sampled < total {
             match event_sub.try_recv().unwrap().event {
                 NodeEvent::ShareSamplingResult {
                     height: ev_height,
@@ -717,19 +928,38 @@ mod tests {
                     column,
                     accepted,
                 } => {
+                    results += 1;
                     assert_eq!(ev_height, height);
                     assert_eq!(square_width, eds.square_width());
                     assert_eq!(
                         accepted,
-                        !(simulate_invalid_sampling && i == INVALID_SHARE_REQ_NUM)
+                        !(simulate_invalid_sampling && results == INVALID_SHARE_REQ_NUM as u64)
                     );
                     // Make sure it is in the list and remove it
                     assert!(remaining_shares.remove(&(row, column)));
                 }
+                NodeEvent::SamplingProgress {
+                    height: ev_height,
+                    sampled,
+                    total: ev_total,
+                    square_width,
+                } => {
+                    assert_eq!(ev_height, height);
+                    assert_eq!(square_width, eds.square_width());
+                    assert_eq!(ev_total, total);
+                    assert_eq!(sampled, results);
+                    assert!(sampled > last_sampled);
+                    last_sampled = sampled;
+                    progress_reports += 1;
+                }
                 ev => panic!("Unexpected event: {ev}"),
             }
         }
 
+        // Progress is throttled instead of being reported for each share
+        assert!(progress_reports <= SAMPLING_PROGRESS_STEPS);
+        assert_eq!(last_sampled, total);
+
         assert!(remaining_shares.is_empty());
 
         // Check if we received `SamplingFinished` for each share
@@ -181,6 +181,28 @@ pub enum NodeEvent {
         id: PeerId,
         /// Whether peer was in the trusted list or not.
         trusted: bool,
+        /// The reason of the disconnection, if known.
+        reason: Option<String>,
+    },
+
+    /// Reconnection to a peer was scheduled.
+    PeerReconnectScheduled {
+        #[serde(serialize_with = "serialize_as_string")]
+        /// The ID of the peer.
+        id: PeerId,
+        /// Delay after which the peer will be dialed again.
+        delay: Duration,
+    },
+
+    /// Score of a peer was changed.
+    PeerScoreUpdated {
+        #[serde(serialize_with = "serialize_as_string")]
+        /// The ID of the peer.
+        id: PeerId,
+        /// The new score of the peer.
+        score: i32,
+        /// The reason of the change.
+        reason: PeerScoreReason,
     },
 
     /// Sampling just started.
@@ -207,6 +229,20 @@ pub enum NodeEvent {
         accepted: bool,
     },
 
+    /// Progress of the sampling of a block.
+    ///
+    /// Reported in a few steps during the sampling, rather than for each share.
+    SamplingProgress {
+        /// The block height that is being sampled.
+        height: u64,
+        /// How many shares were sampled so far.
+        sampled: u64,
+        /// How many shares will be sampled in total.
+        total: u64,
+        /// The square width of the block.
+        square_width: u16,
+    },
+
     /// Sampling just finished.
     SamplingFinished {
         /// The block height that was sampled.
@@ -217,6 +253,14 @@ pub enum NodeEvent {
         took: Duration,
     },
 
+    /// Sampling of a block was skipped.
+    SamplingSkipped {
+        /// The block height that was skipped.
+        height: u64,
+        /// The reason of skipping the block.
+        reason: SamplingSkipReason,
+    },
+
     /// Data sampling fatal error.
     FatalDaserError {
         /// A human readable error.
@@ -300,6 +344,52 @@ pub enum NodeEvent {
     NodeStopped,
 }
 
+/// The reason of skipping the sampling of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SamplingSkipReason {
+    /// Block is outside of the sampling window.
+    ///
+    /// When this happens all the blocks with a lower height are skipped too.
+    OutOfWindow,
+    /// Block was already sampled and accepted.
+    AlreadySampled,
+    /// Block was pruned before it was sampled.
+    Pruned,
+}
+
+impl fmt::Display for SamplingSkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SamplingSkipReason::OutOfWindow => write!(f, "outside of the sampling window"),
+            SamplingSkipReason::AlreadySampled => write!(f, "already sampled"),
+            SamplingSkipReason::Pruned => write!(f, "pruned"),
+        }
+    }
+}
+
+/// The reason of changing the score of a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerScoreReason {
+    /// Peer propagated an invalid gossip message.
+    GossipMisbehavior,
+    /// Peer didn't respond to a request in time.
+    HighLatency,
+    /// Peer delivered the requested headers.
+    HeaderDelivered,
+}
+
+impl fmt::Display for PeerScoreReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PeerScoreReason::GossipMisbehavior => write!(f, "gossip misbehavior"),
+            PeerScoreReason::HighLatency => write!(f, "high latency"),
+            PeerScoreReason::HeaderDelivered => write!(f, "header delivered"),
+        }
+    }
+}
+
 impl NodeEvent {
     /// Returns `true` if the event indicates an error.
     pub fn is_error(&self) -> bool {
@@ -312,9 +402,13 @@ impl NodeEvent {
             NodeEvent::ConnectingToBootnodes
             | NodeEvent::PeerConnected { .. }
             | NodeEvent::PeerDisconnected { .. }
+            | NodeEvent::PeerReconnectScheduled { .. }
+            | NodeEvent::PeerScoreUpdated { .. }
             | NodeEvent::SamplingStarted { .. }
             | NodeEvent::ShareSamplingResult { .. }
+            | NodeEvent::SamplingProgress { .. }
             | NodeEvent::SamplingFinished { .. }
+            | NodeEvent::SamplingSkipped { .. }
             | NodeEvent::AddedHeaderFromHeaderSub { .. }
             | NodeEvent::FetchingHeadHeaderStarted
             | NodeEvent::FetchingHeadHeaderFinished { .. }
@@ -339,12 +433,28 @@ impl fmt::Display for NodeEvent {
                     write!(f, "Peer connected: {id}")
                 }
             }
-            NodeEvent::PeerDisconnected { id, trusted } => {
+            NodeEvent::PeerDisconnected {
+                id,
+                trusted,
+                reason,
+            } => {
                 if *trusted {
-                    write!(f, "Trusted peer disconnected: {id}")
+                    write!(f, "Trusted peer disconnected: {id}")?;
                 } else {
-                    write!(f, "Peer disconnected: {id}")
+                    write!(f, "Peer disconnected: {id}")?;
+                }
+
+                if let Some(reason) = reason {
+                    write!(f, ", reason: {reason}")?;
                 }
+
+                Ok(())
+            }
+            NodeEvent::PeerReconnectScheduled { id, delay } => {
+                write!(f, "Reconnecting to peer {id} in {delay:?}")
+            }
+            NodeEvent::PeerScoreUpdated { id, score, reason } => {
+                write!(f, "Score of peer {id} changed to {score}, reason: {reason}")
             }
             NodeEvent::SamplingStarted {
                 height,
@@ -366,9 +476,20 @@ impl fmt::Display for NodeEvent {
                     "Sampling for share [{row}, {column}] of block {height} was {acc}"
                 )
             }
+            NodeEvent::SamplingProgress {
+                height,
+                sampled,
+                total,
+                ..
+            } => {
+                write!(f, "Sampling of block {height}: {sampled}/{total} shares")
+            }
             NodeEvent::SamplingFinished { height, took, .. } => {
                 write!(f, "Sampling of block {height} finished. Took: {took:?}")
             }
+            NodeEvent::SamplingSkipped { height, reason } => {
+                write!(f, "Sampling of block {height} skipped: {reason}")
+            }
             NodeEvent::FatalDaserError { error } => {
                 write!(f, "Daser stopped because of a fatal error: {error}")
             }
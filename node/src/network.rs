@@ -3,7 +3,10 @@
 use std::fmt;
 use std::ops::Deref;
 use std::str::FromStr;
+use std::time::Duration;
 
+use celestia_types::hash::Hash;
+use celestia_types::{NetworkParams, TrustedHeader};
 use libp2p::Multiaddr;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -75,6 +78,55 @@ impl fmt::Display for NetworkId {
     }
 }
 
+/// Configuration of a private network, e.g. a local devnet.
+///
+/// Can be passed to [`NodeBuilder::private_network`] to configure the node
+/// for a network that isn't one of the canonical Celestia networks.
+///
+/// [`NodeBuilder::private_network`]: crate::NodeBuilder::private_network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivateNetworkConfig {
+    /// Id of the chain.
+    pub chain_id: NetworkId,
+    /// Hash of the genesis block, if known.
+    ///
+    /// When set, the node refuses to store a genesis header with a different hash.
+    pub genesis_hash: Option<Hash>,
+    /// Bootstrap nodes of the network.
+    pub bootnodes: Vec<Multiaddr>,
+    /// Window of time in which the blocks are expected to be sampled and available.
+    ///
+    /// If not set, the default of the [`NodeBuilder`] applies.
+    ///
+    /// [`NodeBuilder`]: crate::NodeBuilder
+    pub sampling_window: Option<Duration>,
+}
+
+impl PrivateNetworkConfig {
+    /// Creates a new configuration with the given chain id.
+    pub fn new(chain_id: NetworkId) -> Self {
+        PrivateNetworkConfig {
+            chain_id,
+            genesis_hash: None,
+            bootnodes: Vec::new(),
+            sampling_window: None,
+        }
+    }
+
+    /// Get the [`Network`] of this configuration.
+    pub fn network(&self) -> Network {
+        Network::Custom(self.chain_id.clone())
+    }
+
+    /// Get the genesis header of the network as a trust anchor.
+    ///
+    /// Returns `None` if the genesis hash is not known.
+    pub fn genesis_anchor(&self) -> Option<TrustedHeader> {
+        self.genesis_hash
+            .map(|hash| TrustedHeader { height: 1, hash })
+    }
+}
+
 impl Network {
     /// Creates a `Network::Custom` value.
     pub fn custom(id: &str) -> Result<Network, InvalidNetworkId> {
@@ -96,6 +148,18 @@ impl Network {
         }
     }
 
+    /// Get the parameters of the network.
+    ///
+    /// Returns `None` for the `Network::Custom` variant.
+    pub fn params(&self) -> Option<NetworkParams> {
+        match self {
+            Network::Mainnet => Some(NetworkParams::MAINNET),
+            Network::Arabica => Some(NetworkParams::ARABICA),
+            Network::Mocha => Some(NetworkParams::MOCHA),
+            Network::Custom(_) => None,
+        }
+    }
+
     /// Get official Celestia and Lumina bootnodes for the given network.
     pub fn canonical_bootnodes(&self) -> impl Iterator<Item = Multiaddr> {
         let peers: &[_] = match self {
@@ -138,11 +202,16 @@ impl FromStr for Network {
     type Err = InvalidNetworkId;
 
     fn from_str(value: &str) -> Result<Self, InvalidNetworkId> {
-        match value {
-            "Mainnet" | "MainNet" | "mainnet" | "celestia" => Ok(Network::Mainnet),
-            "Arabica" | "arabica" | "arabica-11" => Ok(Network::Arabica),
-            "Mocha" | "mocha" | "mocha-4" => Ok(Network::Mocha),
-            custom_id => Network::custom(custom_id),
+        let is = |name: &str| value.eq_ignore_ascii_case(name);
+
+        if is("mainnet") || is("celestia") {
+            Ok(Network::Mainnet)
+        } else if is("arabica") || is("arabica-11") {
+            Ok(Network::Arabica)
+        } else if is("mocha") || is("mocha-4") {
+            Ok(Network::Mocha)
+        } else {
+            Network::custom(value)
         }
     }
 }
@@ -224,6 +293,50 @@ mod tests {
         assert_eq!(private.count(), 0);
     }
 
+    #[test]
+    fn test_network_params() {
+        for network in [Network::Mainnet, Network::Arabica, Network::Mocha] {
+            let params = network.params().unwrap();
+            assert!(!params.chain_id.is_empty());
+            assert_eq!(params.chain_id, network.id());
+        }
+
+        let private = Network::custom("private").unwrap();
+        assert!(private.params().is_none());
+    }
+
+    #[test]
+    fn parse_network() {
+        for network in [Network::Mainnet, Network::Arabica, Network::Mocha] {
+            assert_eq!(network.to_string().parse::<Network>().unwrap(), network);
+            assert_eq!(network.id().parse::<Network>().unwrap(), network);
+        }
+
+        assert_eq!("MAINNET".parse::<Network>().unwrap(), Network::Mainnet);
+        assert_eq!("arabica".parse::<Network>().unwrap(), Network::Arabica);
+        assert_eq!("Mocha-4".parse::<Network>().unwrap(), Network::Mocha);
+
+        let private = "private".parse::<Network>().unwrap();
+        assert_eq!(private, Network::custom("private").unwrap());
+        assert_eq!(private.to_string(), "private");
+
+        "foo/bar".parse::<Network>().unwrap_err();
+    }
+
+    #[test]
+    fn private_network_config() {
+        let mut config = PrivateNetworkConfig::new(NetworkId::new("private").unwrap());
+        assert_eq!(config.network(), Network::custom("private").unwrap());
+        assert!(config.genesis_anchor().is_none());
+
+        let hash = Hash::Sha256([1; 32]);
+        config.genesis_hash = Some(hash);
+
+        let anchor = config.genesis_anchor().unwrap();
+        assert_eq!(anchor.height, 1);
+        assert_eq!(anchor.hash, hash);
+    }
+
     #[test]
     fn check_network_id() {
         Network::custom("foo").unwrap();
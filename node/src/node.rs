@@ -11,7 +11,7 @@ use blockstore::Blockstore;
 use celestia_types::hash::Hash;
 use celestia_types::nmt::Namespace;
 use celestia_types::row::Row;
-use celestia_types::row_namespace_data::RowNamespaceData;
+use celestia_types::row_namespace_data::{NamespaceData, RowNamespaceData};
 use celestia_types::sample::Sample;
 use celestia_types::{Blob, ExtendedHeader};
 use libp2p::identity::Keypair;
@@ -37,8 +37,11 @@ pub use self::builder::{
     MIN_PRUNING_DELAY, MIN_SAMPLING_WINDOW,
 };
 pub use crate::daser::DaserError;
-pub use crate::p2p::{HeaderExError, P2pError};
-pub use crate::peer_tracker::PeerTrackerInfo;
+pub use crate::p2p::{
+    HeaderExError, P2pError, ShrexNdError, TransportLimits, DEFAULT_MAX_CONCURRENT_SUBSTREAMS,
+    DEFAULT_MAX_ESTABLISHED_PER_PEER, DEFAULT_MAX_PENDING_INCOMING,
+};
+pub use crate::peer_tracker::{PeerDetails, PeerTrackerInfo};
 pub use crate::syncer::{SyncerError, SyncingInfo};
 
 /// Alias of [`Result`] with [`NodeError`] error type
@@ -78,12 +81,16 @@ where
     pub(crate) blockstore: B,
     pub(crate) store: S,
     pub(crate) network_id: String,
+    pub(crate) genesis_hash: Option<Hash>,
     pub(crate) p2p_local_keypair: Keypair,
     pub(crate) p2p_bootnodes: Vec<Multiaddr>,
     pub(crate) p2p_listen_on: Vec<Multiaddr>,
     pub(crate) sync_batch_size: u64,
     pub(crate) sampling_window: Duration,
+    pub(crate) sampling_seed: Option<u64>,
     pub(crate) pruning_window: Duration,
+    pub(crate) header_ex_request_timeout: Duration,
+    pub(crate) transport_limits: TransportLimits,
 }
 
 /// Celestia node.
@@ -149,6 +156,8 @@ where
                 blockstore: blockstore.clone(),
                 store: store.clone(),
                 event_pub: event_channel.publisher(),
+                header_ex_request_timeout: config.header_ex_request_timeout,
+                transport_limits: config.transport_limits,
             })
             .await?,
         );
@@ -161,6 +170,7 @@ where
             // We sync only what we need to sample. So syncing_window is
             // the same as sampling_window.
             syncing_window: config.sampling_window,
+            genesis_hash: config.genesis_hash,
         })?);
 
         let daser = Arc::new(Daser::start(DaserArgs {
@@ -168,6 +178,7 @@ where
             store: store.clone(),
             event_pub: event_channel.publisher(),
             sampling_window: config.sampling_window,
+            sampling_seed: config.sampling_seed,
         })?);
 
         let pruner = Arc::new(Pruner::start(PrunerArgs {
@@ -265,6 +276,10 @@ where
         self.syncer.as_ref().expect("Syncer not initialized")
     }
 
+    fn daser(&self) -> &Daser {
+        self.daser.as_ref().expect("Daser not initialized")
+    }
+
     fn p2p(&self) -> &P2p {
         self.p2p.as_ref().expect("P2p not initialized")
     }
@@ -318,6 +333,11 @@ where
         Ok(self.p2p().connected_peers().await?)
     }
 
+    /// Get details of all known peers, including their addresses and supported protocols.
+    pub async fn peers(&self) -> Result<Vec<PeerDetails>> {
+        Ok(self.p2p().peers().await?)
+    }
+
     /// Trust or untrust the peer with a given ID.
     pub async fn set_peer_trust(&self, peer_id: PeerId, is_trusted: bool) -> Result<()> {
         Ok(self.p2p().set_peer_trust(peer_id, is_trusted).await?)
@@ -413,11 +433,46 @@ where
         Ok(self.p2p().get_all_blobs(header, namespace, timeout).await?)
     }
 
+    /// Request all the verified [`RowNamespaceData`] of the namespace in the block
+    /// corresponding to this header using `shrex/nd` protocol.
+    pub async fn request_namespace_data(
+        &self,
+        header: &ExtendedHeader,
+        namespace: Namespace,
+    ) -> Result<NamespaceData> {
+        Ok(self
+            .p2p()
+            .get_namespace_data_shrex(header, namespace)
+            .await?)
+    }
+
     /// Get current header syncing info.
     pub async fn syncer_info(&self) -> Result<SyncingInfo> {
         Ok(self.syncer().info().await?)
     }
 
+    /// Pause header synchronization and data sampling.
+    ///
+    /// Ongoing requests are allowed to finish, but no new ones are made
+    /// until [`Node::resume`] is called. P2P networking is still active.
+    pub async fn pause(&self) -> Result<()> {
+        self.syncer().set_paused(true).await?;
+        self.daser().set_paused(true);
+        Ok(())
+    }
+
+    /// Resume header synchronization and data sampling paused by [`Node::pause`].
+    pub async fn resume(&self) -> Result<()> {
+        self.syncer().set_paused(false).await?;
+        self.daser().set_paused(false);
+        Ok(())
+    }
+
+    /// Returns true if the node is paused.
+    pub fn is_paused(&self) -> bool {
+        self.daser().is_paused()
+    }
+
     /// Get the latest header announced in the network.
     pub async fn get_network_head_header(&self) -> Result<Option<ExtendedHeader>> {
         Ok(self.p2p().get_network_head().await?)
@@ -2,21 +2,25 @@ use std::any::TypeId;
 use std::time::Duration;
 
 use blockstore::Blockstore;
+use celestia_types::hash::Hash;
+use celestia_types::NetworkParams;
 use libp2p::identity::Keypair;
 use libp2p::Multiaddr;
 use tracing::{info, warn};
 
 use crate::blockstore::InMemoryBlockstore;
 use crate::events::EventSubscriber;
-use crate::network::Network;
-use crate::node::{Node, NodeConfig, Result};
+use crate::network::{Network, PrivateNetworkConfig};
+use crate::node::{Node, NodeConfig, Result, TransportLimits};
+use crate::p2p::DEFAULT_HEADER_EX_REQUEST_TIMEOUT;
 use crate::store::{InMemoryStore, Store};
 
 const HOUR: u64 = 60 * 60;
-const DAY: u64 = 24 * HOUR;
 
 /// Default maximum age of blocks [`Node`] will synchronise, sample, and store.
-pub const DEFAULT_SAMPLING_WINDOW: Duration = Duration::from_secs(30 * DAY);
+///
+/// Same as the sampling window of the canonical networks.
+pub const DEFAULT_SAMPLING_WINDOW: Duration = NetworkParams::MAINNET.sampling_window;
 /// Minimum configurable sampling window that can be used in [`NodeBuilder`].
 pub const MIN_SAMPLING_WINDOW: Duration = Duration::from_secs(60);
 
@@ -35,11 +39,15 @@ where
     store: S,
     keypair: Option<Keypair>,
     network: Option<Network>,
+    genesis_hash: Option<Hash>,
     bootnodes: Vec<Multiaddr>,
     listen: Vec<Multiaddr>,
     sync_batch_size: Option<u64>,
     sampling_window: Option<Duration>,
+    sampling_seed: Option<u64>,
     pruning_delay: Option<Duration>,
+    header_ex_request_timeout: Option<Duration>,
+    transport_limits: Option<TransportLimits>,
 }
 
 /// Representation of all the errors that can occur when interacting with the [`NodeBuilder`].
@@ -84,11 +92,15 @@ impl NodeBuilder<InMemoryBlockstore, InMemoryStore> {
             store: InMemoryStore::new(),
             keypair: None,
             network: None,
+            genesis_hash: None,
             bootnodes: Vec::new(),
             listen: Vec::new(),
             sync_batch_size: None,
             sampling_window: None,
+            sampling_seed: None,
             pruning_delay: None,
+            header_ex_request_timeout: None,
+            transport_limits: None,
         }
     }
 }
@@ -131,11 +143,15 @@ where
             store: self.store,
             keypair: self.keypair,
             network: self.network,
+            genesis_hash: self.genesis_hash,
             bootnodes: self.bootnodes,
             listen: self.listen,
             sync_batch_size: self.sync_batch_size,
             sampling_window: self.sampling_window,
+            sampling_seed: self.sampling_seed,
             pruning_delay: self.pruning_delay,
+            header_ex_request_timeout: self.header_ex_request_timeout,
+            transport_limits: self.transport_limits,
         }
     }
 
@@ -151,18 +167,52 @@ where
             store,
             keypair: self.keypair,
             network: self.network,
+            genesis_hash: self.genesis_hash,
             bootnodes: self.bootnodes,
             listen: self.listen,
             sync_batch_size: self.sync_batch_size,
             sampling_window: self.sampling_window,
+            sampling_seed: self.sampling_seed,
             pruning_delay: self.pruning_delay,
+            header_ex_request_timeout: self.header_ex_request_timeout,
+            transport_limits: self.transport_limits,
         }
     }
 
     /// The [`Network`] to connect to.
+    ///
+    /// For the canonical networks, the synced genesis header is required to match
+    /// the genesis hash from [`Network::params`].
     pub fn network(self, network: Network) -> Self {
+        let genesis_hash = network.params().map(|params| params.genesis_hash);
+
+        NodeBuilder {
+            network: Some(network),
+            genesis_hash,
+            ..self
+        }
+    }
+
+    /// Configure the [`Node`] for a private network.
+    ///
+    /// Sets the [`Network`] to the `Network::Custom` with the chain id of the config,
+    /// along with its bootnodes and sampling window, if they are provided. Those can be
+    /// still overridden with [`NodeBuilder::bootnodes`] and [`NodeBuilder::sampling_window`].
+    ///
+    /// If the genesis hash is provided, the synced genesis header is required to match it.
+    pub fn private_network(self, config: PrivateNetworkConfig) -> Self {
+        let network = config.network();
+        let bootnodes = if config.bootnodes.is_empty() {
+            self.bootnodes
+        } else {
+            config.bootnodes
+        };
+
         NodeBuilder {
             network: Some(network),
+            genesis_hash: config.genesis_hash,
+            bootnodes,
+            sampling_window: config.sampling_window.or(self.sampling_window),
             ..self
         }
     }
@@ -216,7 +266,7 @@ where
     /// Sampling window defines maximum age of a block considered for syncing and sampling.
     ///
     /// **Default if [`InMemoryStore`]/[`InMemoryBlockstore`] are used:** 60 seconds.\
-    /// **Default:** 30 days.\
+    /// **Default:** Sampling window from [`Network::params`], or 30 days for custom networks.\
     /// **Minimum:** 60 seconds.
     pub fn sampling_window(self, dur: Duration) -> Self {
         NodeBuilder {
@@ -225,6 +275,20 @@ where
         }
     }
 
+    /// Set the seed used for selecting the shares to sample.
+    ///
+    /// With a seed set, the same shares are selected for a given height on every run,
+    /// which allows reproducing the sampling, e.g. when debugging. It makes the
+    /// selection predictable to others too, so it shouldn't be used otherwise.
+    ///
+    /// **Default:** Shares are selected with a cryptographically secure random generator.
+    pub fn sampling_seed(self, seed: u64) -> Self {
+        NodeBuilder {
+            sampling_seed: Some(seed),
+            ..self
+        }
+    }
+
     /// Set pruning delay.
     ///
     /// Pruning delay defines how much time the pruner should wait after sampling window in
@@ -240,6 +304,31 @@ where
         }
     }
 
+    /// Set the time limit on a request for headers.
+    ///
+    /// If a peer doesn't respond within that time, the request is sent to another peer.
+    ///
+    /// **Default:** 10 seconds.
+    pub fn header_ex_request_timeout(self, dur: Duration) -> Self {
+        NodeBuilder {
+            header_ex_request_timeout: Some(dur),
+            ..self
+        }
+    }
+
+    /// Set the limits on connections and substreams of the transport.
+    ///
+    /// They protect the [`Node`] from peers opening an unbounded number of
+    /// connections or substreams.
+    ///
+    /// **Default:** [`TransportLimits::default`].
+    pub fn transport_limits(self, limits: TransportLimits) -> Self {
+        NodeBuilder {
+            transport_limits: Some(limits),
+            ..self
+        }
+    }
+
     fn build_config(self) -> Result<NodeConfig<B, S>, NodeBuilderError> {
         let network = self.network.ok_or(NodeBuilderError::NetworkNotSpecified)?;
 
@@ -270,7 +359,9 @@ where
         } else if in_memory_stores_used {
             MIN_SAMPLING_WINDOW
         } else {
-            DEFAULT_SAMPLING_WINDOW
+            network
+                .params()
+                .map_or(DEFAULT_SAMPLING_WINDOW, |params| params.sampling_window)
         };
 
         let pruning_delay = if let Some(dur) = self.pruning_delay {
@@ -297,12 +388,98 @@ where
             blockstore: self.blockstore,
             store: self.store,
             network_id: network.id().to_owned(),
+            genesis_hash: self.genesis_hash,
             p2p_local_keypair: self.keypair.unwrap_or_else(Keypair::generate_ed25519),
             p2p_bootnodes: bootnodes,
             p2p_listen_on: self.listen,
             sync_batch_size: self.sync_batch_size.unwrap_or(512),
             sampling_window,
+            sampling_seed: self.sampling_seed,
             pruning_window,
+            header_ex_request_timeout: self
+                .header_ex_request_timeout
+                .unwrap_or(DEFAULT_HEADER_EX_REQUEST_TIMEOUT),
+            transport_limits: self.transport_limits.unwrap_or_default(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::NetworkId;
+
+    #[test]
+    fn private_network() {
+        let bootnode: Multiaddr = "/ip4/127.0.0.1/tcp/2121".parse().unwrap();
+        let config = PrivateNetworkConfig {
+            genesis_hash: Some(Hash::Sha256([1; 32])),
+            bootnodes: vec![bootnode.clone()],
+            sampling_window: Some(Duration::from_secs(2 * HOUR)),
+            ..PrivateNetworkConfig::new(NetworkId::new("private").unwrap())
+        };
+
+        let node_config = NodeBuilder::new()
+            .private_network(config)
+            .build_config()
+            .unwrap();
+
+        assert_eq!(node_config.network_id, "private");
+        assert_eq!(node_config.genesis_hash, Some(Hash::Sha256([1; 32])));
+        assert_eq!(node_config.p2p_bootnodes, vec![bootnode]);
+        assert_eq!(node_config.sampling_window, Duration::from_secs(2 * HOUR));
+    }
+
+    #[test]
+    fn private_network_defaults() {
+        let config = PrivateNetworkConfig::new(NetworkId::new("private").unwrap());
+
+        let node_config = NodeBuilder::new()
+            .private_network(config)
+            .build_config()
+            .unwrap();
+
+        assert_eq!(node_config.network_id, "private");
+        assert_eq!(node_config.genesis_hash, None);
+        assert!(node_config.p2p_bootnodes.is_empty());
+        assert_eq!(node_config.sampling_window, MIN_SAMPLING_WINDOW);
+    }
+
+    #[test]
+    fn canonical_network_genesis_hash() {
+        for network in [Network::Mainnet, Network::Arabica, Network::Mocha] {
+            let params = network.params().unwrap();
+            let node_config = NodeBuilder::new().network(network).build_config().unwrap();
+
+            assert_eq!(node_config.genesis_hash, Some(params.genesis_hash));
+        }
+
+        let node_config = NodeBuilder::new()
+            .network(Network::custom("private").unwrap())
+            .build_config()
+            .unwrap();
+        assert_eq!(node_config.genesis_hash, None);
+    }
+
+    #[test]
+    fn header_ex_request_timeout() {
+        let node_config = NodeBuilder::new()
+            .network(Network::Mainnet)
+            .build_config()
+            .unwrap();
+        assert_eq!(
+            node_config.header_ex_request_timeout,
+            DEFAULT_HEADER_EX_REQUEST_TIMEOUT
+        );
+
+        let node_config = NodeBuilder::new()
+            .network(Network::Mainnet)
+            .header_ex_request_timeout(Duration::from_secs(3))
+            .build_config()
+            .unwrap();
+        assert_eq!(
+            node_config.header_ex_request_timeout,
+            Duration::from_secs(3)
+        );
+    }
+}
@@ -20,20 +20,22 @@ use std::task::Poll;
 use std::time::Duration;
 
 use blockstore::Blockstore;
-use celestia_proto::p2p::pb::{header_request, HeaderRequest};
+use celestia_proto::shwap::RowNamespaceData as RawRowNamespaceData;
 use celestia_types::fraud_proof::BadEncodingFraudProof;
 use celestia_types::hash::Hash;
 use celestia_types::nmt::{Namespace, NamespacedSha2Hasher};
+use celestia_types::p2p::header_request::{HeaderRequest, HeaderRequestExt};
 use celestia_types::row::{Row, RowId};
-use celestia_types::row_namespace_data::{RowNamespaceData, RowNamespaceDataId};
+use celestia_types::row_namespace_data::{NamespaceData, RowNamespaceData, RowNamespaceDataId};
 use celestia_types::sample::{Sample, SampleId};
 use celestia_types::{Blob, ExtendedHeader, FraudProof};
 use cid::Cid;
-use futures::stream::FuturesOrdered;
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{FuturesOrdered, FuturesUnordered};
 use futures::{StreamExt, TryStreamExt};
 use libp2p::core::transport::ListenerId;
 use libp2p::{
-    autonat,
+    autonat, connection_limits,
     core::{ConnectedPoint, Endpoint},
     gossipsub::{self, TopicHash},
     identify,
@@ -43,7 +45,7 @@ use libp2p::{
     ping,
     swarm::{
         dial_opts::{DialOpts, PeerCondition},
-        ConnectionId, DialError, NetworkBehaviour, NetworkInfo, Swarm, SwarmEvent,
+        ConnectionError, ConnectionId, DialError, NetworkBehaviour, NetworkInfo, Swarm, SwarmEvent,
     },
     Multiaddr, PeerId,
 };
@@ -55,20 +57,23 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, trace, warn};
 
 mod connection_control;
+mod framing;
 mod header_ex;
 pub(crate) mod header_session;
+mod shrex_nd;
 pub(crate) mod shwap;
 mod swarm;
 
 use crate::block_ranges::BlockRange;
-use crate::events::{EventPublisher, NodeEvent};
+use crate::events::{EventPublisher, NodeEvent, PeerScoreReason};
 use crate::executor::{self, spawn, Interval, JoinHandle};
-use crate::p2p::header_ex::{HeaderExBehaviour, HeaderExConfig};
+use crate::p2p::header_ex::{HeaderExBehaviour, HeaderExConfig, DEFAULT_MAX_RETRIES};
 use crate::p2p::header_session::HeaderSession;
+use crate::p2p::shrex_nd::{NamespaceDataId, ShrexNdBehaviour, ShrexNdConfig};
 use crate::p2p::shwap::{convert_cid, get_block_container, ShwapMultihasher};
 use crate::p2p::swarm::new_swarm;
 use crate::peer_tracker::PeerTracker;
-use crate::peer_tracker::PeerTrackerInfo;
+use crate::peer_tracker::{PeerDetails, PeerTrackerInfo};
 use crate::store::Store;
 use crate::utils::{
     celestia_protocol_id, fraudsub_ident_topic, gossipsub_ident_topic, MultiaddrExt,
@@ -76,12 +81,23 @@ use crate::utils::{
 };
 
 pub use crate::p2p::header_ex::HeaderExError;
+pub(crate) use crate::p2p::header_ex::DEFAULT_REQUEST_TIMEOUT as DEFAULT_HEADER_EX_REQUEST_TIMEOUT;
+pub use crate::p2p::shrex_nd::ShrexNdError;
+pub use crate::p2p::swarm::{
+    TransportLimits, DEFAULT_MAX_CONCURRENT_SUBSTREAMS, DEFAULT_MAX_ESTABLISHED_PER_PEER,
+    DEFAULT_MAX_PENDING_INCOMING,
+};
 
 // Minimal number of peers that we want to maintain connection to.
 // If we have fewer peers than that, we will try to reconnect / discover
 // more aggresively.
 const MIN_CONNECTED_PEERS: u64 = 4;
 
+// Initial and maximum delay of re-dialing a disconnected bootnode.
+// The delay is doubled on every consecutive disconnection.
+const BOOTNODE_RECONNECT_MIN_DELAY: Duration = Duration::from_secs(1);
+const BOOTNODE_RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
 // Maximum size of a [`Multihash`].
 pub(crate) const MAX_MH_SIZE: usize = 64;
 
@@ -122,6 +138,10 @@ pub enum P2pError {
     #[error("HeaderEx: {0}")]
     HeaderEx(#[from] HeaderExError),
 
+    /// An error propagated from the `shrex/nd`.
+    #[error("ShrexNd: {0}")]
+    ShrexNd(#[from] ShrexNdError),
+
     /// Bootnode address is missing its peer ID.
     #[error("Bootnode multiaddrs without peer ID: {0:?}")]
     BootnodeAddrsWithoutPeerId(Vec<Multiaddr>),
@@ -165,6 +185,7 @@ impl P2pError {
             | P2pError::BootnodeAddrsWithoutPeerId(_) => true,
             P2pError::NoConnectedPeers
             | P2pError::HeaderEx(_)
+            | P2pError::ShrexNd(_)
             | P2pError::Bitswap(_)
             | P2pError::ProtoDecodeFailed(_)
             | P2pError::Cid(_)
@@ -225,6 +246,10 @@ where
     pub store: Arc<S>,
     /// Event publisher.
     pub event_pub: EventPublisher,
+    /// Time limit on a `header-ex` request, after which it is retried with other peer.
+    pub header_ex_request_timeout: Duration,
+    /// Limits on the connections and substreams of the transport.
+    pub transport_limits: TransportLimits,
 }
 
 #[derive(Debug)]
@@ -236,12 +261,19 @@ pub(crate) enum P2pCmd {
         request: HeaderRequest,
         respond_to: OneshotResultSender<Vec<ExtendedHeader>, P2pError>,
     },
+    ShrexNdRequest {
+        id: NamespaceDataId,
+        respond_to: OneshotResultSender<Vec<RawRowNamespaceData>, P2pError>,
+    },
     Listeners {
         respond_to: oneshot::Sender<Vec<Multiaddr>>,
     },
     ConnectedPeers {
         respond_to: oneshot::Sender<Vec<PeerId>>,
     },
+    Peers {
+        respond_to: oneshot::Sender<Vec<PeerDetails>>,
+    },
     InitHeaderSub {
         head: Box<ExtendedHeader>,
         /// Any valid headers received by header-sub will be send to this channel.
@@ -413,31 +445,32 @@ impl P2p {
 
     /// Request the head header on the `header-ex` protocol.
     pub async fn get_head_header(&self) -> Result<ExtendedHeader> {
-        self.get_header_by_height(0).await
+        self.get_single_header(HeaderRequest::head(1)?).await
     }
 
     /// Request the header by hash on the `header-ex` protocol.
     pub async fn get_header(&self, hash: Hash) -> Result<ExtendedHeader> {
-        self.header_ex_request(HeaderRequest {
-            data: Some(header_request::Data::Hash(hash.as_bytes().to_vec())),
-            amount: 1,
-        })
-        .await?
-        .into_iter()
-        .next()
-        .ok_or(HeaderExError::HeaderNotFound.into())
+        self.get_single_header(HeaderRequest::by_hash(hash)).await
     }
 
     /// Request the header by height on the `header-ex` protocol.
+    ///
+    /// Height 0 requests the head header.
     pub async fn get_header_by_height(&self, height: u64) -> Result<ExtendedHeader> {
-        self.header_ex_request(HeaderRequest {
-            data: Some(header_request::Data::Origin(height)),
-            amount: 1,
-        })
-        .await?
-        .into_iter()
-        .next()
-        .ok_or(HeaderExError::HeaderNotFound.into())
+        if height == 0 {
+            return self.get_head_header().await;
+        }
+
+        self.get_single_header(HeaderRequest::by_height(height, 1)?)
+            .await
+    }
+
+    async fn get_single_header(&self, request: HeaderRequest) -> Result<ExtendedHeader> {
+        self.header_ex_request(request)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(HeaderExError::HeaderNotFound.into())
     }
 
     /// Request the headers following the one given with the `header-ex` protocol.
@@ -567,6 +600,62 @@ impl P2p {
         Ok(row_namespace_data)
     }
 
+    /// Request all the [`RowNamespaceData`] of the namespace in the block corresponding
+    /// to this header using `shrex/nd` protocol.
+    ///
+    /// Each row is verified against the [`DataAvailabilityHeader`] of the header.
+    ///
+    /// [`DataAvailabilityHeader`]: celestia_types::DataAvailabilityHeader
+    pub async fn get_namespace_data_shrex(
+        &self,
+        header: &ExtendedHeader,
+        namespace: Namespace,
+    ) -> Result<NamespaceData> {
+        let height = header.height().value();
+        let rows_to_fetch: Vec<_> = header
+            .dah
+            .row_roots()
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.contains::<NamespacedSha2Hasher>(*namespace))
+            .map(|(n, _)| n as u16)
+            .collect();
+
+        let (tx, rx) = oneshot::channel();
+
+        self.send_command(P2pCmd::ShrexNdRequest {
+            id: NamespaceDataId { height, namespace },
+            respond_to: tx,
+        })
+        .await?;
+
+        let raw_rows = rx.await??;
+
+        if raw_rows.len() != rows_to_fetch.len() {
+            return Err(ShrexNdError::InvalidResponse(format!(
+                "expected {} rows, got {}",
+                rows_to_fetch.len(),
+                raw_rows.len()
+            ))
+            .into());
+        }
+
+        let rows = rows_to_fetch
+            .into_iter()
+            .zip(raw_rows)
+            .map(|(row_idx, raw)| -> Result<_> {
+                let id =
+                    RowNamespaceDataId::new(namespace, row_idx, height).map_err(P2pError::Cid)?;
+                let row = RowNamespaceData::from_raw(id, raw)
+                    .and_then(|row| row.verify(id, &header.dah).map(|_| row))
+                    .map_err(|e| ShrexNdError::InvalidResponse(e.to_string()))?;
+                Ok(row)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(NamespaceData { rows })
+    }
+
     /// Request all blobs with provided namespace in the block corresponding to this header
     /// using bitswap protocol.
     pub async fn get_all_blobs(
@@ -616,6 +705,15 @@ impl P2p {
         Ok(rx.await?)
     }
 
+    /// Get details of all known peers, connected or not.
+    pub async fn peers(&self) -> Result<Vec<PeerDetails>> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send_command(P2pCmd::Peers { respond_to: tx }).await?;
+
+        Ok(rx.await?)
+    }
+
     /// Alter the trust status for a given peer.
     pub async fn set_peer_trust(&self, peer_id: PeerId, is_trusted: bool) -> Result<()> {
         self.send_command(P2pCmd::SetPeerTrust {
@@ -663,11 +761,13 @@ where
     S: Store + 'static,
 {
     connection_control: connection_control::Behaviour,
+    connection_limits: connection_limits::Behaviour,
     autonat: autonat::Behaviour,
     bitswap: beetswap::Behaviour<MAX_MH_SIZE, B>,
     ping: ping::Behaviour,
     identify: identify::Behaviour,
     header_ex: HeaderExBehaviour<S>,
+    shrex_nd: ShrexNdBehaviour,
     gossipsub: gossipsub::Behaviour,
     kademlia: kad::Behaviour<kad::store::MemoryStore>,
 }
@@ -690,6 +790,8 @@ where
     store: Arc<S>,
     event_pub: EventPublisher,
     bootnodes: HashMap<PeerId, Vec<Multiaddr>>,
+    bootnode_reconnect_delays: HashMap<PeerId, Duration>,
+    bootnode_reconnects: FuturesUnordered<BoxFuture<'static, PeerId>>,
 }
 
 struct HeaderSubState {
@@ -711,13 +813,17 @@ where
         let local_peer_id = PeerId::from(args.local_keypair.public());
 
         let connection_control = connection_control::Behaviour::new();
+        let connection_limits =
+            connection_limits::Behaviour::new(args.transport_limits.connection_limits());
         let autonat = autonat::Behaviour::new(local_peer_id, autonat::Config::default());
         let ping = ping::Behaviour::new(ping::Config::default());
 
-        let agent_version = format!("lumina/{}/{}", args.network_id, env!("CARGO_PKG_VERSION"));
         let identify = identify::Behaviour::new(
-            identify::Config::new(String::new(), args.local_keypair.public())
-                .with_agent_version(agent_version),
+            identify::Config::new(
+                identify_protocol_version(&args.network_id),
+                args.local_keypair.public(),
+            )
+            .with_agent_version(agent_version(&args.network_id)),
         );
 
         let header_sub_topic = gossipsub_ident_topic(&args.network_id, "/header-sub/v0.0.1");
@@ -736,20 +842,29 @@ where
             network_id: &args.network_id,
             peer_tracker: peer_tracker.clone(),
             header_store: args.store.clone(),
+            request_timeout: args.header_ex_request_timeout,
+            max_retries: DEFAULT_MAX_RETRIES,
+        });
+
+        let shrex_nd = ShrexNdBehaviour::new(ShrexNdConfig {
+            network_id: &args.network_id,
+            peer_tracker: peer_tracker.clone(),
         });
 
         let behaviour = Behaviour {
             connection_control,
+            connection_limits,
             autonat,
             bitswap,
             ping,
             identify,
             gossipsub,
             header_ex,
+            shrex_nd,
             kademlia,
         };
 
-        let mut swarm = new_swarm(args.local_keypair, behaviour).await?;
+        let mut swarm = new_swarm(args.local_keypair, behaviour, &args.transport_limits).await?;
         let mut listeners = SmallVec::new();
 
         for addr in args.listen_on {
@@ -789,6 +904,8 @@ where
             store: args.store,
             event_pub: args.event_pub,
             bootnodes,
+            bootnode_reconnect_delays: HashMap::new(),
+            bootnode_reconnects: FuturesUnordered::new(),
         })
     }
 
@@ -809,6 +926,9 @@ where
                         self.bootstrap();
                     }
                 }
+                Some(peer_id) = self.bootnode_reconnects.next() => {
+                    self.reconnect_bootnode(peer_id);
+                }
                 _ = report_interval.tick() => {
                     self.report();
                 }
@@ -861,6 +981,44 @@ where
         }
     }
 
+    fn schedule_bootnode_reconnect(&mut self, peer_id: PeerId) {
+        let delay = self
+            .bootnode_reconnect_delays
+            .get(&peer_id)
+            .map(|delay| (*delay * 2).min(BOOTNODE_RECONNECT_MAX_DELAY))
+            .unwrap_or(BOOTNODE_RECONNECT_MIN_DELAY);
+
+        self.bootnode_reconnect_delays.insert(peer_id, delay);
+
+        self.bootnode_reconnects.push(
+            async move {
+                executor::sleep(delay).await;
+                peer_id
+            }
+            .boxed(),
+        );
+
+        self.event_pub
+            .send(NodeEvent::PeerReconnectScheduled { id: peer_id, delay });
+    }
+
+    fn reconnect_bootnode(&mut self, peer_id: PeerId) {
+        let Some(addrs) = self.bootnodes.get(&peer_id) else {
+            return;
+        };
+
+        let dial_opts = DialOpts::peer_id(peer_id)
+            .addresses(addrs.clone())
+            .condition(PeerCondition::DisconnectedAndNotDialing)
+            .build();
+
+        if let Err(e) = self.swarm.dial(dial_opts) {
+            if !matches!(e, DialError::DialPeerConditionFalse(_)) {
+                warn!("Failed to dial on {addrs:?}: {e}");
+            }
+        }
+    }
+
     fn prune_canceled_bitswap_queries(&mut self) {
         let mut cancelled = SmallVec::<[_; 16]>::new();
 
@@ -882,6 +1040,7 @@ where
             .connection_control
             .set_stopping(true);
         self.swarm.behaviour_mut().header_ex.stop();
+        self.swarm.behaviour_mut().shrex_nd.stop();
 
         for listener in self.listeners.drain(..) {
             self.swarm.remove_listener(listener);
@@ -910,10 +1069,11 @@ where
                 SwarmEvent::ConnectionClosed {
                     peer_id,
                     connection_id,
+                    cause,
                     ..
                 } => {
                     // This will generate the PeerDisconnected events.
-                    self.on_peer_disconnected(peer_id, connection_id);
+                    self.on_peer_disconnected(peer_id, connection_id, cause);
                 }
                 _ => {}
             }
@@ -930,7 +1090,9 @@ where
                 BehaviourEvent::Ping(ev) => self.on_ping_event(ev).await,
                 BehaviourEvent::Autonat(_)
                 | BehaviourEvent::ConnectionControl(_)
-                | BehaviourEvent::HeaderEx(_) => {}
+                | BehaviourEvent::ConnectionLimits(_)
+                | BehaviourEvent::HeaderEx(_)
+                | BehaviourEvent::ShrexNd(_) => {}
             },
             SwarmEvent::ConnectionEstablished {
                 peer_id,
@@ -943,9 +1105,10 @@ where
             SwarmEvent::ConnectionClosed {
                 peer_id,
                 connection_id,
+                cause,
                 ..
             } => {
-                self.on_peer_disconnected(peer_id, connection_id);
+                self.on_peer_disconnected(peer_id, connection_id, cause);
             }
             _ => {}
         }
@@ -967,6 +1130,12 @@ where
                     .header_ex
                     .send_request(request, respond_to);
             }
+            P2pCmd::ShrexNdRequest { id, respond_to } => {
+                self.swarm
+                    .behaviour_mut()
+                    .shrex_nd
+                    .send_request(id, respond_to);
+            }
             P2pCmd::Listeners { respond_to } => {
                 let local_peer_id = self.swarm.local_peer_id().to_owned();
                 let listeners = self
@@ -986,6 +1155,9 @@ where
             P2pCmd::ConnectedPeers { respond_to } => {
                 respond_to.maybe_send(self.peer_tracker.connected_peers());
             }
+            P2pCmd::Peers { respond_to } => {
+                respond_to.maybe_send(self.peer_tracker.peers());
+            }
             P2pCmd::InitHeaderSub { head, channel } => {
                 self.on_init_header_sub(*head, channel);
             }
@@ -1029,6 +1201,12 @@ where
     async fn on_identify_event(&mut self, ev: identify::Event) -> Result<()> {
         match ev {
             identify::Event::Received { peer_id, info, .. } => {
+                debug!(
+                    "Identified {peer_id}: agent: {}, protocol: {}, protocols: {:?}",
+                    info.agent_version, info.protocol_version, info.protocols
+                );
+                self.peer_tracker.set_identify_info(peer_id, &info);
+
                 // Inform Kademlia about the listening addresses
                 // TODO: Remove this when rust-libp2p#5103 is implemented
                 for addr in info.listen_addrs {
@@ -1067,7 +1245,10 @@ where
                     gossipsub::MessageAcceptance::Ignore
                 };
 
-                if !matches!(acceptance, gossipsub::MessageAcceptance::Reject) {
+                if matches!(acceptance, gossipsub::MessageAcceptance::Reject) {
+                    self.peer_tracker
+                        .update_score(peer, PeerScoreReason::GossipMisbehavior);
+                } else {
                     // We may have discovered a new peer
                     self.peer_maybe_discovered(peer);
                 }
@@ -1171,15 +1352,32 @@ where
 
         self.peer_tracker
             .set_connected(peer_id, connection_id, dialed_addr);
+
+        // Connection succeeded, so next reconnection starts with the initial delay.
+        self.bootnode_reconnect_delays.remove(&peer_id);
     }
 
     #[instrument(skip_all, fields(peer_id = %peer_id))]
-    fn on_peer_disconnected(&mut self, peer_id: PeerId, connection_id: ConnectionId) {
-        if self
+    fn on_peer_disconnected(
+        &mut self,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        cause: Option<ConnectionError>,
+    ) {
+        let reason = cause.map(|e| e.to_string());
+
+        if !self
             .peer_tracker
-            .set_maybe_disconnected(peer_id, connection_id)
+            .set_maybe_disconnected(peer_id, connection_id, reason)
         {
-            debug!("Peer disconnected");
+            return;
+        }
+
+        debug!("Peer disconnected");
+
+        // Do not reconnect if we are stopping.
+        if self.bootnodes.contains_key(&peer_id) && !self.cancellation_token.is_cancelled() {
+            self.schedule_bootnode_reconnect(peer_id);
         }
     }
 
@@ -1288,6 +1486,16 @@ async fn poll_closed(
     .await
 }
 
+/// Agent version reported to the peers, e.g. `lumina/celestia/0.8.0`.
+fn agent_version(network_id: &str) -> String {
+    format!("lumina/{network_id}/{}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Protocol version reported to the peers.
+fn identify_protocol_version(network_id: &str) -> String {
+    format!("/celestia/{network_id}")
+}
+
 fn validate_bootnode_addrs(addrs: &[Multiaddr]) -> Result<(), P2pError> {
     let mut invalid_addrs = Vec::new();
 
@@ -1379,3 +1587,22 @@ where
         .client_set_send_dont_have(false)
         .build())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identify_versions() {
+        let agent = agent_version("mocha-4");
+        let parts: Vec<_> = agent.split('/').collect();
+
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0], "lumina");
+        assert_eq!(parts[1], "mocha-4");
+        assert!(!parts[2].is_empty());
+        assert_eq!(parts[2], env!("CARGO_PKG_VERSION"));
+
+        assert_eq!(identify_protocol_version("mocha-4"), "/celestia/mocha-4");
+    }
+}
@@ -0,0 +1,218 @@
+//! Framing of length delimited protobuf messages.
+
+use std::io;
+
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use prost::Message;
+
+/// Maximum size of a varint encoded length delimiter.
+const MAX_DELIMITER_SIZE: usize = 10;
+
+/// Error of reading a message bigger than the size limit.
+#[derive(Debug, thiserror::Error)]
+#[error("Message of {size} bytes exceeds the limit of {limit} bytes")]
+pub(crate) struct SizeLimitExceeded {
+    pub size: usize,
+    pub limit: usize,
+}
+
+impl SizeLimitExceeded {
+    /// Returns `true` if the error was caused by reading a message bigger than the size limit.
+    pub(crate) fn is_cause_of(e: &io::Error) -> bool {
+        e.get_ref().is_some_and(|e| e.is::<SizeLimitExceeded>())
+    }
+}
+
+/// Reads a single length delimited message that is not bigger than `max_size` bytes.
+///
+/// Returns `None` if the stream ended before the message started.
+///
+/// # Errors
+///
+/// This function returns an error of kind [`io::ErrorKind::UnexpectedEof`] if the stream
+/// ended in the middle of the message and [`io::ErrorKind::InvalidData`] if the message
+/// is bigger than `max_size` or it can't be decoded. The former can be distinguished
+/// with [`SizeLimitExceeded::is_cause_of`].
+pub(crate) async fn read_length_delimited<T, M>(
+    io: &mut T,
+    max_size: usize,
+) -> io::Result<Option<M>>
+where
+    T: AsyncRead + Unpin,
+    M: Message + Default,
+{
+    let Some(len) = read_delimiter(io).await? else {
+        return Ok(None);
+    };
+
+    if len > max_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            SizeLimitExceeded {
+                size: len,
+                limit: max_size,
+            },
+        ));
+    }
+
+    let mut buf = vec![0u8; len];
+    io.read_exact(&mut buf).await?;
+
+    M::decode(&buf[..])
+        .map(Some)
+        .map_err(|e| invalid_data(e.to_string()))
+}
+
+/// Writes a single length delimited message.
+pub(crate) async fn write_length_delimited<T, M>(io: &mut T, msg: &M) -> io::Result<()>
+where
+    T: AsyncWrite + Unpin,
+    M: Message,
+{
+    io.write_all(&msg.encode_length_delimited_to_vec()).await
+}
+
+/// Returns the size of the message after framing.
+pub(crate) fn length_delimited_size<M>(msg: &M) -> usize
+where
+    M: Message,
+{
+    let len = msg.encoded_len();
+    prost::length_delimiter_len(len) + len
+}
+
+async fn read_delimiter<T>(io: &mut T) -> io::Result<Option<usize>>
+where
+    T: AsyncRead + Unpin,
+{
+    let mut buf = [0u8; MAX_DELIMITER_SIZE];
+
+    for i in 0..MAX_DELIMITER_SIZE {
+        if let Err(e) = io.read_exact(&mut buf[i..=i]).await {
+            if i == 0 && e.kind() == io::ErrorKind::UnexpectedEof {
+                return Ok(None);
+            }
+
+            return Err(e);
+        }
+
+        // Last byte of varint doesn't have continuation bit set
+        if buf[i] & 0x80 == 0 {
+            return prost::decode_length_delimiter(&buf[..=i])
+                .map(Some)
+                .map_err(|e| invalid_data(e.to_string()));
+        }
+    }
+
+    Err(invalid_data("Length delimiter is too long"))
+}
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::async_test;
+    use celestia_proto::p2p::pb::header_request::Data;
+    use celestia_proto::p2p::pb::HeaderRequest;
+    use futures::io::Cursor;
+
+    fn header_request() -> HeaderRequest {
+        HeaderRequest {
+            amount: 16,
+            data: Some(Data::Origin(1)),
+        }
+    }
+
+    #[async_test]
+    async fn round_trip() {
+        let req = header_request();
+        let mut buf = Vec::new();
+
+        write_length_delimited(&mut buf, &req).await.unwrap();
+        write_length_delimited(&mut buf, &req).await.unwrap();
+        assert_eq!(buf.len(), 2 * length_delimited_size(&req));
+
+        let mut reader = Cursor::new(buf);
+
+        for _ in 0..2 {
+            let decoded: Option<HeaderRequest> =
+                read_length_delimited(&mut reader, 1024).await.unwrap();
+            assert_eq!(decoded, Some(req.clone()));
+        }
+
+        let decoded: Option<HeaderRequest> =
+            read_length_delimited(&mut reader, 1024).await.unwrap();
+        assert_eq!(decoded, None);
+    }
+
+    #[async_test]
+    async fn truncated_message() {
+        let buf = header_request().encode_length_delimited_to_vec();
+        let mut reader = Cursor::new(&buf[..buf.len() - 1]);
+
+        let err = read_length_delimited::<_, HeaderRequest>(&mut reader, 1024)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[async_test]
+    async fn truncated_delimiter() {
+        let mut reader = Cursor::new([0b1000_0000]);
+
+        let err = read_length_delimited::<_, HeaderRequest>(&mut reader, 1024)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[async_test]
+    async fn oversized_message() {
+        let req = header_request();
+        let buf = req.encode_length_delimited_to_vec();
+        let mut reader = Cursor::new(buf);
+
+        let err = read_length_delimited::<_, HeaderRequest>(&mut reader, req.encoded_len() - 1)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(SizeLimitExceeded::is_cause_of(&err));
+    }
+
+    #[async_test]
+    async fn invalid_delimiter() {
+        // 10 consecutive bytes with continuation bit set + 1 byte, which is longer than allowed
+        //    for length delimiter
+        let varint = [
+            0b1000_0000,
+            0b1000_0000,
+            0b1000_0000,
+            0b1000_0000,
+            0b1000_0000,
+            0b1000_0000,
+            0b1000_0000,
+            0b1000_0000,
+            0b1000_0000,
+            0b1000_0000,
+            0b0000_0001,
+        ];
+        let mut reader = Cursor::new(varint);
+
+        let err = read_length_delimited::<_, HeaderRequest>(&mut reader, 1024)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[async_test]
+    async fn trailing_zero_delimiter() {
+        let mut reader = Cursor::new([0b1000_0001, 0b0000_0000, 0b1111_1111]);
+        assert_eq!(read_delimiter(&mut reader).await.unwrap(), Some(1));
+
+        let mut reader = Cursor::new([0b1000_0000, 0b1000_0000, 0b1000_0000, 0b0000_0000]);
+        assert_eq!(read_delimiter(&mut reader).await.unwrap(), Some(0));
+    }
+}
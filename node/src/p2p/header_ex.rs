@@ -5,7 +5,7 @@ use std::task::{Context, Poll};
 use async_trait::async_trait;
 use celestia_proto::p2p::pb::{HeaderRequest, HeaderResponse};
 use celestia_types::ExtendedHeader;
-use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use futures::{AsyncRead, AsyncWrite};
 use libp2p::core::transport::PortUse;
 use libp2p::{
     core::Endpoint,
@@ -17,7 +17,6 @@ use libp2p::{
     },
     Multiaddr, PeerId, StreamProtocol,
 };
-use prost::Message;
 use tracing::{debug, instrument, warn};
 use web_time::{Duration, Instant};
 
@@ -26,6 +25,9 @@ mod server;
 pub(crate) mod utils;
 
 use crate::executor::timeout;
+use crate::p2p::framing::{
+    length_delimited_size, read_length_delimited, write_length_delimited, SizeLimitExceeded,
+};
 use crate::p2p::header_ex::client::HeaderExClientHandler;
 use crate::p2p::header_ex::server::HeaderExServerHandler;
 use crate::p2p::P2pError;
@@ -43,6 +45,10 @@ const RESPONSE_SIZE_LIMIT: usize = 10 * 1024 * 1024;
 const RESPONSE_TIME_LIMIT: Duration = Duration::from_secs(5);
 /// Substream negotiation timeout
 const NEGOTIATION_TIMEOUT: Duration = Duration::from_secs(1);
+/// Default time limit on the whole request, after which it fails or is retried with other peer
+pub(crate) const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Default amount of times a timed out request is retried with other peer
+pub(crate) const DEFAULT_MAX_RETRIES: usize = 2;
 
 type RequestType = HeaderRequest;
 type ResponseType = Vec<HeaderResponse>;
@@ -64,6 +70,8 @@ pub(crate) struct HeaderExConfig<'a, S> {
     pub network_id: &'a str,
     pub peer_tracker: Arc<PeerTracker>,
     pub header_store: Arc<S>,
+    pub request_timeout: Duration,
+    pub max_retries: usize,
 }
 
 /// Representation of all the errors that can occur in `HeaderEx` component.
@@ -107,9 +115,10 @@ where
                     protocol_id(config.network_id, "/header-ex/v0.0.3"),
                     ProtocolSupport::Full,
                 )],
-                request_response::Config::default(),
+                request_response::Config::default().with_request_timeout(config.request_timeout),
             ),
-            client_handler: HeaderExClientHandler::new(config.peer_tracker),
+            client_handler: HeaderExClientHandler::new(config.peer_tracker)
+                .with_max_retries(config.max_retries),
             server_handler: HeaderExServerHandler::new(config.header_store),
         }
     }
@@ -164,7 +173,8 @@ where
                 request_id,
                 error,
             } => {
-                self.client_handler.on_failure(peer, request_id, error);
+                self.client_handler
+                    .on_failure(&mut self.req_resp, peer, request_id, error);
             }
 
             // Received new inbound request
@@ -375,19 +385,28 @@ impl Codec for HeaderCodec {
     where
         T: AsyncRead + Unpin + Send,
     {
-        let data = read_up_to(io, REQUEST_SIZE_LIMIT, REQUEST_TIME_LIMIT).await?;
-
-        if data.len() >= REQUEST_SIZE_LIMIT {
-            debug!("Message filled the whole buffer (len: {})", data.len());
+        let req = timeout(
+            REQUEST_TIME_LIMIT,
+            read_length_delimited(io, REQUEST_SIZE_LIMIT),
+        )
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::Other, "reading request timed out"))?;
+
+        match req {
+            Ok(Some(req)) => Ok(req),
+            Ok(None) => Err(io::Error::new(io::ErrorKind::Other, "empty request")),
+            Err(e) => {
+                // There are two cases that can reach here:
+                //
+                // 1. The request is invalid
+                // 2. The request is incomplete because of the size limit
+                debug!("Failed to read request: {e}");
+                Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "invalid or incomplete request",
+                ))
+            }
         }
-
-        parse_header_request(&data).ok_or_else(|| {
-            // There are two cases that can reach here:
-            //
-            // 1. The request is invalid
-            // 2. The request is incomplete because of the size limit or time limit
-            io::Error::new(io::ErrorKind::Other, "invalid or incomplete request")
-        })
     }
 
     async fn read_response<T>(
@@ -398,18 +417,42 @@ impl Codec for HeaderCodec {
     where
         T: AsyncRead + Unpin + Send,
     {
-        let data = read_up_to(io, RESPONSE_SIZE_LIMIT, RESPONSE_TIME_LIMIT).await?;
-
-        if data.len() >= RESPONSE_SIZE_LIMIT {
-            debug!("Message filled the whole buffer (len: {})", data.len());
-        }
-
-        let mut data = &data[..];
+        let now = Instant::now();
+        let mut size_left = RESPONSE_SIZE_LIMIT;
         let mut msgs = Vec::new();
 
-        while let Some((header, rest)) = parse_header_response(data) {
-            msgs.push(header);
-            data = rest;
+        loop {
+            let Some(time_limit) = RESPONSE_TIME_LIMIT.checked_sub(now.elapsed()) else {
+                break;
+            };
+
+            let msg = match timeout(time_limit, read_length_delimited(io, size_left)).await {
+                Ok(Ok(Some(msg))) => msg,
+                // EOF
+                Ok(Ok(None)) => break,
+                // Not even a single header fits, so the limit needs to be raised
+                Ok(Err(e)) if msgs.is_empty() && SizeLimitExceeded::is_cause_of(&e) => {
+                    debug!("Failed to read response: {e}");
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "response exceeded size limit",
+                    ));
+                }
+                Ok(Err(e))
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof
+                    ) =>
+                {
+                    debug!("Failed to read response: {e}");
+                    break;
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(_) => break,
+            };
+
+            size_left = size_left.saturating_sub(length_delimited_size(&msg));
+            msgs.push(msg);
         }
 
         if msgs.is_empty() {
@@ -435,11 +478,7 @@ impl Codec for HeaderCodec {
     where
         T: AsyncWrite + Unpin + Send,
     {
-        let mut buf = Vec::with_capacity(REQUEST_SIZE_LIMIT);
-
-        let _ = req.encode_length_delimited(&mut buf);
-
-        timeout(REQUEST_TIME_LIMIT, io.write_all(&buf))
+        timeout(REQUEST_TIME_LIMIT, write_length_delimited(io, &req))
             .await
             .map_err(|_| io::Error::new(io::ErrorKind::Other, "writing request timed out"))??;
 
@@ -455,103 +494,31 @@ impl Codec for HeaderCodec {
     where
         T: AsyncWrite + Unpin + Send,
     {
-        let mut buf = Vec::with_capacity(RESPONSE_SIZE_LIMIT);
+        let write = async {
+            let mut size_left = RESPONSE_SIZE_LIMIT;
 
-        for resp in resps {
-            if resp.encode_length_delimited(&mut buf).is_err() {
-                // Error on encoding means the buffer is full.
-                // We will send a partial response back.
-                debug!("Sending partial response");
-                break;
-            }
-        }
-
-        timeout(RESPONSE_TIME_LIMIT, io.write_all(&buf))
-            .await
-            .map_err(|_| io::Error::new(io::ErrorKind::Other, "writing response timed out"))??;
-
-        Ok(())
-    }
-}
+            for resp in &resps {
+                let size = length_delimited_size(resp);
 
-/// Reads up to `size_limit` within `time_limit`.
-async fn read_up_to<T>(io: &mut T, size_limit: usize, time_limit: Duration) -> io::Result<Vec<u8>>
-where
-    T: AsyncRead + Unpin + Send,
-{
-    let mut buf = vec![0u8; size_limit];
-    let mut read_len = 0;
-    let now = Instant::now();
-
-    loop {
-        if read_len == buf.len() {
-            // No empty space. Buffer is full.
-            break;
-        }
+                if size > size_left {
+                    // We will send a partial response back.
+                    debug!("Sending partial response");
+                    break;
+                }
 
-        let Some(time_limit) = time_limit.checked_sub(now.elapsed()) else {
-            break;
-        };
+                write_length_delimited(io, resp).await?;
+                size_left -= size;
+            }
 
-        let len = match timeout(time_limit, io.read(&mut buf[read_len..])).await {
-            Ok(Ok(len)) => len,
-            Ok(Err(e)) => return Err(e),
-            Err(_) => break,
+            Ok::<_, io::Error>(())
         };
 
-        if len == 0 {
-            // EOF
-            break;
-        }
-
-        read_len += len;
-    }
-
-    buf.truncate(read_len);
-
-    Ok(buf)
-}
-
-fn parse_delimiter(mut buf: &[u8]) -> Option<(usize, &[u8])> {
-    if buf.is_empty() {
-        return None;
-    }
-
-    let Ok(len) = prost::decode_length_delimiter(&mut buf) else {
-        return None;
-    };
-
-    Some((len, buf))
-}
-
-fn parse_header_response(buf: &[u8]) -> Option<(HeaderResponse, &[u8])> {
-    let (len, rest) = parse_delimiter(buf)?;
-
-    if rest.len() < len {
-        debug!("Message is incomplete: {len}");
-        return None;
-    }
-
-    let Ok(msg) = HeaderResponse::decode(&rest[..len]) else {
-        return None;
-    };
-
-    Some((msg, &rest[len..]))
-}
-
-fn parse_header_request(buf: &[u8]) -> Option<HeaderRequest> {
-    let (len, rest) = parse_delimiter(buf)?;
+        timeout(RESPONSE_TIME_LIMIT, write)
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "writing response timed out"))??;
 
-    if rest.len() < len {
-        debug!("Message is incomplete: {len}");
-        return None;
+        Ok(())
     }
-
-    let Ok(msg) = HeaderRequest::decode(&rest[..len]) else {
-        return None;
-    };
-
-    Some(msg)
 }
 
 #[cfg(test)]
@@ -561,7 +528,7 @@ mod tests {
     use bytes::BytesMut;
     use celestia_proto::p2p::pb::header_request::Data;
     use futures::io::{Cursor, Error};
-    use prost::encode_length_delimiter;
+    use prost::{encode_length_delimiter, Message};
     use std::io::ErrorKind;
     use std::pin::Pin;
 
@@ -653,34 +620,34 @@ mod tests {
         assert_eq!(decoding_error.kind(), ErrorKind::Other);
     }
 
-    #[test]
-    fn test_invalid_varint() {
-        // 10 consecutive bytes with continuation bit set + 1 byte, which is longer than allowed
-        //    for length delimiter
-        let varint = [
-            0b1000_0000,
-            0b1000_0000,
-            0b1000_0000,
-            0b1000_0000,
-            0b1000_0000,
-            0b1000_0000,
-            0b1000_0000,
-            0b1000_0000,
-            0b1000_0000,
-            0b1000_0000,
-            0b0000_0001,
-        ];
-
-        assert_eq!(parse_delimiter(&varint), None);
-    }
-
-    #[test]
-    fn parse_trailing_zero_varint() {
-        let varint = [0b1000_0001, 0b0000_0000, 0b1111_1111];
-        assert!(matches!(parse_delimiter(&varint), Some((1, [255]))));
-
-        let varint = [0b1000_0000, 0b1000_0000, 0b1000_0000, 0b0000_0000];
-        assert!(matches!(parse_delimiter(&varint), Some((0, []))));
+    #[async_test]
+    async fn test_decode_header_response_at_size_limit() {
+        let mut header_response = HeaderResponse {
+            body: Vec::new(),
+            status_code: 1,
+        };
+        let body_len = RESPONSE_SIZE_LIMIT - header_response.encoded_len() - 1;
+        header_response.body = vec![0; body_len - prost::length_delimiter_len(body_len)];
+        assert_eq!(header_response.encoded_len(), RESPONSE_SIZE_LIMIT);
+
+        let stream_protocol = StreamProtocol::new("/foo/bar/v0.1");
+        let mut codec = HeaderCodec {};
+
+        let mut reader = Cursor::new(header_response.encode_length_delimited_to_vec());
+        let decoded_header_response = codec
+            .read_response(&stream_protocol, &mut reader)
+            .await
+            .unwrap();
+        assert_eq!(decoded_header_response, vec![header_response.clone()]);
+
+        // just over the limit
+        header_response.body.push(0);
+        let mut reader = Cursor::new(header_response.encode_length_delimited_to_vec());
+        let decoding_error = codec
+            .read_response(&stream_protocol, &mut reader)
+            .await
+            .unwrap_err();
+        assert_eq!(decoding_error.to_string(), "response exceeded size limit");
     }
 
     #[async_test]
@@ -16,6 +16,7 @@ use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, instrument, trace};
 
+use crate::events::PeerScoreReason;
 use crate::executor::yield_now;
 use crate::p2p::header_ex::utils::{HeaderRequestExt, HeaderResponseExt};
 use crate::p2p::header_ex::{HeaderExError, ReqRespBehaviour};
@@ -29,8 +30,9 @@ pub(super) struct HeaderExClientHandler<S = ReqRespBehaviour>
 where
     S: RequestSender,
 {
-    reqs: HashMap<S::RequestId, State>,
+    reqs: PendingRequests<S::RequestId>,
     peer_tracker: Arc<PeerTracker>,
+    max_retries: usize,
     cancellation_token: CancellationToken,
     tasks: FuturesUnordered<BoxFuture<'static, ()>>,
 }
@@ -38,6 +40,15 @@ where
 struct State {
     request: HeaderRequest,
     respond_to: OneshotSender,
+    /// Peers that the request was already sent to.
+    peers: Vec<PeerId>,
+    /// How many times the request can still be re-sent to another peer on timeout.
+    retries_left: usize,
+}
+
+/// Outbound requests waiting for a response, keyed by their request id.
+struct PendingRequests<Id> {
+    reqs: HashMap<Id, State>,
 }
 
 /// Oneshot sender that responds with `RequestCancelled` if not used.
@@ -57,6 +68,50 @@ impl RequestSender for ReqRespBehaviour {
     }
 }
 
+impl State {
+    /// Returns the `(from_height, amount)` of a request by height.
+    fn requested_range(&self) -> Option<(u64, u64)> {
+        match self.request.data {
+            Some(Data::Origin(from_height)) => Some((from_height, self.request.amount)),
+            _ => None,
+        }
+    }
+}
+
+impl<Id> PendingRequests<Id>
+where
+    Id: Hash + Eq,
+{
+    fn new() -> Self {
+        PendingRequests {
+            reqs: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, id: Id, state: State) {
+        self.reqs.insert(id, state);
+    }
+
+    /// Removes the request, once it received a response or failed.
+    fn remove(&mut self, id: &Id) -> Option<State> {
+        self.reqs.remove(id)
+    }
+
+    /// Returns the `(from_height, amount)` of a pending request by height.
+    #[cfg(test)]
+    fn requested_range(&self, id: &Id) -> Option<(u64, u64)> {
+        self.reqs.get(id)?.requested_range()
+    }
+
+    fn len(&self) -> usize {
+        self.reqs.len()
+    }
+
+    fn clear(&mut self) {
+        self.reqs.clear();
+    }
+}
+
 impl OneshotSender {
     fn new(tx: oneshot::Sender<Result<Vec<ExtendedHeader>, P2pError>>) -> Self {
         OneshotSender(Some(tx))
@@ -93,13 +148,20 @@ where
 {
     pub(super) fn new(peer_tracker: Arc<PeerTracker>) -> Self {
         HeaderExClientHandler {
-            reqs: HashMap::new(),
+            reqs: PendingRequests::new(),
             peer_tracker,
+            max_retries: 0,
             cancellation_token: CancellationToken::new(),
             tasks: FuturesUnordered::new(),
         }
     }
 
+    /// Set how many times a timed out request is re-sent to another peer.
+    pub(super) fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
     #[instrument(level = "trace", skip(self, sender, respond_to))]
     pub(super) fn on_send_request(
         &mut self,
@@ -147,9 +209,12 @@ where
         let state = State {
             request,
             respond_to: OneshotSender::new(respond_to),
+            peers: vec![peer],
+            retries_left: self.max_retries,
         };
 
         self.reqs.insert(req_id, state);
+        trace!("{} requests pending", self.reqs.len());
     }
 
     fn send_head_request(
@@ -175,9 +240,12 @@ where
             let (tx, rx) = oneshot::channel();
 
             let req_id = sender.send_request(&peer, request.clone());
+            // HEAD is already requested from multiple peers, so it isn't retried
             let state = State {
                 request: request.clone(),
                 respond_to: OneshotSender::new(tx),
+                peers: vec![peer],
+                retries_left: 0,
             };
 
             self.reqs.insert(req_id, state);
@@ -262,27 +330,66 @@ where
             return;
         };
 
+        if let Some((from_height, amount)) = state.requested_range() {
+            trace!("Response for {amount} headers from height {from_height}");
+        }
+
+        let peer_tracker = self.peer_tracker.clone();
+
         self.tasks.push(
             async move {
                 let res = decode_and_verify_responses(&state.request, &responses)
                     .await
                     .map_err(P2pError::from);
+
+                if res.is_ok() {
+                    peer_tracker.update_score(peer, PeerScoreReason::HeaderDelivered);
+                }
+
                 state.respond_to.maybe_send(res);
             }
             .boxed(),
         );
     }
 
-    #[instrument(level = "debug", skip(self))]
+    #[instrument(level = "debug", skip(self, sender))]
     pub(super) fn on_failure(
         &mut self,
+        sender: &mut S,
         peer: PeerId,
         request_id: S::RequestId,
         error: OutboundFailure,
     ) {
         debug!("Outbound failure");
 
+        if matches!(error, OutboundFailure::Timeout) {
+            self.peer_tracker
+                .update_score(peer, PeerScoreReason::HighLatency);
+        }
+
         if let Some(mut state) = self.reqs.remove(&request_id) {
+            if let Some((from_height, amount)) = state.requested_range() {
+                debug!("Request for {amount} headers from height {from_height} failed");
+            }
+
+            if matches!(error, OutboundFailure::Timeout) && state.retries_left > 0 {
+                let other_peer = self
+                    .peer_tracker
+                    .best_n_peers(MAX_PEERS)
+                    .into_iter()
+                    .find(|peer| !state.peers.contains(peer));
+
+                if let Some(other_peer) = other_peer {
+                    debug!("Retrying request with {other_peer}");
+
+                    let req_id = sender.send_request(&other_peer, state.request.clone());
+                    state.peers.push(other_peer);
+                    state.retries_left -= 1;
+                    self.reqs.insert(req_id, state);
+                    return;
+                }
+            }
+
             state
                 .respond_to
                 .maybe_send_err(HeaderExError::OutboundFailure(error));
@@ -410,6 +517,113 @@ mod tests {
         assert_eq!(result[0], expected_header);
     }
 
+    #[async_test]
+    async fn concurrent_requests_tracked() {
+        let peer_tracker = peer_tracker_with_n_peers(15);
+        let mut mock_req = MockReq::new();
+        let mut handler = HeaderExClientHandler::<MockReq>::new(peer_tracker);
+
+        let (tx1, rx1) = oneshot::channel();
+        let (tx2, rx2) = oneshot::channel();
+
+        handler.on_send_request(&mut mock_req, HeaderRequest::with_origin(5, 2), tx1);
+        handler.on_send_request(&mut mock_req, HeaderRequest::with_origin(20, 3), tx2);
+
+        let req1 = mock_req.reqs.pop_front().unwrap();
+        let req2 = mock_req.reqs.pop_front().unwrap();
+
+        assert_eq!(handler.reqs.len(), 2);
+        assert_eq!(handler.reqs.requested_range(&req1.id), Some((5, 2)));
+        assert_eq!(handler.reqs.requested_range(&req2.id), Some((20, 3)));
+
+        // Respond in reverse order
+        let mut gen = ExtendedHeaderGenerator::new_from_height(20);
+        let headers2 = gen.next_many(3);
+        let responses = headers2.iter().map(|h| h.to_header_response()).collect();
+        handler.on_response_received(req2.peer, req2.id, responses);
+
+        assert_eq!(handler.reqs.len(), 1);
+        assert_eq!(handler.reqs.requested_range(&req2.id), None);
+
+        let mut gen = ExtendedHeaderGenerator::new_from_height(5);
+        let headers1 = gen.next_many(2);
+        let responses = headers1.iter().map(|h| h.to_header_response()).collect();
+        handler.on_response_received(req1.peer, req1.id, responses);
+
+        assert_eq!(handler.reqs.len(), 0);
+
+        let result2 = poll_client_and_receiver(&mut handler, rx2).await.unwrap();
+        assert_eq!(result2, headers2);
+        let result1 = poll_client_and_receiver(&mut handler, rx1).await.unwrap();
+        assert_eq!(result1, headers1);
+    }
+
+    #[async_test]
+    async fn failed_request_untracked() {
+        let peer_tracker = peer_tracker_with_n_peers(15);
+        let mut mock_req = MockReq::new();
+        let mut handler = HeaderExClientHandler::<MockReq>::new(peer_tracker);
+
+        let (tx, rx) = oneshot::channel();
+        handler.on_send_request(&mut mock_req, HeaderRequest::with_origin(5, 1), tx);
+        assert_eq!(handler.reqs.len(), 1);
+
+        mock_req.send_n_failures(&mut handler, 1, OutboundFailure::Timeout);
+        assert_eq!(handler.reqs.len(), 0);
+
+        let result = poll_client_and_receiver(&mut handler, rx).await;
+        assert!(matches!(
+            result,
+            Err(P2pError::HeaderEx(HeaderExError::OutboundFailure(
+                OutboundFailure::Timeout
+            )))
+        ));
+    }
+
+    #[async_test]
+    async fn timed_out_request_retried_with_other_peer() {
+        let peer_tracker = peer_tracker_with_n_peers(2);
+        let mut mock_req = MockReq::new();
+        let mut handler = HeaderExClientHandler::<MockReq>::new(peer_tracker).with_max_retries(1);
+
+        let (tx, rx) = oneshot::channel();
+        handler.on_send_request(&mut mock_req, HeaderRequest::with_origin(5, 1), tx);
+        let first_peer = mock_req.reqs[0].peer;
+
+        mock_req.send_n_failures(&mut handler, 1, OutboundFailure::Timeout);
+        assert_eq!(handler.reqs.len(), 1);
+        assert_eq!(mock_req.reqs.len(), 1);
+        assert_ne!(mock_req.reqs[0].peer, first_peer);
+
+        let expected = ExtendedHeaderGenerator::new_from_height(5).next();
+        mock_req.send_n_responses(&mut handler, 1, vec![expected.to_header_response()]);
+
+        let result = poll_client_and_receiver(&mut handler, rx).await.unwrap();
+        assert_eq!(result, vec![expected]);
+    }
+
+    #[async_test]
+    async fn timed_out_request_not_retried_without_other_peer() {
+        let peer_tracker = peer_tracker_with_n_peers(1);
+        let mut mock_req = MockReq::new();
+        let mut handler = HeaderExClientHandler::<MockReq>::new(peer_tracker).with_max_retries(1);
+
+        let (tx, rx) = oneshot::channel();
+        handler.on_send_request(&mut mock_req, HeaderRequest::with_origin(5, 1), tx);
+
+        mock_req.send_n_failures(&mut handler, 1, OutboundFailure::Timeout);
+        assert_eq!(handler.reqs.len(), 0);
+        assert!(mock_req.reqs.is_empty());
+
+        let result = poll_client_and_receiver(&mut handler, rx).await;
+        assert!(matches!(
+            result,
+            Err(P2pError::HeaderEx(HeaderExError::OutboundFailure(
+                OutboundFailure::Timeout
+            )))
+        ));
+    }
+
     #[async_test]
     async fn request_hash() {
         let peer_tracker = peer_tracker_with_n_peers(15);
@@ -1212,7 +1426,9 @@ mod tests {
             n: usize,
             error: OutboundFailure,
         ) {
-            for req in self.reqs.drain(..n) {
+            let reqs: Vec<_> = self.reqs.drain(..n).collect();
+
+            for req in reqs {
                 // `OutboundFailure` does not implement `Clone`
                 let error = match error {
                     OutboundFailure::DialFailure => OutboundFailure::DialFailure,
@@ -1221,7 +1437,7 @@ mod tests {
                     OutboundFailure::UnsupportedProtocols => OutboundFailure::UnsupportedProtocols,
                     OutboundFailure::Io(ref e) => OutboundFailure::Io(io::Error::new(e.kind(), "")),
                 };
-                handler.on_failure(req.peer, req.id, error);
+                handler.on_failure(self, req.peer, req.id, error);
             }
         }
 
@@ -0,0 +1,693 @@
+//! Client side of the `shrex/nd` protocol, used to request all the [`RowNamespaceData`] of
+//! a namespace in a block directly from a peer.
+//!
+//! [`RowNamespaceData`]: celestia_types::row_namespace_data::RowNamespaceData
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use celestia_proto::shwap::RowNamespaceData as RawRowNamespaceData;
+use celestia_types::nmt::{Namespace, NS_SIZE};
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use libp2p::core::transport::PortUse;
+use libp2p::{
+    core::Endpoint,
+    request_response::{self, Codec, OutboundFailure, OutboundRequestId, ProtocolSupport},
+    swarm::{
+        ConnectionDenied, ConnectionId, FromSwarm, NetworkBehaviour, THandlerInEvent,
+        THandlerOutEvent, ToSwarm,
+    },
+    Multiaddr, PeerId, StreamProtocol,
+};
+use prost::Message;
+use tracing::{debug, instrument, trace};
+use web_time::Duration;
+
+use crate::executor::timeout;
+use crate::p2p::framing::{
+    length_delimited_size, read_length_delimited, write_length_delimited, SizeLimitExceeded,
+};
+use crate::p2p::P2pError;
+use crate::peer_tracker::PeerTracker;
+use crate::utils::{protocol_id, OneshotResultSender, OneshotResultSenderExt, OneshotSenderExt};
+
+/// Size of the encoded [`NamespaceDataId`] in bytes.
+const NAMESPACE_DATA_ID_SIZE: usize = 8 + NS_SIZE;
+/// Time limit on reading/writing a request
+const REQUEST_TIME_LIMIT: Duration = Duration::from_secs(1);
+/// Size limit of the response status in bytes
+const STATUS_SIZE_LIMIT: usize = 16;
+/// Size limit of all the rows in a response in bytes
+const RESPONSE_SIZE_LIMIT: usize = 10 * 1024 * 1024;
+/// Time limit on reading/writing a response
+const RESPONSE_TIME_LIMIT: Duration = Duration::from_secs(5);
+
+type ReqRespBehaviour = request_response::Behaviour<ShrexNdCodec>;
+type ReqRespEvent = request_response::Event<NamespaceDataId, Option<Vec<RawRowNamespaceData>>>;
+type ReqRespMessage = request_response::Message<NamespaceDataId, Option<Vec<RawRowNamespaceData>>>;
+
+/// Identifies the shares of a [`Namespace`] in a block, `NamespaceDataID` of celestia-node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NamespaceDataId {
+    pub height: u64,
+    pub namespace: Namespace,
+}
+
+/// Status of the response, `Status` of the `shrex.proto`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+enum Status {
+    Invalid = 0,
+    Ok = 1,
+    NotFound = 2,
+    Internal = 3,
+}
+
+/// `Response` of the `shrex.proto`, sent before the data.
+///
+/// The proto file doesn't declare a package, so it isn't generated in `celestia-proto`.
+#[derive(Clone, PartialEq, Message)]
+struct RawResponse {
+    #[prost(enumeration = "Status", tag = "1")]
+    status: i32,
+}
+
+pub(crate) struct ShrexNdBehaviour {
+    req_resp: ReqRespBehaviour,
+    peer_tracker: Arc<PeerTracker>,
+    reqs: HashMap<OutboundRequestId, PendingRequest>,
+}
+
+struct PendingRequest {
+    respond_to: OneshotResultSender<Vec<RawRowNamespaceData>, P2pError>,
+}
+
+pub(crate) struct ShrexNdConfig<'a> {
+    pub network_id: &'a str,
+    pub peer_tracker: Arc<PeerTracker>,
+}
+
+/// Representation of all the errors that can occur in `ShrexNd` component.
+#[derive(Debug, thiserror::Error)]
+pub enum ShrexNdError {
+    /// Peer doesn't have the requested data.
+    #[error("Namespace data not found")]
+    NotFound,
+
+    /// The response is invalid.
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+
+    /// Error when handling connection to the server.
+    #[error("Outbound failure: {0}")]
+    OutboundFailure(OutboundFailure),
+
+    /// Request cancelled because [`Node`] is stopping.
+    ///
+    /// [`Node`]: crate::node::Node
+    #[error("Request cancelled because `Node` is stopping")]
+    RequestCancelled,
+}
+
+impl ShrexNdBehaviour {
+    pub(crate) fn new(config: ShrexNdConfig<'_>) -> Self {
+        ShrexNdBehaviour {
+            req_resp: ReqRespBehaviour::new(
+                [(
+                    protocol_id(config.network_id, "/shrex/nd/v0.0.3"),
+                    ProtocolSupport::Outbound,
+                )],
+                request_response::Config::default(),
+            ),
+            peer_tracker: config.peer_tracker,
+            reqs: HashMap::new(),
+        }
+    }
+
+    #[instrument(level = "trace", skip(self, respond_to))]
+    pub(crate) fn send_request(
+        &mut self,
+        id: NamespaceDataId,
+        respond_to: OneshotResultSender<Vec<RawRowNamespaceData>, P2pError>,
+    ) {
+        let Some(peer) = self.peer_tracker.best_peer() else {
+            respond_to.maybe_send_err(P2pError::NoConnectedPeers);
+            return;
+        };
+
+        let req_id = self.req_resp.send_request(&peer, id);
+        self.reqs.insert(req_id, PendingRequest { respond_to });
+
+        trace!("Request initiated");
+    }
+
+    pub(crate) fn stop(&mut self) {
+        for (_, req) in self.reqs.drain() {
+            req.respond_to
+                .maybe_send_err(ShrexNdError::RequestCancelled);
+        }
+    }
+
+    fn on_to_swarm(
+        &mut self,
+        ev: ToSwarm<ReqRespEvent, THandlerInEvent<ReqRespBehaviour>>,
+    ) -> Option<ToSwarm<(), THandlerInEvent<Self>>> {
+        match ev {
+            ToSwarm::GenerateEvent(ev) => {
+                self.on_req_resp_event(ev);
+                None
+            }
+            _ => Some(ev.map_out(|_| ())),
+        }
+    }
+
+    #[instrument(level = "trace", skip_all)]
+    fn on_req_resp_event(&mut self, ev: ReqRespEvent) {
+        match ev {
+            // Received a response for an ongoing outbound request
+            ReqRespEvent::Message {
+                message:
+                    ReqRespMessage::Response {
+                        request_id,
+                        response,
+                    },
+                peer,
+            } => {
+                let Some(req) = self.reqs.remove(&request_id) else {
+                    return;
+                };
+
+                match response {
+                    Some(rows) => req.respond_to.maybe_send_ok(rows),
+                    None => {
+                        debug!("Request to {peer} failed: data not found");
+                        req.respond_to.maybe_send_err(ShrexNdError::NotFound);
+                    }
+                }
+            }
+
+            // Failure while client requests
+            ReqRespEvent::OutboundFailure {
+                peer,
+                request_id,
+                error,
+            } => {
+                debug!("Outbound failure to {peer}: {error}");
+
+                if let Some(req) = self.reqs.remove(&request_id) {
+                    req.respond_to
+                        .maybe_send_err(ShrexNdError::OutboundFailure(error));
+                }
+            }
+
+            // We only support outbound requests
+            ReqRespEvent::Message {
+                message: ReqRespMessage::Request { .. },
+                ..
+            }
+            | ReqRespEvent::ResponseSent { .. }
+            | ReqRespEvent::InboundFailure { .. } => {}
+        }
+    }
+}
+
+impl NetworkBehaviour for ShrexNdBehaviour {
+    type ConnectionHandler = <ReqRespBehaviour as NetworkBehaviour>::ConnectionHandler;
+    type ToSwarm = ();
+
+    fn handle_established_inbound_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        peer: PeerId,
+        local_addr: &Multiaddr,
+        remote_addr: &Multiaddr,
+    ) -> Result<Self::ConnectionHandler, ConnectionDenied> {
+        self.req_resp.handle_established_inbound_connection(
+            connection_id,
+            peer,
+            local_addr,
+            remote_addr,
+        )
+    }
+
+    fn handle_established_outbound_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        peer: PeerId,
+        addr: &Multiaddr,
+        role_override: Endpoint,
+        port_use: PortUse,
+    ) -> Result<Self::ConnectionHandler, ConnectionDenied> {
+        self.req_resp.handle_established_outbound_connection(
+            connection_id,
+            peer,
+            addr,
+            role_override,
+            port_use,
+        )
+    }
+
+    fn handle_pending_inbound_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        local_addr: &Multiaddr,
+        remote_addr: &Multiaddr,
+    ) -> Result<(), ConnectionDenied> {
+        self.req_resp
+            .handle_pending_inbound_connection(connection_id, local_addr, remote_addr)
+    }
+
+    fn handle_pending_outbound_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        maybe_peer: Option<PeerId>,
+        addresses: &[Multiaddr],
+        effective_role: Endpoint,
+    ) -> Result<Vec<Multiaddr>, ConnectionDenied> {
+        self.req_resp.handle_pending_outbound_connection(
+            connection_id,
+            maybe_peer,
+            addresses,
+            effective_role,
+        )
+    }
+
+    fn on_swarm_event(&mut self, event: FromSwarm) {
+        self.req_resp.on_swarm_event(event)
+    }
+
+    fn on_connection_handler_event(
+        &mut self,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        event: THandlerOutEvent<Self>,
+    ) {
+        self.req_resp
+            .on_connection_handler_event(peer_id, connection_id, event)
+    }
+
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
+        while let Poll::Ready(ev) = self.req_resp.poll(cx) {
+            if let Some(ev) = self.on_to_swarm(ev) {
+                return Poll::Ready(ev);
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Codec of the `shrex/nd` protocol.
+///
+/// Request is the binary encoded [`NamespaceDataId`]: the block height as big endian `u64`
+/// followed by the namespace. Response is a length delimited `shrex.Response` with the
+/// status, followed by the length delimited [`RawRowNamespaceData`] of each row which
+/// may contain the namespace, if the status is `OK`.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ShrexNdCodec;
+
+#[async_trait]
+impl Codec for ShrexNdCodec {
+    type Protocol = StreamProtocol;
+    type Request = NamespaceDataId;
+    type Response = Option<Vec<RawRowNamespaceData>>;
+
+    async fn read_request<T>(&mut self, _: &Self::Protocol, io: &mut T) -> io::Result<Self::Request>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut buf = [0u8; NAMESPACE_DATA_ID_SIZE];
+
+        timeout(REQUEST_TIME_LIMIT, io.read_exact(&mut buf))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "reading request timed out"))??;
+
+        let (height, namespace) = buf.split_at(8);
+        let height = u64::from_be_bytes(height.try_into().expect("size checked"));
+        let namespace = Namespace::from_raw(namespace)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        Ok(NamespaceDataId { height, namespace })
+    }
+
+    async fn read_response<T>(
+        &mut self,
+        _: &Self::Protocol,
+        io: &mut T,
+    ) -> io::Result<Self::Response>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        timeout(RESPONSE_TIME_LIMIT, read_rows(io, RESPONSE_SIZE_LIMIT))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "reading response timed out"))?
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        _: &Self::Protocol,
+        io: &mut T,
+        req: Self::Request,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        let mut buf = Vec::with_capacity(NAMESPACE_DATA_ID_SIZE);
+        buf.extend_from_slice(&req.height.to_be_bytes());
+        buf.extend_from_slice(req.namespace.as_bytes());
+
+        timeout(REQUEST_TIME_LIMIT, io.write_all(&buf))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "writing request timed out"))??;
+
+        Ok(())
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        _: &Self::Protocol,
+        io: &mut T,
+        resp: Self::Response,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        timeout(RESPONSE_TIME_LIMIT, write_rows(io, resp))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "writing response timed out"))?
+    }
+}
+
+async fn read_rows<T>(io: &mut T, size_limit: usize) -> io::Result<Option<Vec<RawRowNamespaceData>>>
+where
+    T: AsyncRead + Unpin + Send,
+{
+    let resp: RawResponse = read_length_delimited(io, STATUS_SIZE_LIMIT)
+        .await?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "missing response status"))?;
+
+    match Status::try_from(resp.status) {
+        Ok(Status::Ok) => {}
+        Ok(Status::NotFound) => return Ok(None),
+        status => {
+            let msg = format!("unexpected response status: {status:?}");
+            return Err(io::Error::new(io::ErrorKind::Other, msg));
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut remaining_size = size_limit;
+
+    // rows are sent until the end of the stream
+    while let Some(row) =
+        read_length_delimited::<_, RawRowNamespaceData>(io, remaining_size).await?
+    {
+        // the read is bounded only by the size of the row itself, so the
+        // delimiter may still push it over the limit
+        let size = length_delimited_size(&row);
+        remaining_size = remaining_size.checked_sub(size).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                SizeLimitExceeded {
+                    size,
+                    limit: remaining_size,
+                },
+            )
+        })?;
+        rows.push(row);
+    }
+
+    Ok(Some(rows))
+}
+
+async fn write_rows<T>(io: &mut T, rows: Option<Vec<RawRowNamespaceData>>) -> io::Result<()>
+where
+    T: AsyncWrite + Unpin + Send,
+{
+    let status = match rows {
+        Some(_) => Status::Ok,
+        None => Status::NotFound,
+    };
+
+    write_length_delimited(
+        io,
+        &RawResponse {
+            status: status.into(),
+        },
+    )
+    .await?;
+
+    for row in rows.unwrap_or_default() {
+        write_length_delimited(io, &row).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::async_test;
+    use celestia_types::consts::appconsts::AppVersion;
+    use celestia_types::eds::fixtures::generate;
+    use celestia_types::eds::{ExtendedDataSquare, RawExtendedDataSquare};
+    use celestia_types::row_namespace_data::RowNamespaceData;
+    use celestia_types::DataAvailabilityHeader;
+    use futures::io::Cursor;
+    use std::io::ErrorKind;
+
+    fn stream_protocol() -> StreamProtocol {
+        StreamProtocol::new("/foo/bar/v0.1")
+    }
+
+    #[async_test]
+    async fn request_roundtrip() {
+        let id = NamespaceDataId {
+            height: 10,
+            namespace: Namespace::new_v0(&[1, 2, 3]).unwrap(),
+        };
+        let mut codec = ShrexNdCodec;
+
+        let mut writer = Cursor::new(Vec::new());
+        codec
+            .write_request(&stream_protocol(), &mut writer, id)
+            .await
+            .unwrap();
+
+        let bytes = writer.into_inner();
+        assert_eq!(bytes.len(), NAMESPACE_DATA_ID_SIZE);
+        assert_eq!(&bytes[..8], &10u64.to_be_bytes());
+
+        let mut reader = Cursor::new(bytes);
+        let decoded = codec
+            .read_request(&stream_protocol(), &mut reader)
+            .await
+            .unwrap();
+
+        assert_eq!(decoded, id);
+    }
+
+    #[async_test]
+    async fn rows_response_roundtrip() {
+        let namespace = Namespace::new_v0(&[1, 2, 3]).unwrap();
+        let fixture = generate(1, &[(namespace, 5000)], AppVersion::V2);
+        let rows = fixture
+            .eds
+            .get_namespace_data(namespace, &fixture.dah, 1)
+            .unwrap();
+        assert!(rows.len() > 1);
+        let raw_rows: Vec<_> = rows
+            .iter()
+            .map(|(_, data)| RawRowNamespaceData::from(data.clone()))
+            .collect();
+        let mut codec = ShrexNdCodec;
+
+        let mut writer = Cursor::new(Vec::new());
+        codec
+            .write_response(&stream_protocol(), &mut writer, Some(raw_rows))
+            .await
+            .unwrap();
+
+        // status is sent first
+        let bytes = writer.into_inner();
+        let status = RawResponse::decode_length_delimited(&bytes[..]).unwrap();
+        assert_eq!(status.status, Status::Ok as i32);
+
+        let mut reader = Cursor::new(bytes);
+        let decoded = codec
+            .read_response(&stream_protocol(), &mut reader)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(decoded.len(), rows.len());
+        for (raw, (id, data)) in decoded.into_iter().zip(rows) {
+            let decoded = RowNamespaceData::from_raw(id, raw).unwrap();
+            assert_eq!(decoded, data);
+            decoded.verify(id, &fixture.dah).unwrap();
+        }
+    }
+
+    #[async_test]
+    async fn rows_response_size_limit() {
+        let namespace = Namespace::new_v0(&[1, 2, 3]).unwrap();
+        let fixture = generate(1, &[(namespace, 10)], AppVersion::V2);
+        let (_, row) = fixture
+            .eds
+            .get_namespace_data(namespace, &fixture.dah, 1)
+            .unwrap()
+            .remove(0);
+        let raw_row = RawRowNamespaceData::from(row);
+
+        let mut writer = Cursor::new(Vec::new());
+        write_rows(&mut writer, Some(vec![raw_row.clone()]))
+            .await
+            .unwrap();
+        let bytes = writer.into_inner();
+
+        // the whole frame fits
+        let mut reader = Cursor::new(bytes.clone());
+        let rows = read_rows(&mut reader, length_delimited_size(&raw_row))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rows, vec![raw_row.clone()]);
+
+        // the row body fits, but not its delimiter
+        let mut reader = Cursor::new(bytes);
+        let err = read_rows(&mut reader, raw_row.encoded_len())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(SizeLimitExceeded::is_cause_of(&err));
+    }
+
+    #[async_test]
+    async fn wire_format() {
+        let eds_json = include_str!("../../../types/test_data/shwap_samples/eds.json");
+        let raw_eds: RawExtendedDataSquare = serde_json::from_str(eds_json).unwrap();
+        let eds = ExtendedDataSquare::from_raw(raw_eds, AppVersion::V2).unwrap();
+        let dah_json = include_str!("../../../types/test_data/shwap_samples/dah.json");
+        let dah: DataAvailabilityHeader = serde_json::from_str(dah_json).unwrap();
+
+        let height = 45577;
+        let namespace = Namespace::new_v0(&[1, 170]).unwrap();
+        let mut codec = ShrexNdCodec;
+
+        // 8 byte big endian height followed by the 29 byte namespace
+        let mut request = vec![0, 0, 0, 0, 0, 0, 0xb2, 0x09];
+        request.push(0);
+        request.extend_from_slice(&[0; 18]);
+        request.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 1, 170]);
+
+        let mut writer = Cursor::new(Vec::new());
+        codec
+            .write_request(
+                &stream_protocol(),
+                &mut writer,
+                NamespaceDataId { height, namespace },
+            )
+            .await
+            .unwrap();
+        assert_eq!(writer.into_inner(), request);
+
+        let rows = eds.get_namespace_data(namespace, &dah, height).unwrap();
+        assert!(!rows.is_empty());
+
+        // length delimited `Response { status: OK }`, followed by the length
+        // delimited rows until the end of the stream
+        let mut response = vec![0x02, 0x08, 0x01];
+        for (_, row) in &rows {
+            let row = RawRowNamespaceData::from(row.clone()).encode_to_vec();
+            prost::encode_length_delimiter(row.len(), &mut response).unwrap();
+            response.extend_from_slice(&row);
+        }
+
+        let mut reader = Cursor::new(response);
+        let decoded = codec
+            .read_response(&stream_protocol(), &mut reader)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(decoded.len(), rows.len());
+        for (raw, (id, data)) in decoded.into_iter().zip(rows) {
+            let decoded = RowNamespaceData::from_raw(id, raw).unwrap();
+            assert_eq!(decoded, data);
+            decoded.verify(id, &dah).unwrap();
+        }
+
+        // length delimited `Response { status: NOT_FOUND }`
+        let mut reader = Cursor::new(vec![0x02, 0x08, 0x02]);
+        let resp = codec
+            .read_response(&stream_protocol(), &mut reader)
+            .await
+            .unwrap();
+        assert!(resp.is_none());
+    }
+
+    #[async_test]
+    async fn not_found_response() {
+        let mut codec = ShrexNdCodec;
+
+        let mut writer = Cursor::new(Vec::new());
+        codec
+            .write_response(&stream_protocol(), &mut writer, None)
+            .await
+            .unwrap();
+
+        let mut reader = Cursor::new(writer.into_inner());
+        let resp = codec
+            .read_response(&stream_protocol(), &mut reader)
+            .await
+            .unwrap();
+
+        assert!(resp.is_none());
+    }
+
+    #[async_test]
+    async fn internal_error_response() {
+        let resp = RawResponse {
+            status: Status::Internal.into(),
+        };
+        let mut reader = Cursor::new(resp.encode_length_delimited_to_vec());
+        let mut codec = ShrexNdCodec;
+
+        codec
+            .read_response(&stream_protocol(), &mut reader)
+            .await
+            .unwrap_err();
+    }
+
+    #[async_test]
+    async fn empty_response() {
+        let mut reader = Cursor::new(Vec::new());
+        let mut codec = ShrexNdCodec;
+
+        let err = codec
+            .read_response(&stream_protocol(), &mut reader)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[async_test]
+    async fn truncated_request() {
+        let mut reader = Cursor::new(vec![0; NAMESPACE_DATA_ID_SIZE - 1]);
+        let mut codec = ShrexNdCodec;
+
+        let err = codec
+            .read_request(&stream_protocol(), &mut reader)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
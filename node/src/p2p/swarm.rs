@@ -1,11 +1,60 @@
+use libp2p::connection_limits::ConnectionLimits;
 use libp2p::identity::Keypair;
 use libp2p::swarm::{NetworkBehaviour, Swarm};
+use libp2p::yamux;
 use web_time::Duration;
 
 use crate::p2p::{P2pError, Result};
 
 pub(crate) use self::imp::new_swarm;
 
+/// Default maximum number of concurrent substreams within a single connection.
+pub const DEFAULT_MAX_CONCURRENT_SUBSTREAMS: usize = 256;
+/// Default maximum number of inbound connections that are still being negotiated.
+pub const DEFAULT_MAX_PENDING_INCOMING: u32 = 32;
+/// Default maximum number of established connections with a single peer.
+pub const DEFAULT_MAX_ESTABLISHED_PER_PEER: u32 = 4;
+
+/// Limits applied to the connections and substreams of the transport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransportLimits {
+    /// Maximum number of concurrent substreams within a single yamux or QUIC connection.
+    pub max_concurrent_substreams: usize,
+    /// Maximum number of inbound connections that are still being negotiated.
+    ///
+    /// `None` means no limit.
+    pub max_pending_incoming: Option<u32>,
+    /// Maximum number of established connections with a single peer.
+    ///
+    /// `None` means no limit.
+    pub max_established_per_peer: Option<u32>,
+}
+
+impl Default for TransportLimits {
+    fn default() -> Self {
+        TransportLimits {
+            max_concurrent_substreams: DEFAULT_MAX_CONCURRENT_SUBSTREAMS,
+            max_pending_incoming: Some(DEFAULT_MAX_PENDING_INCOMING),
+            max_established_per_peer: Some(DEFAULT_MAX_ESTABLISHED_PER_PEER),
+        }
+    }
+}
+
+impl TransportLimits {
+    /// Limits to be enforced by the `connection_limits` behaviour.
+    pub(crate) fn connection_limits(&self) -> ConnectionLimits {
+        ConnectionLimits::default()
+            .with_max_pending_incoming(self.max_pending_incoming)
+            .with_max_established_per_peer(self.max_established_per_peer)
+    }
+
+    fn yamux_config(&self) -> yamux::Config {
+        let mut config = yamux::Config::default();
+        config.set_max_num_streams(self.max_concurrent_substreams);
+        config
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 mod imp {
     use std::env;
@@ -15,13 +64,17 @@ mod imp {
     use futures::future::Either;
     use libp2p::core::muxing::StreamMuxerBox;
     use libp2p::core::upgrade::Version;
-    use libp2p::{dns, noise, quic, swarm, tcp, websocket, yamux, PeerId, Transport};
+    use libp2p::{dns, noise, quic, swarm, tcp, websocket, PeerId, Transport};
     use rustls_pki_types::{CertificateDer, PrivateKeyDer};
     use tokio::fs;
 
     use super::*;
 
-    pub(crate) async fn new_swarm<B>(keypair: Keypair, behaviour: B) -> Result<Swarm<B>>
+    pub(crate) async fn new_swarm<B>(
+        keypair: Keypair,
+        behaviour: B,
+        limits: &TransportLimits,
+    ) -> Result<Swarm<B>>
     where
         B: NetworkBehaviour,
     {
@@ -72,15 +125,21 @@ mod imp {
             wss_transport
                 .upgrade(Version::V1Lazy)
                 .authenticate(noise_config.clone())
-                .multiplex(yamux::Config::default())
+                .multiplex(limits.yamux_config())
         };
 
         let tcp_transport = tcp::tokio::Transport::new(tcp::Config::default())
             .upgrade(Version::V1Lazy)
             .authenticate(noise_config)
-            .multiplex(yamux::Config::default());
+            .multiplex(limits.yamux_config());
 
-        let quic_transport = quic::tokio::Transport::new(quic::Config::new(&keypair));
+        let mut quic_config = quic::Config::new(&keypair);
+        quic_config.max_concurrent_stream_limit = limits
+            .max_concurrent_substreams
+            .try_into()
+            .unwrap_or(u32::MAX);
+
+        let quic_transport = quic::tokio::Transport::new(quic_config);
 
         // WSS must be before TCP transport and must not be wrapped in DNS transport.
         let transport = wss_transport
@@ -156,20 +215,99 @@ mod imp {
             Ok(certs)
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use futures::StreamExt;
+        use libp2p::connection_limits;
+        use libp2p::swarm::{ListenError, SwarmEvent};
+        use tokio::time::timeout;
+
+        use super::*;
+
+        #[tokio::test]
+        async fn connection_limits_applied() {
+            let limits = TransportLimits {
+                max_established_per_peer: Some(1),
+                ..TransportLimits::default()
+            };
+
+            let mut listener = new_swarm(
+                Keypair::generate_ed25519(),
+                connection_limits::Behaviour::new(limits.connection_limits()),
+                &limits,
+            )
+            .await
+            .unwrap();
+
+            let mut dialer = new_swarm(
+                Keypair::generate_ed25519(),
+                connection_limits::Behaviour::new(Default::default()),
+                &TransportLimits::default(),
+            )
+            .await
+            .unwrap();
+
+            listener
+                .listen_on("/ip4/127.0.0.1/tcp/0".parse().unwrap())
+                .unwrap();
+
+            let addr = loop {
+                if let SwarmEvent::NewListenAddr { address, .. } = listener.select_next_some().await
+                {
+                    break address;
+                }
+            };
+
+            // Dialing an address without peer id doesn't deduplicate connections
+            dialer.dial(addr.clone()).unwrap();
+            dialer.dial(addr).unwrap();
+
+            let mut established = 0;
+
+            timeout(Duration::from_secs(10), async {
+                loop {
+                    tokio::select! {
+                        ev = listener.select_next_some() => match ev {
+                            SwarmEvent::ConnectionEstablished { .. } => established += 1,
+                            SwarmEvent::IncomingConnectionError {
+                                error: ListenError::Denied { cause },
+                                ..
+                            } => {
+                                cause.downcast::<connection_limits::Exceeded>().unwrap();
+                                break;
+                            }
+                            _ => {}
+                        },
+                        _ = dialer.select_next_some() => {}
+                    }
+                }
+            })
+            .await
+            .expect("second connection was not denied");
+
+            assert_eq!(established, 1);
+        }
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
 mod imp {
     use super::*;
     use libp2p::core::upgrade::Version;
-    use libp2p::{noise, websocket_websys, webtransport_websys, yamux, SwarmBuilder, Transport};
+    use libp2p::{noise, websocket_websys, webtransport_websys, SwarmBuilder, Transport};
 
-    pub(crate) async fn new_swarm<B>(keypair: Keypair, behaviour: B) -> Result<Swarm<B>>
+    pub(crate) async fn new_swarm<B>(
+        keypair: Keypair,
+        behaviour: B,
+        limits: &TransportLimits,
+    ) -> Result<Swarm<B>>
     where
         B: NetworkBehaviour,
     {
         let noise_config =
             noise::Config::new(&keypair).map_err(|e| P2pError::NoiseInit(e.to_string()))?;
+        let yamux_config = limits.yamux_config();
 
         Ok(SwarmBuilder::with_existing_identity(keypair)
             .with_wasm_bindgen()
@@ -177,7 +315,7 @@ mod imp {
                 Ok(websocket_websys::Transport::default()
                     .upgrade(Version::V1Lazy)
                     .authenticate(noise_config)
-                    .multiplex(yamux::Config::default()))
+                    .multiplex(yamux_config))
             })
             .expect("websocket_websys::Transport is infallible")
             .with_other_transport(|local_keypair| {
@@ -5,13 +5,18 @@ use std::borrow::Borrow;
 use dashmap::mapref::entry::Entry;
 use dashmap::mapref::one::RefMut;
 use dashmap::DashMap;
-use libp2p::{swarm::ConnectionId, Multiaddr, PeerId};
+use libp2p::{identify, swarm::ConnectionId, Multiaddr, PeerId, StreamProtocol};
 use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use tokio::sync::watch;
 
-use crate::events::{EventPublisher, NodeEvent};
+use crate::events::{EventPublisher, NodeEvent, PeerScoreReason};
+
+/// Lowest score a peer can have.
+const MIN_PEER_SCORE: i32 = -100;
+/// Highest score a peer can have.
+const MAX_PEER_SCORE: i32 = 100;
 
 /// Keeps track various information about peers.
 #[derive(Debug)]
@@ -37,6 +42,26 @@ struct PeerInfo {
     addrs: SmallVec<[Multiaddr; 4]>,
     connections: SmallVec<[ConnectionId; 1]>,
     trusted: bool,
+    agent_version: Option<String>,
+    protocols: Vec<StreamProtocol>,
+    score: i32,
+}
+
+/// Snapshot of the information known about a peer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerDetails {
+    /// Id of the peer.
+    pub id: PeerId,
+    /// Known addresses of the peer.
+    pub addrs: Vec<Multiaddr>,
+    /// Protocols supported by the peer, as reported by `identify`.
+    pub protocols: Vec<StreamProtocol>,
+    /// Agent version of the peer, as reported by `identify`.
+    pub agent_version: Option<String>,
+    /// Whether the peer is currently connected.
+    pub connected: bool,
+    /// Whether the peer is trusted.
+    pub trusted: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -83,6 +108,9 @@ impl PeerTracker {
                     addrs: SmallVec::new(),
                     connections: SmallVec::new(),
                     trusted: false,
+                    agent_version: None,
+                    protocols: Vec::new(),
+                    score: 0,
                 });
                 true
             }
@@ -99,6 +127,9 @@ impl PeerTracker {
             addrs: SmallVec::new(),
             connections: SmallVec::new(),
             trusted: false,
+            agent_version: None,
+            protocols: Vec::new(),
+            score: 0,
         })
     }
 
@@ -180,8 +211,15 @@ impl PeerTracker {
 
     /// Sets peer as disconnected if `connection_id` was the last connection.
     ///
+    /// `reason` is reported in the [`NodeEvent::PeerDisconnected`] event.
+    ///
     /// Returns `true` if was set to disconnected.
-    pub fn set_maybe_disconnected(&self, peer: PeerId, connection_id: ConnectionId) -> bool {
+    pub fn set_maybe_disconnected(
+        &self,
+        peer: PeerId,
+        connection_id: ConnectionId,
+        reason: Option<String>,
+    ) -> bool {
         let mut peer_info = self.get(peer);
 
         peer_info.connections.retain(|id| *id != connection_id);
@@ -199,6 +237,7 @@ impl PeerTracker {
             self.event_pub.send(NodeEvent::PeerDisconnected {
                 id: peer,
                 trusted: peer_info.trusted,
+                reason,
             });
 
             true
@@ -219,12 +258,69 @@ impl PeerTracker {
         self.get(peer).addrs.clone()
     }
 
+    /// Sets the agent version and supported protocols reported by the peer's `identify`.
+    pub fn set_identify_info(&self, peer: PeerId, info: &identify::Info) {
+        let mut peer_info = self.get(peer);
+        peer_info.agent_version = Some(info.agent_version.clone());
+        peer_info.protocols = info.protocols.clone();
+    }
+
+    /// Updates the score of the peer for the given reason.
+    ///
+    /// Emits [`NodeEvent::PeerScoreUpdated`] if the score changed.
+    pub fn update_score(&self, peer: PeerId, reason: PeerScoreReason) {
+        let score = {
+            let mut peer_info = self.get(peer);
+            let score = peer_info
+                .score
+                .saturating_add(score_delta(reason))
+                .clamp(MIN_PEER_SCORE, MAX_PEER_SCORE);
+
+            if score == peer_info.score {
+                return;
+            }
+
+            peer_info.score = score;
+            score
+        };
+
+        self.event_pub.send(NodeEvent::PeerScoreUpdated {
+            id: peer,
+            score,
+            reason,
+        });
+    }
+
+    /// Returns the score of the peer.
+    #[cfg(test)]
+    pub fn score(&self, peer: PeerId) -> i32 {
+        self.get(peer).score
+    }
+
     /// Removes a peer.
     #[allow(dead_code)]
     pub fn remove(&self, peer: PeerId) {
         self.peers.remove(&peer);
     }
 
+    /// Returns a snapshot of all the known peers.
+    pub fn peers(&self) -> Vec<PeerDetails> {
+        self.peers
+            .iter()
+            .map(|pair| {
+                let info = pair.value();
+                PeerDetails {
+                    id: *pair.key(),
+                    addrs: info.addrs.to_vec(),
+                    protocols: info.protocols.clone(),
+                    agent_version: info.agent_version.clone(),
+                    connected: info.is_connected(),
+                    trusted: info.trusted,
+                }
+            })
+            .collect()
+    }
+
     /// Returns connected peers.
     pub fn connected_peers(&self) -> Vec<PeerId> {
         self.peers
@@ -261,7 +357,6 @@ impl PeerTracker {
     }
 
     /// Returns up to N amount of best peers.
-    #[allow(dead_code)]
     pub fn best_n_peers(&self, limit: usize) -> Vec<PeerId> {
         // TODO: Implement peer score and return the best N peers.
         self.peers
@@ -285,6 +380,14 @@ impl PeerTracker {
     }
 }
 
+fn score_delta(reason: PeerScoreReason) -> i32 {
+    match reason {
+        PeerScoreReason::GossipMisbehavior => -20,
+        PeerScoreReason::HighLatency => -5,
+        PeerScoreReason::HeaderDelivered => 1,
+    }
+}
+
 fn increment_connected_peers(info_tx: &watch::Sender<PeerTrackerInfo>, trusted: bool) {
     info_tx.send_modify(|tracker_info| {
         tracker_info.num_connected_peers += 1;
@@ -376,4 +479,120 @@ mod tests {
         assert_eq!(info.num_connected_peers, 1);
         assert_eq!(info.num_connected_trusted_peers, 0);
     }
+
+    #[test]
+    fn identify_info() {
+        let event_channel = EventChannel::new();
+        let tracker = PeerTracker::new(event_channel.publisher());
+        let keypair = libp2p::identity::Keypair::generate_ed25519();
+        let peer = keypair.public().to_peer_id();
+        let addr: Multiaddr = "/ip4/1.2.3.4/tcp/2121".parse().unwrap();
+
+        assert!(tracker.peers().is_empty());
+
+        tracker.add_addresses(peer, [addr.clone()]);
+        tracker.set_connected(peer, ConnectionId::new_unchecked(1), None);
+        tracker.set_identify_info(
+            peer,
+            &identify::Info {
+                public_key: keypair.public(),
+                protocol_version: "/celestia/private".to_string(),
+                agent_version: "lumina/private/0.1.0".to_string(),
+                listen_addrs: vec![addr.clone()],
+                protocols: vec![
+                    StreamProtocol::new("/private/header-ex/v0.0.3"),
+                    StreamProtocol::new("/ipfs/id/1.0.0"),
+                ],
+                observed_addr: addr.clone(),
+            },
+        );
+
+        assert_eq!(
+            tracker.peers(),
+            vec![PeerDetails {
+                id: peer,
+                addrs: vec![addr],
+                protocols: vec![
+                    StreamProtocol::new("/private/header-ex/v0.0.3"),
+                    StreamProtocol::new("/ipfs/id/1.0.0"),
+                ],
+                agent_version: Some("lumina/private/0.1.0".to_string()),
+                connected: true,
+                trusted: false,
+            }]
+        );
+
+        // disconnected peers stay in the table
+        tracker.set_maybe_disconnected(peer, ConnectionId::new_unchecked(1), None);
+        assert!(!tracker.peers()[0].connected);
+    }
+
+    #[test]
+    fn disconnect_reason() {
+        let event_channel = EventChannel::new();
+        let mut event_sub = event_channel.subscribe();
+        let tracker = PeerTracker::new(event_channel.publisher());
+        let peer = PeerId::random();
+
+        tracker.set_connected(peer, ConnectionId::new_unchecked(1), None);
+        tracker.set_connected(peer, ConnectionId::new_unchecked(2), None);
+
+        let ev = event_sub.try_recv().unwrap();
+        assert!(matches!(ev.event, NodeEvent::PeerConnected { id, .. } if id == peer));
+
+        // Peer still has one connection open
+        assert!(!tracker.set_maybe_disconnected(
+            peer,
+            ConnectionId::new_unchecked(1),
+            Some("Connection reset".to_string())
+        ));
+        event_sub.try_recv().unwrap_err();
+
+        assert!(tracker.set_maybe_disconnected(
+            peer,
+            ConnectionId::new_unchecked(2),
+            Some("Keep-alive timeout".to_string())
+        ));
+
+        let ev = event_sub.try_recv().unwrap();
+        match ev.event {
+            NodeEvent::PeerDisconnected { id, reason, .. } => {
+                assert_eq!(id, peer);
+                assert_eq!(reason.as_deref(), Some("Keep-alive timeout"));
+            }
+            ev => panic!("Unexpected event: {ev:?}"),
+        }
+    }
+
+    #[test]
+    fn score_updates() {
+        let event_channel = EventChannel::new();
+        let mut event_sub = event_channel.subscribe();
+        let tracker = PeerTracker::new(event_channel.publisher());
+        let peer = PeerId::random();
+
+        tracker.update_score(peer, PeerScoreReason::HeaderDelivered);
+        assert_eq!(tracker.score(peer), 1);
+
+        let ev = event_sub.try_recv().unwrap();
+        assert!(matches!(
+            ev.event,
+            NodeEvent::PeerScoreUpdated {
+                id,
+                score: 1,
+                reason: PeerScoreReason::HeaderDelivered,
+            } if id == peer
+        ));
+
+        for _ in 0..10 {
+            tracker.update_score(peer, PeerScoreReason::GossipMisbehavior);
+        }
+        assert_eq!(tracker.score(peer), MIN_PEER_SCORE);
+
+        // Only changes of the score are reported
+        for _ in 0..6 {
+            event_sub.try_recv().unwrap();
+        }
+        event_sub.try_recv().unwrap_err();
+    }
 }
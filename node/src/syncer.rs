@@ -16,6 +16,7 @@ use std::time::Duration;
 
 use backoff::backoff::Backoff;
 use backoff::ExponentialBackoffBuilder;
+use celestia_types::hash::Hash;
 use celestia_types::ExtendedHeader;
 use serde::{Deserialize, Serialize};
 use tendermint::Time;
@@ -30,7 +31,7 @@ use crate::events::{EventPublisher, NodeEvent};
 use crate::executor::{sleep, spawn, Interval, JoinHandle};
 use crate::p2p::{P2p, P2pError};
 use crate::store::{Store, StoreError};
-use crate::utils::{FusedReusableFuture, OneshotSenderExt};
+use crate::utils::{otel, FusedReusableFuture, OneshotSenderExt};
 
 type Result<T, E = SyncerError> = std::result::Result<T, E>;
 
@@ -99,6 +100,8 @@ where
     pub(crate) batch_size: u64,
     /// Syncing window
     pub(crate) syncing_window: Duration,
+    /// Expected hash of the genesis header, if known.
+    pub(crate) genesis_hash: Option<Hash>,
 }
 
 #[derive(Debug)]
@@ -106,6 +109,9 @@ enum SyncerCmd {
     GetInfo {
         respond_to: oneshot::Sender<SyncingInfo>,
     },
+    SetPaused {
+        paused: bool,
+    },
 }
 
 /// Status of the synchronization.
@@ -177,6 +183,18 @@ where
 
         Ok(rx.await?)
     }
+
+    /// Pause or resume the synchronization.
+    ///
+    /// While paused, no new headers are fetched from the network. Ongoing
+    /// batch is allowed to finish.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the [`Syncer`] has been stopped.
+    pub(crate) async fn set_paused(&self, paused: bool) -> Result<()> {
+        self.send_command(SyncerCmd::SetPaused { paused }).await
+    }
 }
 
 impl<S> Drop for Syncer<S>
@@ -202,6 +220,8 @@ where
     batch_size: u64,
     ongoing_batch: Ongoing,
     syncing_window: Duration,
+    genesis_hash: Option<Hash>,
+    paused: bool,
 }
 
 struct Ongoing {
@@ -232,6 +252,8 @@ where
                 task: FusedReusableFuture::terminated(),
             },
             syncing_window: args.syncing_window,
+            genesis_hash: args.genesis_hash,
+            paused: false,
         })
     }
 
@@ -346,6 +368,8 @@ where
                 }
                 Some(cmd) = self.cmd_rx.recv() => {
                     self.on_cmd(cmd).await?;
+                    // Syncing may have been resumed.
+                    self.fetch_next_batch().await?;
                 }
                 (res, took) = &mut self.ongoing_batch.task => {
                     self.on_fetch_next_batch_result(res, took).await?;
@@ -395,6 +419,12 @@ where
                 let info = self.syncing_info().await?;
                 respond_to.maybe_send(info);
             }
+            SyncerCmd::SetPaused { paused } => {
+                if paused != self.paused {
+                    info!("Syncing {}", if paused { "paused" } else { "resumed" });
+                    self.paused = paused;
+                }
+            }
         }
 
         Ok(())
@@ -406,6 +436,11 @@ where
 
         self.set_subjective_head_height(new_head_height);
 
+        if self.paused {
+            // Header will be fetched after syncing is resumed.
+            return Ok(());
+        }
+
         if let Ok(store_head_height) = self.store.head_height().await {
             // If our new header is adjacent to the HEAD of the store
             if store_head_height + 1 == new_head_height {
@@ -448,6 +483,11 @@ where
             return Ok(());
         }
 
+        if self.paused {
+            // Syncing is paused, nothing to schedule.
+            return Ok(());
+        }
+
         if self.p2p.peer_tracker_info().num_connected_peers == 0 {
             // No connected peers. We can't do the request.
             // We will recover from this in `run`.
@@ -492,12 +532,14 @@ where
 
         self.ongoing_batch.range = Some(next_batch.clone());
 
-        self.ongoing_batch.task.set(async move {
+        let fetch = otel::fetch_headers(*next_batch.start(), *next_batch.end(), async move {
             let now = Instant::now();
             let res = p2p.get_unverified_header_range(next_batch).await;
             (res, now.elapsed())
         });
 
+        self.ongoing_batch.task.set(fetch);
+
         Ok(())
     }
 
@@ -535,6 +577,28 @@ where
             }
         };
 
+        // Headers are verified only against each other, so the genesis one
+        // has to be checked against the known hash.
+        let genesis = headers
+            .first()
+            .filter(|header| header.height().value() == 1);
+
+        if let (Some(genesis), Some(genesis_hash)) = (genesis, self.genesis_hash) {
+            if genesis.hash() != genesis_hash {
+                self.event_pub.send(NodeEvent::FetchingHeadersFailed {
+                    from_height,
+                    to_height,
+                    error: format!(
+                        "Genesis header hash ({}) != expected genesis hash ({genesis_hash})",
+                        genesis.hash()
+                    ),
+                    took,
+                });
+
+                return Ok(());
+            }
+        }
+
         if let Err(e) = self.store.insert(headers).await {
             if e.is_fatal() {
                 return Err(e.into());
@@ -788,6 +852,7 @@ mod tests {
             event_pub: events.publisher(),
             batch_size: 512,
             syncing_window: DEFAULT_SAMPLING_WINDOW,
+            genesis_hash: None,
         })
         .unwrap();
 
@@ -936,6 +1001,7 @@ mod tests {
             event_pub: events.publisher(),
             batch_size: 512,
             syncing_window: DEFAULT_SAMPLING_WINDOW,
+            genesis_hash: None,
         })
         .unwrap();
 
@@ -1145,6 +1211,53 @@ mod tests {
         assert_syncing(&syncer, &store, &[1..=20], 20).await;
     }
 
+    #[async_test]
+    async fn genesis_hash_mismatch() {
+        let events = EventChannel::new();
+        let mut event_sub = events.subscribe();
+        let (mock, mut handle) = P2p::mocked();
+        let store = Arc::new(InMemoryStore::new());
+        let mut gen = ExtendedHeaderGenerator::new();
+        let headers = gen.next_many(26);
+
+        let syncer = Syncer::start(SyncerArgs {
+            p2p: Arc::new(mock),
+            store: store.clone(),
+            event_pub: events.publisher(),
+            batch_size: 512,
+            syncing_window: DEFAULT_SAMPLING_WINDOW,
+            genesis_hash: Some(Hash::Sha256([0; 32])),
+        })
+        .unwrap();
+
+        handle.announce_trusted_peer_connected();
+
+        let (height, amount, respond_to) = handle.expect_header_request_for_height_cmd().await;
+        assert_eq!(height, 0);
+        assert_eq!(amount, 1);
+        respond_to.send(Ok(vec![headers[25].clone()])).unwrap();
+        handle.expect_init_header_sub().await;
+
+        // The batch containing the genesis header is rejected
+        handle_session_batch(&mut handle, &headers, 1..=25, true).await;
+
+        loop {
+            let ev = event_sub.recv().await.unwrap();
+
+            if let NodeEvent::FetchingHeadersFailed {
+                from_height: 1,
+                error,
+                ..
+            } = ev.event
+            {
+                assert!(error.contains("Genesis header hash"), "{error}");
+                break;
+            }
+        }
+
+        assert_syncing(&syncer, &store, &[26..=26], 26).await;
+    }
+
     async fn assert_syncing(
         syncer: &Syncer<InMemoryStore>,
         store: &InMemoryStore,
@@ -1176,6 +1289,7 @@ mod tests {
             event_pub: events.publisher(),
             batch_size: 512,
             syncing_window: DEFAULT_SAMPLING_WINDOW,
+            genesis_hash: None,
         })
         .unwrap();
 
@@ -6,6 +6,7 @@ use tokio::sync::oneshot;
 #[cfg(not(target_arch = "wasm32"))]
 mod counter;
 mod fused_reusable_future;
+pub(crate) mod otel;
 mod token;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -0,0 +1,117 @@
+//! Spans of the syncing and sampling operations.
+//!
+//! With the `otel` feature enabled, operations are wrapped in [`tracing`] spans carrying
+//! the heights and the duration of the operation. They are meant to be exported to
+//! OpenTelemetry, e.g. over OTLP with `tracing-opentelemetry` and `opentelemetry-otlp`.
+//! Without the feature, futures are returned as is.
+
+use std::future::Future;
+
+/// Instrument fetching of the headers in the given range.
+pub(crate) fn fetch_headers<F>(
+    from_height: u64,
+    to_height: u64,
+    fut: F,
+) -> impl Future<Output = F::Output>
+where
+    F: Future,
+{
+    #[cfg(feature = "otel")]
+    {
+        let span = tracing::info_span!(
+            "fetch_headers",
+            from_height,
+            to_height,
+            duration_ms = tracing::field::Empty,
+        );
+        imp::in_span(span, fut)
+    }
+
+    #[cfg(not(feature = "otel"))]
+    {
+        let _ = (from_height, to_height);
+        fut
+    }
+}
+
+/// Instrument sampling of the block at the given height.
+pub(crate) fn sampling<F>(height: u64, square_width: u16, fut: F) -> impl Future<Output = F::Output>
+where
+    F: Future,
+{
+    #[cfg(feature = "otel")]
+    {
+        let span = tracing::info_span!(
+            "sampling",
+            height,
+            square_width,
+            duration_ms = tracing::field::Empty,
+        );
+        imp::in_span(span, fut)
+    }
+
+    #[cfg(not(feature = "otel"))]
+    {
+        let _ = (height, square_width);
+        fut
+    }
+}
+
+#[cfg(feature = "otel")]
+mod imp {
+    use std::future::Future;
+
+    use tracing::{Instrument, Span};
+    use web_time::Instant;
+
+    pub(super) async fn in_span<F>(span: Span, fut: F) -> F::Output
+    where
+        F: Future,
+    {
+        let now = Instant::now();
+        let output = fut.instrument(span.clone()).await;
+        span.record("duration_ms", now.elapsed().as_millis() as u64);
+        output
+    }
+}
+
+#[cfg(all(test, feature = "otel", not(target_arch = "wasm32")))]
+mod tests {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry::Value;
+    use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+    use opentelemetry_sdk::trace::TracerProvider;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[test]
+    fn fetch_headers_span() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            futures::executor::block_on(fetch_headers(5, 10, async {}));
+        });
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].name, "fetch_headers");
+
+        let attribute = |key: &str| {
+            spans[0]
+                .attributes
+                .iter()
+                .find(|kv| kv.key.as_str() == key)
+                .map(|kv| kv.value.clone())
+        };
+
+        assert_eq!(attribute("from_height"), Some(Value::I64(5)));
+        assert_eq!(attribute("to_height"), Some(Value::I64(10)));
+        assert!(attribute("duration_ms").is_some());
+    }
+}
@@ -0,0 +1,32 @@
+//! Authentication of the requests to the celestia-node.
+
+use http::header::{self, HeaderMap, HeaderValue};
+
+use crate::Error;
+
+/// Build the headers authenticating requests with the given token.
+///
+/// Returns the `Authorization: Bearer <token>` header, or no headers if the
+/// token is `None`. The headers can be used with both http and websocket
+/// clients of [`jsonrpsee`].
+///
+/// # Example
+///
+/// ```
+/// use celestia_rpc::auth::bearer_headers;
+///
+/// let headers = bearer_headers(Some("token")).unwrap();
+/// assert_eq!(headers["authorization"], "Bearer token");
+///
+/// assert!(bearer_headers(None).unwrap().is_empty());
+/// ```
+pub fn bearer_headers(token: Option<&str>) -> Result<HeaderMap, Error> {
+    let mut headers = HeaderMap::new();
+
+    if let Some(token) = token {
+        let val = HeaderValue::from_str(&format!("Bearer {token}"))?;
+        headers.insert(header::AUTHORIZATION, val);
+    }
+
+    Ok(headers)
+}
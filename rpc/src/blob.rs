@@ -1,11 +1,176 @@
 //! celestia-node rpc types and methods related to blobs
 
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::marker::{Send, Sync};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+use celestia_types::blob::{self, MsgPayForBlobs};
+use celestia_types::consts::appconsts::AppVersion;
 use celestia_types::nmt::{Namespace, NamespaceProof};
-use celestia_types::{Blob, Commitment};
+use celestia_types::state::AccAddress;
+use celestia_types::{Blob, Commitment, ExtendedHeader};
+#[cfg(not(target_arch = "wasm32"))]
+use futures::future::{self, Either};
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+#[cfg(not(target_arch = "wasm32"))]
+use futures_timer::Delay;
+use jsonrpsee::core::client::{Error, Subscription, SubscriptionClientT};
 use jsonrpsee::proc_macros::rpc;
 use serde::{Deserialize, Serialize};
 
-use crate::TxConfig;
+use crate::{HeaderClient, StateClient, TxConfig, DEFAULT_MIN_GAS_PRICE};
+
+/// Messages of the errors returned when transaction's gas price is too low.
+const INSUFFICIENT_GAS_PRICE: &[&str] = &["insufficient fee", "insufficient minimum gas price"];
+
+/// Maximum number of heights searched by [`BlobClient::blob_submit_idempotent`]
+/// for blobs whose previous submission has an unknown outcome.
+pub const MAX_IDEMPOTENT_SEARCH_HEIGHTS: u64 = 64;
+
+/// Record of a blob submission kept in [`SubmitLog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubmitRecord {
+    /// Network head height at the time submission was attempted.
+    pub attempted_at: u64,
+    /// A height in which the blob was included, if submission is known to succeed.
+    pub included_at: Option<u64>,
+}
+
+/// Log of blob submissions used by [`BlobClient::blob_submit_idempotent`].
+///
+/// Implement it on top of a persistent storage to keep the submissions across restarts.
+pub trait SubmitLog {
+    /// Get the record of the blob with the given commitment.
+    fn get(&self, commitment: &Commitment) -> Option<SubmitRecord>;
+
+    /// Insert or replace the record of the blob with the given commitment.
+    fn insert(&mut self, commitment: Commitment, record: SubmitRecord);
+}
+
+impl SubmitLog for BTreeMap<Commitment, SubmitRecord> {
+    fn get(&self, commitment: &Commitment) -> Option<SubmitRecord> {
+        BTreeMap::get(self, commitment).copied()
+    }
+
+    fn insert(&mut self, commitment: Commitment, record: SubmitRecord) {
+        BTreeMap::insert(self, commitment, record);
+    }
+}
+
+/// Preview of a PayForBlobs transaction returned by [`BlobClient::blob_submit_dry_run`].
+#[derive(Debug, Clone)]
+pub struct PfbPreview {
+    /// The message which would be submitted.
+    pub msg: MsgPayForBlobs,
+    /// Gas limit of the transaction, either the one from [`TxConfig`] or estimated.
+    pub gas: u64,
+}
+
+impl PfbPreview {
+    /// Share commitments of the blobs, in the order they were given.
+    pub fn commitments(&self) -> &[Commitment] {
+        &self.msg.share_commitments
+    }
+}
+
+/// State of the submission reported by [`BlobClient::blob_submit_tracked`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitState {
+    /// The PayForBlobs transaction was broadcast successfully.
+    Broadcast,
+    /// The transaction was committed in the block at the given height.
+    Committed {
+        /// Height of the block including the blobs.
+        height: u64,
+    },
+    /// The header at the height was received and the inclusion of all the blobs
+    /// was verified against it.
+    Confirmed {
+        /// Height of the block including the blobs.
+        height: u64,
+    },
+    /// The submission failed, no further states follow.
+    Failed {
+        /// Reason of the failure.
+        reason: String,
+    },
+}
+
+impl SubmitState {
+    fn failed(error: Error) -> Self {
+        SubmitState::Failed {
+            reason: error.to_string(),
+        }
+    }
+}
+
+/// Step of the [`BlobClient::blob_submit_tracked`] stream.
+enum SubmitStep {
+    Submit(TxConfig),
+    Committed(u64),
+    Confirm(u64),
+}
+
+/// State of the [`BlobClient::blob_stream_from`] stream.
+struct ReplayState {
+    /// Sorted and deduplicated namespaces of the stream.
+    namespaces: Vec<Namespace>,
+    /// The next height to be yielded.
+    next_height: u64,
+    /// Heights up to which blobs can be fetched directly from the node.
+    backfill_until: u64,
+    /// Merged subscriptions of all the namespaces, created on the first poll.
+    live: Option<BoxStream<'static, Result<BlobsAtHeight, Error>>>,
+    /// Blobs received from the subscriptions, with the number of namespaces reported.
+    pending: BTreeMap<u64, (usize, Vec<Blob>)>,
+}
+
+/// Filter of the blobs, applied client side to the blobs returned by the node.
+///
+/// Used by [`BlobClient::blob_get_all_filtered`] and [`BlobClient::blob_subscribe_filtered`].
+/// Fields which are not set match all the blobs, so the default filter matches everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlobFilter {
+    /// Minimum size of the blob's data, inclusive.
+    pub min_size: Option<usize>,
+    /// Maximum size of the blob's data, inclusive.
+    pub max_size: Option<usize>,
+    /// Share version of the blob.
+    pub share_version: Option<u8>,
+    /// Address of the account which signed the blob.
+    ///
+    /// Only blobs with share version one have a signer, so other blobs never match it.
+    pub signer: Option<AccAddress>,
+}
+
+impl BlobFilter {
+    /// Returns `true` if the blob matches the filter.
+    pub fn matches(&self, blob: &Blob) -> bool {
+        let size = blob.data.len();
+
+        self.min_size.map_or(true, |min| size >= min)
+            && self.max_size.map_or(true, |max| size <= max)
+            && self
+                .share_version
+                .map_or(true, |version| blob.share_version == version)
+            && self
+                .signer
+                .as_ref()
+                .map_or(true, |signer| blob.signer() == Some(signer))
+    }
+
+    /// Keep only the blobs matching the filter.
+    ///
+    /// Returns `None` if none of the blobs match, the same way the node does
+    /// when there are no blobs.
+    fn apply(&self, blobs: Option<Vec<Blob>>) -> Option<Vec<Blob>> {
+        let mut blobs = blobs?;
+        blobs.retain(|blob| self.matches(blob));
+        (!blobs.is_empty()).then_some(blobs)
+    }
+}
 
 /// Response type for [`BlobClient::blob_subscribe`].
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -17,53 +182,735 @@ pub struct BlobsAtHeight {
     pub height: u64,
 }
 
-#[rpc(client)]
-pub trait Blob {
+mod rpc {
+    use super::*;
+
+    #[rpc(client)]
+    pub trait Blob {
+        #[method(name = "blob.Get")]
+        async fn blob_get(
+            &self,
+            height: u64,
+            namespace: Namespace,
+            commitment: Commitment,
+        ) -> Result<Blob, Error>;
+
+        #[method(name = "blob.GetAll")]
+        async fn blob_get_all(
+            &self,
+            height: u64,
+            namespaces: &[Namespace],
+        ) -> Result<Option<Vec<Blob>>, Error>;
+
+        #[method(name = "blob.GetProof")]
+        async fn blob_get_proof(
+            &self,
+            height: u64,
+            namespace: Namespace,
+            commitment: Commitment,
+        ) -> Result<Vec<NamespaceProof>, Error>;
+
+        #[method(name = "blob.Included")]
+        async fn blob_included(
+            &self,
+            height: u64,
+            namespace: Namespace,
+            proof: &NamespaceProof,
+            commitment: Commitment,
+        ) -> Result<bool, Error>;
+
+        #[method(name = "blob.Submit")]
+        async fn blob_submit(&self, blobs: &[Blob], opts: TxConfig) -> Result<u64, Error>;
+
+        #[subscription(name = "blob.Subscribe", unsubscribe = "blob.Unsubscribe", item = BlobsAtHeight)]
+        async fn blob_subscribe(&self, namespace: Namespace) -> SubcriptionResult;
+    }
+}
+
+/// Client implementation for the `Blob` RPC API.
+pub trait BlobClient: SubscriptionClientT {
     /// Get retrieves the blob by commitment under the given namespace and height.
-    #[method(name = "blob.Get")]
-    async fn blob_get(
-        &self,
+    fn blob_get<'a, 'fut>(
+        &'a self,
         height: u64,
         namespace: Namespace,
         commitment: Commitment,
-    ) -> Result<Blob, Error>;
+    ) -> impl Future<Output = Result<Blob, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::BlobClient::blob_get(self, height, namespace, commitment)
+    }
 
     /// GetAll returns all blobs under the given namespaces and height.
-    #[method(name = "blob.GetAll")]
-    async fn blob_get_all(
-        &self,
+    fn blob_get_all<'a, 'b, 'fut>(
+        &'a self,
+        height: u64,
+        namespaces: &'b [Namespace],
+    ) -> impl Future<Output = Result<Option<Vec<Blob>>, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::BlobClient::blob_get_all(self, height, namespaces)
+    }
+
+    /// Get all blobs under the given namespaces and height, matching the [`BlobFilter`].
+    ///
+    /// Filtering is done client side, after the blobs are received from the node.
+    fn blob_get_all_filtered<'a, 'b, 'fut>(
+        &'a self,
         height: u64,
-        namespaces: &[Namespace],
-    ) -> Result<Option<Vec<Blob>>, Error>;
+        namespaces: &'b [Namespace],
+        filter: BlobFilter,
+    ) -> impl Future<Output = Result<Option<Vec<Blob>>, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let blobs = rpc::BlobClient::blob_get_all(self, height, namespaces).await?;
+            Ok(filter.apply(blobs))
+        }
+    }
 
     /// GetProof retrieves proofs in the given namespaces at the given height by commitment.
-    #[method(name = "blob.GetProof")]
-    async fn blob_get_proof(
-        &self,
+    fn blob_get_proof<'a, 'fut>(
+        &'a self,
         height: u64,
         namespace: Namespace,
         commitment: Commitment,
-    ) -> Result<Vec<NamespaceProof>, Error>;
+    ) -> impl Future<Output = Result<Vec<NamespaceProof>, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::BlobClient::blob_get_proof(self, height, namespace, commitment)
+    }
 
     /// Included checks whether a blob's given commitment(Merkle subtree root) is included at given height and under the namespace.
-    #[method(name = "blob.Included")]
-    async fn blob_included(
-        &self,
+    fn blob_included<'a, 'b, 'fut>(
+        &'a self,
         height: u64,
         namespace: Namespace,
-        proof: &NamespaceProof,
+        proof: &'b NamespaceProof,
         commitment: Commitment,
-    ) -> Result<bool, Error>;
+    ) -> impl Future<Output = Result<bool, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::BlobClient::blob_included(self, height, namespace, proof, commitment)
+    }
 
     /// Submit sends Blobs and reports the height in which they were included. Allows sending multiple Blobs atomically synchronously. Uses default wallet registered on the Node.
-    #[method(name = "blob.Submit")]
-    async fn blob_submit(&self, blobs: &[Blob], opts: TxConfig) -> Result<u64, Error>;
+    ///
+    /// If [`TxConfig::auto_bump`] is set, submissions rejected due to too low gas price
+    /// are retried with the bumped gas price.
+    fn blob_submit<'a, 'b, 'fut>(
+        &'a self,
+        blobs: &'b [Blob],
+        opts: TxConfig,
+    ) -> impl Future<Output = Result<u64, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        submit_with_bump(self, blobs, opts)
+    }
+
+    /// Compute the PayForBlobs message for the blobs without broadcasting it.
+    ///
+    /// Blobs are validated against the app version of the current network head.
+    /// The signer is taken from [`TxConfig::signer_address`] or defaults to the
+    /// node's account. If [`TxConfig::gas`] isn't set, the gas is estimated
+    /// with [`blob::estimate_gas`].
+    fn blob_submit_dry_run<'a, 'b, 'fut>(
+        &'a self,
+        blobs: &'b [Blob],
+        opts: TxConfig,
+    ) -> impl Future<Output = Result<PfbPreview, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let head = self.header_network_head().await?;
+            let app_version = head.header.version.app;
+            let app_version = AppVersion::from_u64(app_version).ok_or_else(|| {
+                let e = format!("Invalid or unsupported AppVersion: {app_version}");
+                Error::Custom(e)
+            })?;
+
+            for blob in blobs {
+                blob.validate(app_version)
+                    .map_err(|e| Error::Custom(e.to_string()))?;
+            }
+
+            let signer = match opts.signer_address {
+                Some(addr) => addr.into(),
+                None => self.state_account_address().await?,
+            };
+            let msg =
+                MsgPayForBlobs::new(blobs, signer).map_err(|e| Error::Custom(e.to_string()))?;
+            let gas = opts
+                .gas
+                .filter(|gas| *gas > 0)
+                .unwrap_or_else(|| blob::estimate_gas(blobs, app_version));
+
+            Ok(PfbPreview { msg, gas })
+        }
+    }
+
+    /// Submit the blobs and verify that they were included in the chain.
+    ///
+    /// After the submission, this waits for the header at the height in which
+    /// blobs were included and verifies the inclusion proof of each blob
+    /// against the [`DataAvailabilityHeader`] of that header.
+    ///
+    /// Returns the height in which blobs were included.
+    ///
+    /// # Errors
+    ///
+    /// Besides the errors from the submission itself, this function returns
+    /// an error if blob's commitment doesn't match its data or if it's inclusion
+    /// couldn't be proven.
+    ///
+    /// [`DataAvailabilityHeader`]: celestia_types::DataAvailabilityHeader
+    fn blob_submit_and_verify<'a, 'b, 'fut>(
+        &'a self,
+        blobs: &'b [Blob],
+        opts: TxConfig,
+    ) -> impl Future<Output = Result<u64, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let height = submit_with_bump(self, blobs, opts).await?;
+            confirm_inclusion(self, height, blobs).await?;
+            Ok(height)
+        }
+    }
+
+    /// Submit the blobs and track the state of the submission.
+    ///
+    /// The returned stream reports [`SubmitState::Broadcast`] once the transaction is
+    /// broadcast successfully, then [`SubmitState::Committed`] with the height of the
+    /// block including it, and [`SubmitState::Confirmed`] after the inclusion of blobs
+    /// is verified the same way as in [`blob_submit_and_verify`]. Any error ends the
+    /// stream with [`SubmitState::Failed`].
+    ///
+    /// Celestia nodes don't report the mempool status of a transaction, as the
+    /// submission returns only after the transaction is committed. Because of that,
+    /// [`SubmitState::Broadcast`] is immediately followed by [`SubmitState::Committed`].
+    ///
+    /// [`blob_submit_and_verify`]: BlobClient::blob_submit_and_verify
+    fn blob_submit_tracked<'a, 'b, 'fut>(
+        &'a self,
+        blobs: &'b [Blob],
+        opts: TxConfig,
+    ) -> impl Stream<Item = SubmitState> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        let step = Some(SubmitStep::Submit(opts));
+
+        stream::unfold(step, move |step| async move {
+            let (state, next) = match step? {
+                SubmitStep::Submit(opts) => match submit_with_bump(self, blobs, opts).await {
+                    Ok(height) => (SubmitState::Broadcast, Some(SubmitStep::Committed(height))),
+                    Err(e) => (SubmitState::failed(e), None),
+                },
+                SubmitStep::Committed(height) => (
+                    SubmitState::Committed { height },
+                    Some(SubmitStep::Confirm(height)),
+                ),
+                SubmitStep::Confirm(height) => match confirm_inclusion(self, height, blobs).await {
+                    Ok(()) => (SubmitState::Confirmed { height }, None),
+                    Err(e) => (SubmitState::failed(e), None),
+                },
+            };
+
+            Some((state, next))
+        })
+    }
+
+    /// Submit the blobs and wait until the header at the height of their inclusion
+    /// is delivered by [`header_subscribe`].
+    ///
+    /// The subscription is created before the submission, so that the header cannot
+    /// be missed. This requires the client to support subscriptions, e.g. using 'ws'
+    /// protocol.
+    ///
+    /// Returns the height in which blobs were included.
+    ///
+    /// # Errors
+    ///
+    /// Besides the errors from the submission itself, this function returns
+    /// [`Error::RequestTimeout`] if the header doesn't arrive within the `timeout`.
+    ///
+    /// [`header_subscribe`]: HeaderClient::header_subscribe
+    #[cfg(not(target_arch = "wasm32"))]
+    fn blob_submit_awaited<'a, 'b, 'fut>(
+        &'a self,
+        blobs: &'b [Blob],
+        opts: TxConfig,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<u64, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let mut subscription = self.header_subscribe().await?;
+            let height = submit_with_bump(self, blobs, opts).await?;
+
+            let wait_for_header = async move {
+                while let Some(header) = subscription.next().await {
+                    if header?.height().value() >= height {
+                        return Ok(());
+                    }
+                }
+
+                Err(Error::Custom("Header subscription closed".into()))
+            };
+
+            match future::select(Box::pin(wait_for_header), Delay::new(timeout)).await {
+                Either::Left((res, _)) => res?,
+                Either::Right(_) => return Err(Error::RequestTimeout),
+            }
+
+            Ok(height)
+        }
+    }
+
+    /// Submit the blobs, unless the previous submission of them already succeeded.
+    ///
+    /// Every submission is recorded in the `log`. If all the blobs were already
+    /// submitted before, e.g. when retrying after a timeout, this first checks whether
+    /// they were included in the chain and returns the height of inclusion without
+    /// submitting them again. If the outcome of the previous submission is unknown,
+    /// up to [`MAX_IDEMPOTENT_SEARCH_HEIGHTS`] heights following the submission are
+    /// searched.
+    ///
+    /// Returns the height in which blobs were included.
+    fn blob_submit_idempotent<'a, 'b, 'c, 'fut, L>(
+        &'a self,
+        blobs: &'b [Blob],
+        opts: TxConfig,
+        log: &'c mut L,
+    ) -> impl Future<Output = Result<u64, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        'c: 'fut,
+        L: SubmitLog + Send + 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            if let Some(height) = find_previous_submission(self, blobs, log).await? {
+                return Ok(height);
+            }
+
+            let attempted_at = self.header_network_head().await?.height().value();
+
+            for blob in blobs {
+                let record = SubmitRecord {
+                    attempted_at,
+                    included_at: None,
+                };
+                log.insert(blob.commitment, record);
+            }
+
+            let height = submit_with_bump(self, blobs, opts).await?;
+
+            for blob in blobs {
+                let record = SubmitRecord {
+                    attempted_at,
+                    included_at: Some(height),
+                };
+                log.insert(blob.commitment, record);
+            }
+
+            Ok(height)
+        }
+    }
 
     /// Subscribe to published blobs from the given namespace as they are included.
     ///
     /// # Notes
     ///
     /// Unsubscribe is not implemented by Celestia nodes.
-    #[subscription(name = "blob.Subscribe", unsubscribe = "blob.Unsubscribe", item = BlobsAtHeight)]
-    async fn blob_subscribe(&self, namespace: Namespace) -> SubcriptionResult;
+    fn blob_subscribe<'a, 'fut>(
+        &'a self,
+        namespace: Namespace,
+    ) -> impl Future<Output = Result<Subscription<BlobsAtHeight>, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::BlobClient::blob_subscribe(self, namespace)
+    }
+
+    /// Get the blobs of the namespaces at every height, starting from `height`.
+    ///
+    /// Heights up to the current head of the node are fetched with [`blob_get_all`],
+    /// after which the stream switches to [`blob_subscribe`]. Subscriptions are
+    /// created before the backfill, and any heights missed in between are fetched
+    /// directly, so every height is yielded exactly once and in order, even if the
+    /// head advances during the backfill. Heights without matching blobs are
+    /// yielded with an empty list.
+    ///
+    /// The stream ends after the first error. This requires the client to support
+    /// subscriptions, e.g. using 'ws' protocol.
+    ///
+    /// [`blob_get_all`]: BlobClient::blob_get_all
+    /// [`blob_subscribe`]: BlobClient::blob_subscribe
+    fn blob_stream_from<'a, 'b, 'fut>(
+        &'a self,
+        height: u64,
+        namespaces: &'b [Namespace],
+    ) -> impl Stream<Item = Result<(u64, Vec<Blob>), Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        let mut namespaces = namespaces.to_vec();
+        namespaces.sort();
+        namespaces.dedup();
+
+        let state = ReplayState {
+            namespaces,
+            next_height: height.max(1),
+            backfill_until: 0,
+            live: None,
+            pending: BTreeMap::new(),
+        };
+
+        stream::unfold(Some(state), move |state| async move {
+            let mut state = state?;
+
+            match replay_next(self, &mut state).await {
+                Ok(item) => Some((Ok(item), Some(state))),
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
+    /// Subscribe to published blobs from the given namespace, matching the [`BlobFilter`].
+    ///
+    /// Filtering is done client side, after the blobs are received from the node.
+    /// Heights are still reported if none of their blobs match.
+    ///
+    /// # Notes
+    ///
+    /// Unsubscribe is not implemented by Celestia nodes.
+    fn blob_subscribe_filtered<'a, 'fut>(
+        &'a self,
+        namespace: Namespace,
+        filter: BlobFilter,
+    ) -> impl Future<Output = Result<BoxStream<'static, Result<BlobsAtHeight, Error>>, Error>>
+           + Send
+           + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let subscription = rpc::BlobClient::blob_subscribe(self, namespace).await?;
+
+            let filtered = subscription.map(move |item| {
+                let mut item = item?;
+                item.blobs = filter.apply(item.blobs.take());
+                Ok(item)
+            });
+
+            Ok(filtered.boxed())
+        }
+    }
+}
+
+impl<T> BlobClient for T where T: SubscriptionClientT {}
+
+/// Returns `true` if the error was returned because the transaction's
+/// gas price was lower than the minimum accepted one.
+pub fn is_insufficient_gas_price(error: &Error) -> bool {
+    match error {
+        Error::Call(error) => INSUFFICIENT_GAS_PRICE
+            .iter()
+            .any(|msg| error.message().contains(msg)),
+        _ => false,
+    }
+}
+
+/// Verify that the blob's shares starting at `index` of the original data square are
+/// included in the block with given header, using the proofs from [`BlobClient::blob_get_proof`].
+///
+/// The blob itself is not validated, see [`Blob::validate`].
+pub fn verify_blob_proofs(
+    header: &ExtendedHeader,
+    blob: &Blob,
+    index: u64,
+    proofs: &[NamespaceProof],
+) -> Result<(), Error> {
+    let mut row = index / u64::from(header.dah.square_width() / 2);
+
+    let shares = blob.to_shares().map_err(|e| Error::Custom(e.to_string()))?;
+    let mut shares = &shares[..];
+
+    for proof in proofs {
+        let leaves_len = (proof.end_idx() - proof.start_idx()) as usize;
+
+        if leaves_len > shares.len() {
+            return Err(Error::Custom(
+                "Blob proof covers more shares than blob has".into(),
+            ));
+        }
+
+        let (leaves, rest) = shares.split_at(leaves_len);
+        let root = u16::try_from(row)
+            .ok()
+            .and_then(|row| header.dah.row_root(row))
+            .ok_or_else(|| Error::Custom(format!("Missing row root for row {row}")))?;
+
+        proof
+            .verify_range(&root, leaves, *blob.namespace)
+            .map_err(|e| Error::Custom(format!("Blob inclusion verification failed: {e:?}")))?;
+
+        shares = rest;
+        row += 1;
+    }
+
+    if !shares.is_empty() {
+        return Err(Error::Custom(
+            "Blob proofs don't cover all of the blob's shares".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Submit the blobs, bumping the gas price on rejections if [`TxConfig::auto_bump`] is set.
+async fn submit_with_bump<C>(client: &C, blobs: &[Blob], opts: TxConfig) -> Result<u64, Error>
+where
+    C: SubscriptionClientT + Sync,
+{
+    let Some(bump) = opts.auto_bump else {
+        return rpc::BlobClient::blob_submit(client, blobs, opts).await;
+    };
+
+    let mut price = opts
+        .gas_price
+        .filter(|price| *price >= 0.0)
+        .unwrap_or(DEFAULT_MIN_GAS_PRICE);
+    let mut config = opts;
+    let mut attempt = 1;
+
+    loop {
+        match rpc::BlobClient::blob_submit(client, blobs, config.clone()).await {
+            Err(e) if is_insufficient_gas_price(&e) && attempt < bump.max_attempts => {
+                let bumped = (price * bump.factor).min(bump.price_cap);
+
+                if bumped <= price {
+                    // Price cap reached
+                    return Err(e);
+                }
+
+                price = bumped;
+                config.gas_price = Some(price);
+                attempt += 1;
+            }
+            res => return res,
+        }
+    }
+}
+
+/// Find the height in which the blobs were included by the previous submission.
+async fn find_previous_submission<C, L>(
+    client: &C,
+    blobs: &[Blob],
+    log: &mut L,
+) -> Result<Option<u64>, Error>
+where
+    C: SubscriptionClientT + Sync,
+    L: SubmitLog,
+{
+    let Some(records) = blobs
+        .iter()
+        .map(|blob| log.get(&blob.commitment))
+        .collect::<Option<Vec<_>>>()
+    else {
+        // Not all of the blobs were submitted before
+        return Ok(None);
+    };
+
+    let Some(attempted_at) = records.iter().map(|record| record.attempted_at).min() else {
+        return Ok(None);
+    };
+
+    // Blobs are submitted atomically, so they are all included in the same height.
+    if let Some(height) = records.iter().find_map(|record| record.included_at) {
+        let included = blobs_included(client, height, blobs).await?;
+        return Ok(included.then_some(height));
+    }
+
+    let head = client.header_network_head().await?.height().value();
+    let last = head.min(attempted_at.saturating_add(MAX_IDEMPOTENT_SEARCH_HEIGHTS));
+
+    for height in attempted_at + 1..=last {
+        if blobs_included(client, height, blobs).await? {
+            for blob in blobs {
+                let record = SubmitRecord {
+                    attempted_at,
+                    included_at: Some(height),
+                };
+                log.insert(blob.commitment, record);
+            }
+
+            return Ok(Some(height));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Check whether all the blobs were included in the given height.
+async fn blobs_included<C>(client: &C, height: u64, blobs: &[Blob]) -> Result<bool, Error>
+where
+    C: SubscriptionClientT + Sync,
+{
+    let mut namespaces: Vec<_> = blobs.iter().map(|blob| blob.namespace).collect();
+    namespaces.sort();
+    namespaces.dedup();
+
+    let Some(included) = rpc::BlobClient::blob_get_all(client, height, &namespaces).await? else {
+        return Ok(false);
+    };
+
+    Ok(blobs.iter().all(|blob| {
+        included
+            .iter()
+            .any(|inc| inc.namespace == blob.namespace && inc.commitment == blob.commitment)
+    }))
+}
+
+/// Get the next height of the [`BlobClient::blob_stream_from`] stream.
+async fn replay_next<C>(client: &C, state: &mut ReplayState) -> Result<(u64, Vec<Blob>), Error>
+where
+    C: SubscriptionClientT + Sync,
+{
+    if state.live.is_none() {
+        if state.namespaces.is_empty() {
+            return Err(Error::Custom("No namespaces to stream".into()));
+        }
+
+        let mut subscriptions = Vec::with_capacity(state.namespaces.len());
+
+        for namespace in &state.namespaces {
+            subscriptions.push(client.blob_subscribe(*namespace).await?.boxed());
+        }
+
+        state.live = Some(stream::select_all(subscriptions).boxed());
+        state.backfill_until = client.header_local_head().await?.height().value();
+    }
+
+    loop {
+        let height = state.next_height;
+
+        while let Some(entry) = state.pending.first_entry() {
+            if *entry.key() >= height {
+                break;
+            }
+            entry.remove();
+        }
+
+        let complete = state
+            .pending
+            .get(&height)
+            .is_some_and(|(reported, _)| *reported == state.namespaces.len());
+
+        let blobs = if complete {
+            let (_, mut blobs) = state.pending.remove(&height).expect("entry is present");
+            blobs.sort_by_key(|blob| blob.namespace);
+            Some(blobs)
+        } else if height <= state.backfill_until {
+            let blobs = client.blob_get_all(height, &state.namespaces).await?;
+            Some(blobs.unwrap_or_default())
+        } else {
+            None
+        };
+
+        if let Some(blobs) = blobs {
+            state.next_height += 1;
+            return Ok((height, blobs));
+        }
+
+        let live = state.live.as_mut().expect("subscriptions are created");
+        let item = live
+            .next()
+            .await
+            .ok_or_else(|| Error::Custom("Blob subscription closed".into()))??;
+
+        // all the heights before the received one are already available
+        state.backfill_until = state.backfill_until.max(item.height.saturating_sub(1));
+
+        if item.height >= state.next_height {
+            let (reported, blobs) = state.pending.entry(item.height).or_default();
+            *reported += 1;
+            blobs.extend(item.blobs.unwrap_or_default());
+        }
+    }
+}
+
+/// Wait for the header at the given height and verify the inclusion of the blobs in it.
+async fn confirm_inclusion<C>(client: &C, height: u64, blobs: &[Blob]) -> Result<(), Error>
+where
+    C: SubscriptionClientT + Sync,
+{
+    let header = client.header_wait_for_height(height).await?;
+
+    for blob in blobs {
+        verify_blob_inclusion(client, &header, blob).await?;
+    }
+
+    Ok(())
+}
+
+async fn verify_blob_inclusion<C>(
+    client: &C,
+    header: &ExtendedHeader,
+    blob: &Blob,
+) -> Result<(), Error>
+where
+    C: SubscriptionClientT + Sync,
+{
+    let app_version = header
+        .app_version()
+        .map_err(|e| Error::Custom(e.to_string()))?;
+    blob.validate(app_version)
+        .map_err(|e| Error::Custom(e.to_string()))?;
+
+    let height = header.height().value();
+    let included =
+        rpc::BlobClient::blob_get(client, height, blob.namespace, blob.commitment).await?;
+    let proofs =
+        rpc::BlobClient::blob_get_proof(client, height, blob.namespace, blob.commitment).await?;
+
+    let index = included
+        .index
+        .ok_or_else(|| Error::Custom("Missing index of the included blob".into()))?;
+
+    verify_blob_proofs(header, blob, index, &proofs)
 }
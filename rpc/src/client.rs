@@ -3,29 +3,199 @@
 //! This module aims to provide a convenient way to create a Json-RPC clients. If
 //! you need more configuration options and / or some custom client you can create
 //! one using [`jsonrpsee`] crate directly.
+//!
+//! The native clients use rustls for 'https' and 'wss' connections, which is
+//! the only TLS backend of [`jsonrpsee`]. Certificates are verified using the
+//! operating system, unless the `webpki-roots` feature is enabled, in which case
+//! the bundled Mozilla root certificates are trusted instead.
+
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "rustls-tls")))]
+compile_error!("The `rustls-tls` feature must be enabled, it's the only supported TLS backend");
 
+pub use self::limit::ConcurrencyLimit;
 #[cfg(not(target_arch = "wasm32"))]
-pub use self::native::Client;
+pub use self::native::{Client, AUTH_TOKEN_ENV, URL_ENV};
 
 #[cfg(all(target_arch = "wasm32", feature = "wasm-bindgen"))]
 pub use self::wasm::Client;
 
+mod limit {
+    use std::fmt;
+    use std::result::Result;
+
+    use async_trait::async_trait;
+    use jsonrpsee::core::client::{BatchResponse, ClientT, Subscription, SubscriptionClientT};
+    use jsonrpsee::core::params::BatchRequestBuilder;
+    use jsonrpsee::core::traits::ToRpcParams;
+    use jsonrpsee::core::ClientError;
+    use serde::de::DeserializeOwned;
+    use tokio::sync::{Semaphore, SemaphorePermit};
+
+    /// Json RPC client wrapper limiting the number of concurrent requests.
+    ///
+    /// Requests above the limit wait until one of the in-flight requests completes.
+    /// Subscriptions count towards the limit only while they are being created.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use celestia_rpc::client::ConcurrencyLimit;
+    /// use celestia_rpc::{Client, HeaderClient};
+    ///
+    /// let client = Client::new("ws://localhost:26658", None).await?;
+    /// let client = ConcurrencyLimit::new(client, 16);
+    ///
+    /// let head = client.header_network_head().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct ConcurrencyLimit<C> {
+        client: C,
+        semaphore: Semaphore,
+        max_concurrent_requests: usize,
+    }
+
+    impl<C> ConcurrencyLimit<C> {
+        /// Wrap the client, allowing at most `max_concurrent_requests` simultaneous requests.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `max_concurrent_requests` is `0` or exceeds [`Semaphore::MAX_PERMITS`].
+        pub fn new(client: C, max_concurrent_requests: usize) -> Self {
+            assert!(
+                max_concurrent_requests > 0,
+                "max_concurrent_requests must be > 0"
+            );
+
+            ConcurrencyLimit {
+                client,
+                semaphore: Semaphore::new(max_concurrent_requests),
+                max_concurrent_requests,
+            }
+        }
+
+        /// Maximum number of concurrent requests.
+        pub fn max_concurrent_requests(&self) -> usize {
+            self.max_concurrent_requests
+        }
+
+        /// Number of requests currently in-flight.
+        pub fn in_flight(&self) -> usize {
+            self.max_concurrent_requests - self.semaphore.available_permits()
+        }
+
+        /// Get the reference to the wrapped client.
+        pub fn inner(&self) -> &C {
+            &self.client
+        }
+
+        /// Unwrap the client.
+        pub fn into_inner(self) -> C {
+            self.client
+        }
+
+        async fn acquire(&self) -> SemaphorePermit<'_> {
+            // semaphore is never closed
+            self.semaphore
+                .acquire()
+                .await
+                .expect("semaphore closed unexpectedly")
+        }
+    }
+
+    #[async_trait]
+    impl<C> ClientT for ConcurrencyLimit<C>
+    where
+        C: ClientT + Send + Sync,
+    {
+        async fn notification<Params>(
+            &self,
+            method: &str,
+            params: Params,
+        ) -> Result<(), ClientError>
+        where
+            Params: ToRpcParams + Send,
+        {
+            let _permit = self.acquire().await;
+            self.client.notification(method, params).await
+        }
+
+        async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, ClientError>
+        where
+            R: DeserializeOwned,
+            Params: ToRpcParams + Send,
+        {
+            let _permit = self.acquire().await;
+            self.client.request(method, params).await
+        }
+
+        async fn batch_request<'a, R>(
+            &self,
+            batch: BatchRequestBuilder<'a>,
+        ) -> Result<BatchResponse<'a, R>, ClientError>
+        where
+            R: DeserializeOwned + fmt::Debug + 'a,
+        {
+            let _permit = self.acquire().await;
+            self.client.batch_request(batch).await
+        }
+    }
+
+    #[async_trait]
+    impl<C> SubscriptionClientT for ConcurrencyLimit<C>
+    where
+        C: SubscriptionClientT + Send + Sync,
+    {
+        async fn subscribe<'a, N, Params>(
+            &self,
+            subscribe_method: &'a str,
+            params: Params,
+            unsubscribe_method: &'a str,
+        ) -> Result<Subscription<N>, ClientError>
+        where
+            Params: ToRpcParams + Send,
+            N: DeserializeOwned,
+        {
+            let _permit = self.acquire().await;
+            self.client
+                .subscribe(subscribe_method, params, unsubscribe_method)
+                .await
+        }
+
+        async fn subscribe_to_method<'a, N>(
+            &self,
+            method: &'a str,
+        ) -> Result<Subscription<N>, ClientError>
+        where
+            N: DeserializeOwned,
+        {
+            let _permit = self.acquire().await;
+            self.client.subscribe_to_method(method).await
+        }
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 mod native {
+    use std::env::{self, VarError};
     use std::fmt;
     use std::result::Result;
+    #[cfg(feature = "webpki-roots")]
+    use std::sync::Arc;
 
     use async_trait::async_trait;
     use celestia_types::consts::appconsts::{self, SHARE_SIZE};
-    use http::{header, HeaderValue};
     use jsonrpsee::core::client::{BatchResponse, ClientT, Subscription, SubscriptionClientT};
     use jsonrpsee::core::params::BatchRequestBuilder;
     use jsonrpsee::core::traits::ToRpcParams;
     use jsonrpsee::core::ClientError;
-    use jsonrpsee::http_client::{HeaderMap, HttpClient, HttpClientBuilder};
+    use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
     use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
     use serde::de::DeserializeOwned;
 
+    use crate::auth::bearer_headers;
     use crate::Error;
 
     // NOTE: Always the largest `appconsts::*::SQUARE_SIZE_UPPER_BOUND` needs to be used.
@@ -38,6 +208,12 @@ mod native {
     // Also, we allow 1 MB extra for any metadata they come with it.
     const MAX_RESPONSE_SIZE: usize = MAX_EDS_SIZE_BYTES + 1024 * 1024;
 
+    /// Environment variable holding the connection string used by [`Client::from_env`].
+    pub const URL_ENV: &str = "CELESTIA_NODE_URL";
+
+    /// Environment variable holding the auth token used by [`Client::from_env`].
+    pub const AUTH_TOKEN_ENV: &str = "CELESTIA_NODE_AUTH_TOKEN";
+
     /// Json RPC client.
     pub enum Client {
         /// A client using 'http\[s\]' protocol.
@@ -56,33 +232,84 @@ mod native {
         /// Please note that currently the celestia-node supports only 'http' and 'ws'.
         /// For a secure connection you have to hide it behind a proxy.
         pub async fn new(conn_str: &str, auth_token: Option<&str>) -> Result<Self, Error> {
-            let mut headers = HeaderMap::new();
-
-            if let Some(token) = auth_token {
-                let val = HeaderValue::from_str(&format!("Bearer {token}"))?;
-                headers.insert(header::AUTHORIZATION, val);
-            }
+            let headers = bearer_headers(auth_token)?;
 
             let protocol = conn_str.split_once(':').map(|(proto, _)| proto);
             let client = match protocol {
-                Some("http") | Some("https") => Client::Http(
-                    HttpClientBuilder::default()
+                Some("http") | Some("https") => {
+                    let builder = HttpClientBuilder::default()
                         .max_response_size(MAX_RESPONSE_SIZE as u32)
-                        .set_headers(headers)
-                        .build(conn_str)?,
-                ),
-                Some("ws") | Some("wss") => Client::Ws(
-                    WsClientBuilder::default()
+                        .set_headers(headers);
+                    #[cfg(feature = "webpki-roots")]
+                    let builder = builder.with_custom_cert_store(rustls_config()?);
+
+                    Client::Http(builder.build(conn_str)?)
+                }
+                Some("ws") | Some("wss") => {
+                    let builder = WsClientBuilder::default()
                         .max_response_size(MAX_RESPONSE_SIZE as u32)
-                        .set_headers(headers)
-                        .build(conn_str)
-                        .await?,
-                ),
+                        .set_headers(headers);
+                    #[cfg(feature = "webpki-roots")]
+                    let builder = builder.with_custom_cert_store(rustls_config()?);
+
+                    Client::Ws(builder.build(conn_str).await?)
+                }
                 _ => return Err(Error::ProtocolNotSupported(conn_str.into())),
             };
 
             Ok(client)
         }
+
+        /// Create a new Json RPC client configured from the environment.
+        ///
+        /// The connection string is read from the [`CELESTIA_NODE_URL`] variable,
+        /// which is required. The auth token is read from the optional
+        /// [`CELESTIA_NODE_AUTH_TOKEN`] variable. See [`Client::new`] for
+        /// the supported protocols.
+        ///
+        /// [`CELESTIA_NODE_URL`]: URL_ENV
+        /// [`CELESTIA_NODE_AUTH_TOKEN`]: AUTH_TOKEN_ENV
+        pub async fn from_env() -> Result<Self, Error> {
+            Client::from_env_with(env::var).await
+        }
+
+        /// Create a new Json RPC client configured from the variables returned by `var`.
+        ///
+        /// Works like [`Client::from_env`], but the variables are looked up with the
+        /// provided function instead of the process environment, e.g. to read them from
+        /// a config file or to avoid modifying the environment in tests.
+        pub async fn from_env_with<F>(var: F) -> Result<Self, Error>
+        where
+            F: Fn(&'static str) -> Result<String, VarError>,
+        {
+            let conn_str = var(URL_ENV).map_err(|e| Error::EnvVar(URL_ENV, e))?;
+            let auth_token = match var(AUTH_TOKEN_ENV) {
+                Ok(token) => Some(token),
+                Err(VarError::NotPresent) => None,
+                Err(e) => return Err(Error::EnvVar(AUTH_TOKEN_ENV, e)),
+            };
+
+            Client::new(&conn_str, auth_token.as_deref()).await
+        }
+    }
+
+    /// Rustls configuration trusting the bundled Mozilla root certificates.
+    ///
+    /// Without it, jsonrpsee verifies certificates using the operating system.
+    #[cfg(feature = "webpki-roots")]
+    fn rustls_config() -> Result<rustls::ClientConfig, Error> {
+        let roots = rustls::RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+        let config = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|e| ClientError::Transport(e.into()))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        Ok(config)
     }
 
     #[async_trait]
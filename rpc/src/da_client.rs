@@ -0,0 +1,72 @@
+//! Object safe facade over the most commonly used RPC methods
+
+use async_trait::async_trait;
+use celestia_types::nmt::Namespace;
+use celestia_types::row_namespace_data::NamespaceData;
+use celestia_types::{Blob, Commitment, ExtendedHeader};
+use jsonrpsee::core::client::{Error, SubscriptionClientT};
+
+use crate::{BlobClient, HeaderClient, ShareClient, TxConfig};
+
+/// Object safe client covering the common read and write methods.
+///
+/// Unlike [`BlobClient`], [`HeaderClient`] and [`ShareClient`], this trait can be used
+/// as a trait object, e.g. `Box<dyn DaClient>`, which allows abstracting over the
+/// transport or replacing the client with a mock in tests. It is implemented for
+/// every `jsonrpsee` client, including [`Client`].
+///
+/// [`Client`]: crate::Client
+#[async_trait]
+pub trait DaClient: Send + Sync {
+    /// Submit the blobs, see [`BlobClient::blob_submit`].
+    async fn blob_submit(&self, blobs: &[Blob], opts: TxConfig) -> Result<u64, Error>;
+
+    /// Get the blob by commitment, see [`BlobClient::blob_get`].
+    async fn blob_get(
+        &self,
+        height: u64,
+        namespace: Namespace,
+        commitment: Commitment,
+    ) -> Result<Blob, Error>;
+
+    /// Get the header at the given height, see [`HeaderClient::header_get_by_height`].
+    async fn header_get_by_height(&self, height: u64) -> crate::Result<ExtendedHeader>;
+
+    /// Get all shares within the namespace, see [`ShareClient::share_get_namespace_data`].
+    async fn share_get_namespace_data(
+        &self,
+        root: &ExtendedHeader,
+        namespace: Namespace,
+    ) -> crate::Result<NamespaceData>;
+}
+
+#[async_trait]
+impl<T> DaClient for T
+where
+    T: SubscriptionClientT + Send + Sync,
+{
+    async fn blob_submit(&self, blobs: &[Blob], opts: TxConfig) -> Result<u64, Error> {
+        BlobClient::blob_submit(self, blobs, opts).await
+    }
+
+    async fn blob_get(
+        &self,
+        height: u64,
+        namespace: Namespace,
+        commitment: Commitment,
+    ) -> Result<Blob, Error> {
+        BlobClient::blob_get(self, height, namespace, commitment).await
+    }
+
+    async fn header_get_by_height(&self, height: u64) -> crate::Result<ExtendedHeader> {
+        HeaderClient::header_get_by_height(self, height).await
+    }
+
+    async fn share_get_namespace_data(
+        &self,
+        root: &ExtendedHeader,
+        namespace: Namespace,
+    ) -> crate::Result<NamespaceData> {
+        ShareClient::share_get_namespace_data(self, root, namespace).await
+    }
+}
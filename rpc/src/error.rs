@@ -1,3 +1,12 @@
+use jsonrpsee::core::ClientError;
+
+/// Error message returned by celestia nodes when the header isn't in the store.
+pub(crate) const HEADER_NOT_FOUND: &str = "header: not found";
+
+/// Part of the error message returned by celestia nodes when the requested height is
+/// outside of their sampling window (`availability.ErrOutsideSamplingWindow`).
+pub(crate) const OUTSIDE_SAMPLING_WINDOW: &str = "outside of the sampling window";
+
 /// Alias for a `Result` with the error type [`celestia_rpc::Error`].
 ///
 /// [`celestia_rpc::Error`]: crate::Error
@@ -13,11 +22,39 @@ pub enum Error {
     #[error("Token contains invalid characters: {0}")]
     InvalidCharactersInToken(#[from] http::header::InvalidHeaderValue),
 
+    /// Environment variable is missing or isn't valid unicode.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("Environment variable {0}: {1}")]
+    EnvVar(&'static str, std::env::VarError),
+
     /// Protocol specified in connection string is not supported.
     #[error("Protocol not supported or missing: {0}")]
     ProtocolNotSupported(String),
 
+    /// Requested height is outside of the node's sampling window, so its data is not available.
+    ///
+    /// Returned by the [`ShareClient`] methods and [`HeaderClient::header_get_by_height`].
+    /// Such requests will keep failing, so there is no point in retrying them.
+    ///
+    /// [`ShareClient`]: crate::ShareClient
+    /// [`HeaderClient::header_get_by_height`]: crate::HeaderClient::header_get_by_height
+    #[error("Height {height} is outside of the sampling window starting at {window_tail}")]
+    OutsideSamplingWindow {
+        /// Requested height.
+        height: u64,
+        /// Lowest height still available in the node.
+        window_tail: u64,
+    },
+
     /// Error propagated from the [`jsonrpsee`].
     #[error(transparent)]
-    JsonRpc(#[from] jsonrpsee::core::ClientError),
+    JsonRpc(#[from] ClientError),
+}
+
+/// Returns `true` if the node responded with an error containing the given message.
+pub(crate) fn is_node_error(error: &ClientError, message: &str) -> bool {
+    match error {
+        ClientError::Call(error) => error.message().contains(message),
+        _ => false,
+    }
 }
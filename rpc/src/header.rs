@@ -1,50 +1,496 @@
+//! celestia-node rpc types and methods related to headers
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::marker::{Send, Sync};
+use std::slice;
+use std::time::Duration;
+
 use celestia_types::hash::Hash;
-use celestia_types::{ExtendedHeader, SyncState};
+use celestia_types::{ExtendedHeader, SyncState, TrustedHeader};
+use futures::stream::{self, Stream};
+use jsonrpsee::core::client::{ClientT, Error, Subscription, SubscriptionClientT};
+use jsonrpsee::core::params::ArrayParams;
 use jsonrpsee::proc_macros::rpc;
+use serde::de::IgnoredAny;
+
+use crate::error::{is_node_error, HEADER_NOT_FOUND};
+use crate::share::sampling_window_error;
+
+/// Maximum amount of headers that [`HeaderClient::header_subscribe_gapless`] is allowed
+/// to backfill after the subscription was re-established.
+pub const MAX_GAPLESS_BACKFILL: u64 = 512;
+
+/// Maximum distance between the requested and trusted heights that
+/// [`HeaderClient::header_get_by_height_verified`] is allowed to verify.
+pub const MAX_VERIFIED_DISTANCE: u64 = 512;
+
+mod rpc {
+    use super::*;
+
+    #[rpc(client)]
+    pub trait Header {
+        #[method(name = "header.GetByHash")]
+        async fn header_get_by_hash(&self, hash: Hash) -> Result<ExtendedHeader, Error>;
+
+        #[method(name = "header.GetByHeight")]
+        async fn header_get_by_height(&self, height: u64) -> Result<ExtendedHeader, Error>;
+
+        #[method(name = "header.GetRangeByHeight")]
+        async fn header_get_range_by_height(
+            &self,
+            from: &ExtendedHeader,
+            to: u64,
+        ) -> Result<Vec<ExtendedHeader>, Error>;
+
+        #[method(name = "header.LocalHead")]
+        async fn header_local_head(&self) -> Result<ExtendedHeader, Error>;
+
+        #[method(name = "header.NetworkHead")]
+        async fn header_network_head(&self) -> Result<ExtendedHeader, Error>;
 
-#[rpc(client)]
-pub trait Header {
+        #[subscription(name = "header.Subscribe", unsubscribe = "header.Unsubscribe", item = ExtendedHeader)]
+        async fn header_subscribe(&self) -> SubcriptionResult;
+
+        #[method(name = "header.SyncState")]
+        async fn header_sync_state(&self) -> Result<SyncState, Error>;
+
+        #[method(name = "header.SyncWait")]
+        async fn header_sync_wait(&self) -> Result<(), Error>;
+
+        #[method(name = "header.WaitForHeight")]
+        async fn header_wait_for_height(&self, height: u64) -> Result<ExtendedHeader, Error>;
+    }
+}
+
+/// Client implementation for the `Header` RPC API.
+pub trait HeaderClient: SubscriptionClientT {
     /// GetByHash returns the header of the given hash from the node's header store.
-    #[method(name = "header.GetByHash")]
-    async fn header_get_by_hash(&self, hash: Hash) -> Result<ExtendedHeader, Error>;
+    ///
+    /// Empty hash is rejected without querying the node. If the header isn't
+    /// in the node's store, the returned error satisfies [`is_header_not_found`].
+    fn header_get_by_hash<'a, 'fut>(
+        &'a self,
+        hash: Hash,
+    ) -> impl Future<Output = Result<ExtendedHeader, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            if !matches!(hash, Hash::Sha256(_)) {
+                return Err(Error::Custom("Header hash must be a SHA-256 hash".into()));
+            }
+
+            rpc::HeaderClient::header_get_by_hash(self, hash).await
+        }
+    }
 
     /// GetByHeight returns the ExtendedHeader at the given height if it is currently available.
-    #[method(name = "header.GetByHeight")]
-    async fn header_get_by_height(&self, height: u64) -> Result<ExtendedHeader, Error>;
+    ///
+    /// Returns [`crate::Error::OutsideSamplingWindow`] if the header was pruned or never synced.
+    fn header_get_by_height<'a, 'fut>(
+        &'a self,
+        height: u64,
+    ) -> impl Future<Output = crate::Result<ExtendedHeader>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            match rpc::HeaderClient::header_get_by_height(self, height).await {
+                Ok(header) => Ok(header),
+                Err(e) => Err(sampling_window_error(self, height, e).await),
+            }
+        }
+    }
+
+    /// Get the header at the given height and verify it against the trusted anchor.
+    ///
+    /// The trusted header is fetched and checked against the anchor's hash and the
+    /// `trusting_period`. Then the headers between the trusted and requested heights are
+    /// fetched, and the whole chain is verified with
+    /// [`ExtendedHeader::verify_adjacent_range`]. The requested height may be either
+    /// above or below the trusted one.
+    ///
+    /// Returns an error if the distance between the heights exceeds
+    /// [`MAX_VERIFIED_DISTANCE`], or if any of the headers fails verification.
+    fn header_get_by_height_verified<'a, 'b, 'fut>(
+        &'a self,
+        height: u64,
+        trusted: &'b TrustedHeader,
+        trusting_period: Duration,
+    ) -> impl Future<Output = Result<ExtendedHeader, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let distance = height.abs_diff(trusted.height);
+
+            if distance > MAX_VERIFIED_DISTANCE {
+                return Err(Error::Custom(format!(
+                    "Height {height} is {distance} headers away from the trusted height, \
+                     more than the limit of {MAX_VERIFIED_DISTANCE}"
+                )));
+            }
+
+            let anchor = rpc::HeaderClient::header_get_by_height(self, trusted.height).await?;
+            anchor
+                .verify_against_trusted(trusted, trusting_period)
+                .map_err(verification_error)?;
+
+            if height == trusted.height {
+                return Ok(anchor);
+            }
+
+            let header = rpc::HeaderClient::header_get_by_height(self, height).await?;
+            header.validate().map_err(verification_error)?;
+
+            let (from, to) = if height > trusted.height {
+                (anchor, header)
+            } else {
+                (header, anchor)
+            };
+
+            let mut headers = if distance > 1 {
+                rpc::HeaderClient::header_get_range_by_height(self, &from, to.height().value())
+                    .await?
+            } else {
+                Vec::new()
+            };
+
+            // `verify_adjacent_range` expects validated headers.
+            for header in &headers {
+                header.validate().map_err(verification_error)?;
+            }
+
+            headers.push(to);
+
+            from.verify_adjacent_range(&headers)
+                .map_err(verification_error)?;
+
+            if height > trusted.height {
+                Ok(headers.pop().expect("requested header is last"))
+            } else {
+                Ok(from)
+            }
+        }
+    }
 
     /// GetRangeByHeight returns the given range (from:to) of ExtendedHeaders from the node's header store and verifies that the returned headers are adjacent to each other.
-    #[method(name = "header.GetRangeByHeight")]
-    async fn header_get_range_by_height(
-        &self,
-        from: &ExtendedHeader,
+    fn header_get_range_by_height<'a, 'b, 'fut>(
+        &'a self,
+        from: &'b ExtendedHeader,
         to: u64,
-    ) -> Result<Vec<ExtendedHeader>, Error>;
+    ) -> impl Future<Output = Result<Vec<ExtendedHeader>, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::HeaderClient::header_get_range_by_height(self, from, to)
+    }
 
     /// LocalHead returns the ExtendedHeader of the chain head.
-    #[method(name = "header.LocalHead")]
-    async fn header_local_head(&self) -> Result<ExtendedHeader, Error>;
+    fn header_local_head<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<ExtendedHeader, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::HeaderClient::header_local_head(self)
+    }
 
     /// NetworkHead provides the Syncer's view of the current network head.
-    #[method(name = "header.NetworkHead")]
-    async fn header_network_head(&self) -> Result<ExtendedHeader, Error>;
+    ///
+    /// This is the objective head, i.e. the most recent header the node has seen
+    /// on the network, which wasn't necessarily synced yet. See
+    /// [`header_subjective_head`] for the trusted head.
+    ///
+    /// [`header_subjective_head`]: HeaderClient::header_subjective_head
+    fn header_network_head<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<ExtendedHeader, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::HeaderClient::header_network_head(self)
+    }
+
+    /// Returns the subjective head of the node.
+    ///
+    /// The subjective head is the most recent header that the node synced and
+    /// verified, and therefore trusts. It lags behind the objective
+    /// [`header_network_head`] while the node is catching up.
+    ///
+    /// Celestia nodes don't expose a dedicated endpoint for it, so it is derived
+    /// from the currently synced height reported by [`header_sync_state`].
+    ///
+    /// [`header_network_head`]: HeaderClient::header_network_head
+    /// [`header_sync_state`]: HeaderClient::header_sync_state
+    fn header_subjective_head<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<ExtendedHeader, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let state = rpc::HeaderClient::header_sync_state(self).await?;
+            rpc::HeaderClient::header_get_by_height(self, state.height).await
+        }
+    }
+
+    /// Get the range of heights of the headers available in the node's header store.
+    ///
+    /// Returns the inclusive `(from, to)` range, where `to` is the height of the
+    /// [`header_local_head`]. Headers below `from` are either pruned or were never
+    /// synced and requesting them with [`header_get_by_height`] returns
+    /// [`crate::Error::OutsideSamplingWindow`].
+    ///
+    /// Celestia nodes don't expose the tail of their header store, so `from` is found
+    /// with a binary search using [`header_get_by_height`]. Each of its `log2(to)` probes
+    /// transfers a whole header, however they are not decoded nor validated.
+    ///
+    /// [`header_local_head`]: HeaderClient::header_local_head
+    /// [`header_get_by_height`]: HeaderClient::header_get_by_height
+    fn header_available_range<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<(u64, u64), Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        available_range(self)
+    }
 
     /// Subscribe to recent ExtendedHeaders from the network.
     ///
     /// # Notes
     ///
     /// Unsubscribe is not implemented by Celestia nodes.
-    #[subscription(name = "header.Subscribe", unsubscribe = "header.Unsubscribe", item = ExtendedHeader)]
-    async fn header_subscribe(&self) -> SubcriptionResult;
+    fn header_subscribe<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<Subscription<ExtendedHeader>, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::HeaderClient::header_subscribe(self)
+    }
+
+    /// Subscribe to recent ExtendedHeaders from the network, without skipping any height.
+    ///
+    /// Celestia nodes close the subscription e.g. when the consumer is too slow. This
+    /// subscription keeps track of the last delivered header and, when it gets closed,
+    /// subscribes again and backfills the missed headers using
+    /// [`header_get_range_by_height`] before resuming with the live ones. This way the
+    /// consumer always receives a contiguous sequence of heights.
+    ///
+    /// If `last_delivered` is provided, the stream starts right after that header,
+    /// which allows resuming a previous subscription.
+    ///
+    /// Each header is validated and verified to be adjacent to the previously delivered
+    /// one before being yielded.
+    ///
+    /// The stream ends with an error if the subscription cannot be re-established,
+    /// if a header fails verification, or if more than [`MAX_GAPLESS_BACKFILL`] headers
+    /// would need to be backfilled.
+    ///
+    /// [`header_get_range_by_height`]: HeaderClient::header_get_range_by_height
+    fn header_subscribe_gapless<'a>(
+        &'a self,
+        last_delivered: Option<ExtendedHeader>,
+    ) -> impl Stream<Item = Result<ExtendedHeader, Error>> + Send + 'a
+    where
+        Self: Sized + Sync + 'a,
+    {
+        let subscription = GaplessSubscription {
+            client: self,
+            subscription: None,
+            last_delivered,
+            pending: VecDeque::new(),
+            finished: false,
+        };
+
+        stream::unfold(subscription, |mut subscription| async move {
+            let item = subscription.next().await?;
+            Some((item, subscription))
+        })
+    }
 
     /// SyncState returns the current state of the header Syncer.
-    #[method(name = "header.SyncState")]
-    async fn header_sync_state(&self) -> Result<SyncState, Error>;
+    fn header_sync_state<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<SyncState, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::HeaderClient::header_sync_state(self)
+    }
 
     /// SyncWait blocks until the header Syncer is synced to network head.
-    #[method(name = "header.SyncWait")]
-    async fn header_sync_wait(&self) -> Result<(), Error>;
+    fn header_sync_wait<'a, 'fut>(&'a self) -> impl Future<Output = Result<(), Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::HeaderClient::header_sync_wait(self)
+    }
 
     /// WaitForHeight blocks until the header at the given height has been processed by the store or context deadline is exceeded.
-    #[method(name = "header.WaitForHeight")]
-    async fn header_wait_for_height(&self, height: u64) -> Result<ExtendedHeader, Error>;
+    fn header_wait_for_height<'a, 'fut>(
+        &'a self,
+        height: u64,
+    ) -> impl Future<Output = Result<ExtendedHeader, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::HeaderClient::header_wait_for_height(self, height)
+    }
+}
+
+impl<T> HeaderClient for T where T: SubscriptionClientT {}
+
+/// Get the range of heights available in the node, see [`HeaderClient::header_available_range`].
+///
+/// Only requires [`ClientT`], so that it can be used by clients without subscriptions.
+pub(crate) async fn available_range<C>(client: &C) -> Result<(u64, u64), Error>
+where
+    C: ClientT + Sync,
+{
+    let head: ExtendedHeader = client
+        .request("header.LocalHead", ArrayParams::new())
+        .await?;
+    let to = head.height().value();
+
+    // `high` is always known to be available
+    let mut low = 1;
+    let mut high = to;
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+
+        let mut params = ArrayParams::new();
+        params.insert(mid)?;
+
+        // only the presence of the header matters, so skip decoding it
+        let probe: Result<IgnoredAny, _> = client.request("header.GetByHeight", params).await;
+
+        match probe {
+            Ok(_) => high = mid,
+            Err(e) if is_header_not_found(&e) => low = mid + 1,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok((high, to))
+}
+
+/// Returns `true` if the error was returned because the requested header
+/// isn't in the node's header store.
+pub fn is_header_not_found(error: &Error) -> bool {
+    is_node_error(error, HEADER_NOT_FOUND)
+}
+
+fn verification_error(e: celestia_types::Error) -> Error {
+    Error::Custom(format!("Header verification failed: {e}"))
+}
+
+/// State of the [`HeaderClient::header_subscribe_gapless`] stream.
+struct GaplessSubscription<'a, C> {
+    client: &'a C,
+    subscription: Option<Subscription<ExtendedHeader>>,
+    last_delivered: Option<ExtendedHeader>,
+    pending: VecDeque<ExtendedHeader>,
+    finished: bool,
+}
+
+impl<C> GaplessSubscription<'_, C>
+where
+    C: SubscriptionClientT + Sync,
+{
+    async fn next(&mut self) -> Option<Result<ExtendedHeader, Error>> {
+        if self.finished {
+            return None;
+        }
+
+        let res = self.next_header().await;
+
+        if res.is_err() {
+            self.finished = true;
+        }
+
+        Some(res)
+    }
+
+    async fn next_header(&mut self) -> Result<ExtendedHeader, Error> {
+        loop {
+            if let Some(header) = self.pending.pop_front() {
+                header.validate().map_err(verification_error)?;
+
+                // make sure the chain stays contiguous with what was already delivered
+                if let Some(last_delivered) = self.last_delivered.as_ref() {
+                    last_delivered
+                        .verify_adjacent_range(slice::from_ref(&header))
+                        .map_err(verification_error)?;
+                }
+
+                self.last_delivered = Some(header.clone());
+                return Ok(header);
+            }
+
+            let mut subscription = match self.subscription.take() {
+                Some(subscription) => subscription,
+                None => rpc::HeaderClient::header_subscribe(self.client).await?,
+            };
+
+            let Some(header) = subscription.next().await else {
+                // Subscription was closed by the node, re-subscribe.
+                continue;
+            };
+
+            self.subscription = Some(subscription);
+            let header = header?;
+
+            let Some(last_delivered) = self.last_delivered.as_ref() else {
+                self.pending.push_back(header);
+                continue;
+            };
+
+            let last_height = last_delivered.height().value();
+            let height = header.height().value();
+
+            // Already delivered, e.g. as part of the backfill.
+            if height <= last_height {
+                continue;
+            }
+
+            let missing = height - last_height - 1;
+
+            if missing > MAX_GAPLESS_BACKFILL {
+                return Err(Error::Custom(format!(
+                    "Missed {missing} headers, more than the backfill limit of {MAX_GAPLESS_BACKFILL}"
+                )));
+            }
+
+            if missing > 0 {
+                let headers = rpc::HeaderClient::header_get_range_by_height(
+                    self.client,
+                    last_delivered,
+                    height,
+                )
+                .await?;
+                self.pending.extend(headers);
+            }
+
+            self.pending.push_back(header);
+        }
+    }
 }
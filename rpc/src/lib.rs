@@ -1,14 +1,21 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
 
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg_attr(docsrs, doc(cfg(not(target_arch = "wasm32"))))]
+pub mod auth;
 pub mod blob;
 pub mod client;
+mod da_client;
 mod error;
-mod header;
+pub mod header;
 #[cfg(feature = "p2p")]
 mod p2p;
+pub mod reorg;
 pub mod share;
 mod state;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod subscription;
 mod tx_config;
 
 pub use crate::blob::BlobClient;
@@ -24,14 +31,19 @@ pub use crate::blob::BlobClient;
     )))
 )]
 pub use crate::client::Client;
+pub use crate::da_client::DaClient;
 pub use crate::error::{Error, Result};
 pub use crate::header::HeaderClient;
 #[cfg(feature = "p2p")]
 #[cfg_attr(docsrs, doc(cfg(feature = "p2p")))]
 pub use crate::p2p::P2PClient;
+pub use crate::reorg::{Reorg, ReorgDetector};
 pub use crate::share::ShareClient;
 pub use crate::state::StateClient;
-pub use crate::tx_config::TxConfig;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg_attr(docsrs, doc(cfg(not(target_arch = "wasm32"))))]
+pub use crate::subscription::SubscriptionManager;
+pub use crate::tx_config::{GasPriceBump, TxConfig, DEFAULT_MIN_GAS_PRICE};
 
 /// Re-exports of all the RPC traits.
 pub mod prelude {
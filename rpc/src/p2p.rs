@@ -1,80 +1,369 @@
+//! celestia-node rpc types and methods related to p2p
+
+use std::future::Future;
+use std::marker::{Send, Sync};
+
+use celestia_types::p2p::addr::{dialable_addrs, resolve_dnsaddrs};
 use celestia_types::p2p::{
-    AddrInfo, BandwidthStats, Connectedness, PeerId, Reachability, ResourceManagerStats,
+    AddrInfo, BandwidthStats, Connectedness, Multiaddr, PeerId, Reachability, ResourceManagerStats,
 };
+use jsonrpsee::core::client::{ClientT, Error};
 use jsonrpsee::proc_macros::rpc;
 
-#[rpc(client)]
-pub trait P2P {
+mod rpc {
+    use super::*;
+
+    #[rpc(client)]
+    pub trait P2P {
+        #[method(name = "p2p.BandwidthForPeer")]
+        async fn p2p_bandwidth_for_peer(&self, peer_id: &PeerId) -> Result<BandwidthStats, Error>;
+
+        #[method(name = "p2p.BandwidthForProtocol")]
+        async fn p2p_bandwidth_for_protocol(
+            &self,
+            protocol_id: &str,
+        ) -> Result<BandwidthStats, Error>;
+
+        #[method(name = "p2p.BandwidthStats")]
+        async fn p2p_bandwidth_stats(&self) -> Result<BandwidthStats, Error>;
+
+        #[method(name = "p2p.BlockPeer")]
+        async fn p2p_block_peer(&self, peer_id: &PeerId) -> Result<(), Error>;
+
+        #[method(name = "p2p.ClosePeer")]
+        async fn p2p_close_peer(&self, peer_id: &PeerId) -> Result<(), Error>;
+
+        #[method(name = "p2p.Connect")]
+        async fn p2p_connect(&self, address: &AddrInfo) -> Result<(), Error>;
+
+        #[method(name = "p2p.Connectedness")]
+        async fn p2p_connectedness(&self, peer_id: &PeerId) -> Result<Connectedness, Error>;
+
+        #[method(name = "p2p.Info")]
+        async fn p2p_info(&self) -> Result<AddrInfo, Error>;
+
+        #[method(name = "p2p.IsProtected")]
+        async fn p2p_is_protected(&self, peer_id: &PeerId, tag: &str) -> Result<bool, Error>;
+
+        #[method(name = "p2p.ListBlockedPeers")]
+        async fn p2p_list_blocked_peers(&self) -> Result<Vec<PeerId>, Error>;
+
+        #[method(name = "p2p.NATStatus")]
+        async fn p2p_nat_status(&self) -> Result<Reachability, Error>;
+
+        #[method(name = "p2p.PeerInfo")]
+        async fn p2p_peer_info(&self, peer_id: &PeerId) -> Result<AddrInfo, Error>;
+
+        #[method(name = "p2p.Peers")]
+        async fn p2p_peers(&self) -> Result<Vec<PeerId>, Error>;
+
+        #[method(name = "p2p.Protect")]
+        async fn p2p_protect(&self, peer_id: &PeerId, tag: &str) -> Result<(), Error>;
+
+        // We might get null in response here, so Option is needed
+        #[method(name = "p2p.PubSubPeers")]
+        async fn p2p_pub_sub_peers(&self, topic: &str) -> Result<Option<Vec<PeerId>>, Error>;
+
+        #[method(name = "p2p.ResourceState")]
+        async fn p2p_resource_state(&self) -> Result<ResourceManagerStats, Error>;
+
+        #[method(name = "p2p.UnblockPeer")]
+        async fn p2p_unblock_peer(&self, peer_id: &PeerId) -> Result<(), Error>;
+
+        #[method(name = "p2p.Unprotect")]
+        async fn p2p_unprotect(&self, peer_id: &PeerId, tag: &str) -> Result<bool, Error>;
+    }
+}
+
+/// Client implementation for the `P2P` RPC API.
+pub trait P2PClient: ClientT {
     /// BandwidthForPeer returns a Stats struct with bandwidth metrics associated with the given peer.ID. The metrics returned include all traffic sent / received for the peer, regardless of protocol.
-    #[method(name = "p2p.BandwidthForPeer")]
-    async fn p2p_bandwidth_for_peer(&self, peer_id: &PeerId) -> Result<BandwidthStats, Error>;
+    fn p2p_bandwidth_for_peer<'a, 'b, 'fut>(
+        &'a self,
+        peer_id: &'b PeerId,
+    ) -> impl Future<Output = Result<BandwidthStats, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_bandwidth_for_peer(self, peer_id)
+    }
 
     /// BandwidthForProtocol returns a Stats struct with bandwidth metrics associated with the given protocol.ID.
-    #[method(name = "p2p.BandwidthForProtocol")]
-    async fn p2p_bandwidth_for_protocol(&self, protocol_id: &str) -> Result<BandwidthStats, Error>;
+    fn p2p_bandwidth_for_protocol<'a, 'b, 'fut>(
+        &'a self,
+        protocol_id: &'b str,
+    ) -> impl Future<Output = Result<BandwidthStats, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_bandwidth_for_protocol(self, protocol_id)
+    }
 
     /// BandwidthStats returns a Stats struct with bandwidth metrics for all data sent/received by the local peer, regardless of protocol or remote peer IDs.
-    #[method(name = "p2p.BandwidthStats")]
-    async fn p2p_bandwidth_stats(&self) -> Result<BandwidthStats, Error>;
+    fn p2p_bandwidth_stats<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<BandwidthStats, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_bandwidth_stats(self)
+    }
 
     /// BlockPeer adds a peer to the set of blocked peers.
-    #[method(name = "p2p.BlockPeer")]
-    async fn p2p_block_peer(&self, peer_id: &PeerId) -> Result<(), Error>;
+    fn p2p_block_peer<'a, 'b, 'fut>(
+        &'a self,
+        peer_id: &'b PeerId,
+    ) -> impl Future<Output = Result<(), Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_block_peer(self, peer_id)
+    }
 
     /// ClosePeer closes the connection to a given peer.
-    #[method(name = "p2p.ClosePeer")]
-    async fn p2p_close_peer(&self, peer_id: &PeerId) -> Result<(), Error>;
+    fn p2p_close_peer<'a, 'b, 'fut>(
+        &'a self,
+        peer_id: &'b PeerId,
+    ) -> impl Future<Output = Result<(), Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_close_peer(self, peer_id)
+    }
 
     /// Connect ensures there is a connection between this host and the peer with given peer.
-    #[method(name = "p2p.Connect")]
-    async fn p2p_connect(&self, address: &AddrInfo) -> Result<(), Error>;
+    fn p2p_connect<'a, 'b, 'fut>(
+        &'a self,
+        address: &'b AddrInfo,
+    ) -> impl Future<Output = Result<(), Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_connect(self, address)
+    }
 
     /// Connectedness returns a state signaling connection capabilities.
-    #[method(name = "p2p.Connectedness")]
-    async fn p2p_connectedness(&self, peer_id: &PeerId) -> Result<Connectedness, Error>;
+    fn p2p_connectedness<'a, 'b, 'fut>(
+        &'a self,
+        peer_id: &'b PeerId,
+    ) -> impl Future<Output = Result<Connectedness, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_connectedness(self, peer_id)
+    }
 
     /// Info returns address information about the host.
-    #[method(name = "p2p.Info")]
-    async fn p2p_info(&self) -> Result<AddrInfo, Error>;
+    fn p2p_info<'a, 'fut>(&'a self) -> impl Future<Output = Result<AddrInfo, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_info(self)
+    }
+
+    /// Info returns address information about the host, with addresses ready to be dialed.
+    ///
+    /// `/dnsaddr` entries are resolved using the system's DNS configuration. Addresses
+    /// are deduplicated, suffixed with the host's peer id if they didn't have one, and
+    /// the ones pointing to a different peer are dropped.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn p2p_info_resolved<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<AddrInfo, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        self.p2p_info_resolved_with(query_dnsaddr)
+    }
+
+    /// Same as [`P2PClient::p2p_info_resolved`], but `/dnsaddr` entries are resolved
+    /// with the provided function.
+    ///
+    /// The function receives the host of the `/dnsaddr` entry and should return
+    /// multiaddrs from the TXT records of `_dnsaddr.<host>`. See
+    /// [`parse_dnsaddr_txt`](celestia_types::p2p::addr::parse_dnsaddr_txt).
+    fn p2p_info_resolved_with<'a, 'fut, F, Fut>(
+        &'a self,
+        resolve: F,
+    ) -> impl Future<Output = Result<AddrInfo, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        F: FnMut(String) -> Fut + Send + 'fut,
+        Fut: Future<Output = Result<Vec<Multiaddr>, Error>> + Send,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let info = self.p2p_info().await?;
+            let addrs = resolve_dnsaddrs(info.addrs, resolve).await?;
+
+            Ok(AddrInfo {
+                addrs: dialable_addrs(addrs, info.id.0),
+                id: info.id,
+            })
+        }
+    }
 
     /// IsProtected returns whether the given peer is protected.
-    #[method(name = "p2p.IsProtected")]
-    async fn p2p_is_protected(&self, peer_id: &PeerId, tag: &str) -> Result<bool, Error>;
+    fn p2p_is_protected<'a, 'b, 'c, 'fut>(
+        &'a self,
+        peer_id: &'b PeerId,
+        tag: &'c str,
+    ) -> impl Future<Output = Result<bool, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        'c: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_is_protected(self, peer_id, tag)
+    }
 
     /// ListBlockedPeers returns a list of blocked peers.
-    #[method(name = "p2p.ListBlockedPeers")]
-    async fn p2p_list_blocked_peers(&self) -> Result<Vec<PeerId>, Error>;
+    fn p2p_list_blocked_peers<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<Vec<PeerId>, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_list_blocked_peers(self)
+    }
 
     /// NATStatus returns the current NAT status.
-    #[method(name = "p2p.NATStatus")]
-    async fn p2p_nat_status(&self) -> Result<Reachability, Error>;
+    fn p2p_nat_status<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<Reachability, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_nat_status(self)
+    }
 
     /// PeerInfo returns a small slice of information Peerstore has on the given peer.
-    #[method(name = "p2p.PeerInfo")]
-    async fn p2p_peer_info(&self, peer_id: &PeerId) -> Result<AddrInfo, Error>;
+    fn p2p_peer_info<'a, 'b, 'fut>(
+        &'a self,
+        peer_id: &'b PeerId,
+    ) -> impl Future<Output = Result<AddrInfo, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_peer_info(self, peer_id)
+    }
 
     /// Peers returns connected peers.
-    #[method(name = "p2p.Peers")]
-    async fn p2p_peers(&self) -> Result<Vec<PeerId>, Error>;
+    fn p2p_peers<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<Vec<PeerId>, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_peers(self)
+    }
 
     /// Protect adds a peer to the list of peers who have a bidirectional peering agreement that they are protected from being trimmed, dropped or negatively scored.
-    #[method(name = "p2p.Protect")]
-    async fn p2p_protect(&self, peer_id: &PeerId, tag: &str) -> Result<(), Error>;
+    fn p2p_protect<'a, 'b, 'c, 'fut>(
+        &'a self,
+        peer_id: &'b PeerId,
+        tag: &'c str,
+    ) -> impl Future<Output = Result<(), Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        'c: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_protect(self, peer_id, tag)
+    }
 
-    // We might get null in response here, so Option is needed
     /// PubSubPeers returns the peer IDs of the peers joined on the given topic.
-    #[method(name = "p2p.PubSubPeers")]
-    async fn p2p_pub_sub_peers(&self, topic: &str) -> Result<Option<Vec<PeerId>>, Error>;
+    fn p2p_pub_sub_peers<'a, 'b, 'fut>(
+        &'a self,
+        topic: &'b str,
+    ) -> impl Future<Output = Result<Option<Vec<PeerId>>, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_pub_sub_peers(self, topic)
+    }
 
     /// ResourceState returns the state of the resource manager.
-    #[method(name = "p2p.ResourceState")]
-    async fn p2p_resource_state(&self) -> Result<ResourceManagerStats, Error>;
+    fn p2p_resource_state<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<ResourceManagerStats, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_resource_state(self)
+    }
 
     /// UnblockPeer removes a peer from the set of blocked peers.
-    #[method(name = "p2p.UnblockPeer")]
-    async fn p2p_unblock_peer(&self, peer_id: &PeerId) -> Result<(), Error>;
+    fn p2p_unblock_peer<'a, 'b, 'fut>(
+        &'a self,
+        peer_id: &'b PeerId,
+    ) -> impl Future<Output = Result<(), Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_unblock_peer(self, peer_id)
+    }
 
     /// Unprotect removes a peer from the list of peers who have a bidirectional peering agreement that they are protected from being trimmed, dropped or negatively scored, returning a bool representing whether the given peer is protected or not.
-    #[method(name = "p2p.Unprotect")]
-    async fn p2p_unprotect(&self, peer_id: &PeerId, tag: &str) -> Result<bool, Error>;
+    fn p2p_unprotect<'a, 'b, 'c, 'fut>(
+        &'a self,
+        peer_id: &'b PeerId,
+        tag: &'c str,
+    ) -> impl Future<Output = Result<bool, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        'c: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::P2PClient::p2p_unprotect(self, peer_id, tag)
+    }
+}
+
+impl<T> P2PClient for T where T: ClientT {}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn query_dnsaddr(host: String) -> Result<Vec<Multiaddr>, Error> {
+    use celestia_types::p2p::addr::parse_dnsaddr_txt;
+    use hickory_resolver::TokioAsyncResolver;
+
+    let resolver =
+        TokioAsyncResolver::tokio_from_system_conf().map_err(|e| Error::Custom(e.to_string()))?;
+    let lookup = resolver
+        .txt_lookup(format!("_dnsaddr.{host}"))
+        .await
+        .map_err(|e| Error::Custom(format!("Failed to resolve dnsaddr {host}: {e}")))?;
+
+    Ok(lookup
+        .iter()
+        .filter_map(|txt| String::from_utf8(txt.txt_data().concat()).ok())
+        .filter_map(|record| parse_dnsaddr_txt(&record))
+        .collect())
 }
@@ -0,0 +1,149 @@
+//! Detection of chain reorganizations when following the headers.
+
+use std::collections::BTreeMap;
+
+use celestia_types::hash::Hash;
+use celestia_types::ExtendedHeader;
+
+/// Default amount of the most recent heights tracked by the [`ReorgDetector`].
+pub const DEFAULT_TRACKED_HEIGHTS: usize = 1024;
+
+/// Reorganization of the chain reported by the [`ReorgDetector`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reorg {
+    /// Lowest height known to be replaced by the new chain.
+    pub from_height: u64,
+    /// Hash of the replaced header at `from_height`.
+    pub old_hash: Hash,
+    /// Hash of the new header at `from_height`.
+    pub new_hash: Hash,
+    /// The highest height that both chains have in common, if known.
+    ///
+    /// It is `None` if the reorg goes deeper than what the detector could verify,
+    /// e.g. when only the tip of the new chain was observed. In that case the
+    /// headers below `from_height` should be fetched again and fed to the detector.
+    pub fork_point: Option<u64>,
+}
+
+/// Tracker of the header hashes which detects chain reorganizations.
+///
+/// Headers are expected to be fed in an ascending order, e.g. as received from
+/// [`HeaderClient::header_subscribe`] or [`HeaderClient::header_get_range_by_height`].
+/// A reorg is reported when a header at an already seen height, or its parent,
+/// has a different hash than previously observed.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example(client: celestia_rpc::Client) -> celestia_rpc::Result<()> {
+/// use celestia_rpc::prelude::*;
+/// use celestia_rpc::reorg::ReorgDetector;
+/// use futures::StreamExt;
+///
+/// let mut detector = ReorgDetector::new();
+/// let mut headers = client.header_subscribe().await?;
+///
+/// while let Some(header) = headers.next().await {
+///     if let Some(reorg) = detector.observe(&header?) {
+///         println!("reorg from height {}", reorg.from_height);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`HeaderClient::header_subscribe`]: crate::HeaderClient::header_subscribe
+/// [`HeaderClient::header_get_range_by_height`]: crate::HeaderClient::header_get_range_by_height
+#[derive(Debug, Clone)]
+pub struct ReorgDetector {
+    hashes: BTreeMap<u64, Hash>,
+    max_tracked_heights: usize,
+}
+
+impl ReorgDetector {
+    /// Create a new detector tracking the [`DEFAULT_TRACKED_HEIGHTS`] most recent heights.
+    pub fn new() -> Self {
+        ReorgDetector::with_tracked_heights(DEFAULT_TRACKED_HEIGHTS)
+    }
+
+    /// Create a new detector tracking the given amount of the most recent heights.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_tracked_heights` is 0.
+    pub fn with_tracked_heights(max_tracked_heights: usize) -> Self {
+        assert!(
+            max_tracked_heights > 0,
+            "max_tracked_heights must be non-zero"
+        );
+
+        ReorgDetector {
+            hashes: BTreeMap::new(),
+            max_tracked_heights,
+        }
+    }
+
+    /// Get the tracked hash of the header at the given height.
+    pub fn hash(&self, height: u64) -> Option<Hash> {
+        self.hashes.get(&height).copied()
+    }
+
+    /// Observe the header, returning the [`Reorg`] if it replaces the tracked chain.
+    ///
+    /// After the reorg is reported, all the heights replaced by the new chain
+    /// are forgotten and the detector follows the new chain.
+    pub fn observe(&mut self, header: &ExtendedHeader) -> Option<Reorg> {
+        let height = header.height().value();
+        let hash = header.hash();
+        let parent = (height > 1).then(|| (height - 1, header.last_header_hash()));
+
+        let reorg = match parent {
+            // parent differs, so the fork point is somewhere below it
+            Some((parent_height, parent_hash))
+                if self
+                    .hash(parent_height)
+                    .is_some_and(|old| old != parent_hash) =>
+            {
+                Some(Reorg {
+                    from_height: parent_height,
+                    old_hash: self.hashes[&parent_height],
+                    new_hash: parent_hash,
+                    fork_point: None,
+                })
+            }
+            _ => self
+                .hash(height)
+                .filter(|old| *old != hash)
+                .map(|old_hash| Reorg {
+                    from_height: height,
+                    old_hash,
+                    new_hash: hash,
+                    // parent is the same in both chains, if tracked
+                    fork_point: parent
+                        .filter(|(parent_height, _)| self.hashes.contains_key(parent_height))
+                        .map(|(parent_height, _)| parent_height),
+                }),
+        };
+
+        if let Some(reorg) = &reorg {
+            self.hashes.retain(|height, _| *height < reorg.from_height);
+        }
+
+        if let Some((parent_height, parent_hash)) = parent {
+            self.hashes.insert(parent_height, parent_hash);
+        }
+        self.hashes.insert(height, hash);
+
+        while self.hashes.len() > self.max_tracked_heights {
+            self.hashes.pop_first();
+        }
+
+        reorg
+    }
+}
+
+impl Default for ReorgDetector {
+    fn default() -> Self {
+        ReorgDetector::new()
+    }
+}
@@ -4,13 +4,20 @@ use std::future::Future;
 use std::marker::{Send, Sync};
 
 use celestia_types::consts::appconsts::AppVersion;
-use celestia_types::nmt::Namespace;
+use celestia_types::nmt::{Namespace, NamespacedSha2Hasher};
+use celestia_types::row::{Row, RowId};
 use celestia_types::row_namespace_data::NamespaceData;
 use celestia_types::{ExtendedDataSquare, ExtendedHeader, RawShare, Share, ShareProof};
+use futures::stream::{self, Stream};
 use jsonrpsee::core::client::{ClientT, Error};
 use jsonrpsee::proc_macros::rpc;
+use rand::seq::index;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{is_node_error, HEADER_NOT_FOUND, OUTSIDE_SAMPLING_WINDOW};
+use crate::header::available_range;
+
 /// Response type for [`ShareClient::share_get_range`].
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -21,6 +28,29 @@ pub struct GetRangeResponse {
     pub proof: ShareProof,
 }
 
+impl GetRangeResponse {
+    /// Verify that the shares are proven to be included in the block with given header.
+    pub fn verify(&self, root: &ExtendedHeader) -> Result<(), Error> {
+        self.proof
+            .verify(root.dah.hash())
+            .map_err(|e| Error::Custom(e.to_string()))?;
+
+        let proven = self.proof.shares();
+
+        if self.shares.len() != proven.len()
+            || self
+                .shares
+                .iter()
+                .zip(proven)
+                .any(|(share, proven)| share.data() != proven)
+        {
+            return Err(Error::Custom("Shares don't match the proof".into()));
+        }
+
+        Ok(())
+    }
+}
+
 mod rpc {
     use super::*;
     use celestia_types::eds::RawExtendedDataSquare;
@@ -58,6 +88,9 @@ mod rpc {
 ///
 /// Please note that celestia-node requires just the block height for most of those API's.
 /// This trait instead requires [`ExtendedHeader`] to perform validation of the returned types.
+///
+/// Requests for heights outside of the node's sampling window fail with
+/// [`crate::Error::OutsideSamplingWindow`].
 // NOTE: we use EH wherever Share is returned because it's gonna be required in future
 // to check if shares are allowed to have version 1 in corresponding app version
 pub trait ShareClient: ClientT {
@@ -65,7 +98,7 @@ pub trait ShareClient: ClientT {
     fn share_get_eds<'a, 'b, 'fut>(
         &'a self,
         root: &'b ExtendedHeader,
-    ) -> impl Future<Output = Result<ExtendedDataSquare, Error>> + Send + 'fut
+    ) -> impl Future<Output = crate::Result<ExtendedDataSquare>> + Send + 'fut
     where
         'a: 'fut,
         'b: 'fut,
@@ -78,13 +111,45 @@ pub trait ShareClient: ClientT {
                 Error::Custom(e)
             })?;
 
-            let raw_eds = rpc::ShareClient::share_get_eds(self, root.height().value()).await?;
+            let height = root.height().value();
+            let raw_eds = rpc::ShareClient::share_get_eds(self, height).await;
+            let raw_eds = check_sampling_window(self, height, raw_eds).await?;
+
+            let eds = ExtendedDataSquare::from_raw(raw_eds, app_version)
+                .map_err(|e| Error::Custom(e.to_string()))?;
 
-            ExtendedDataSquare::from_raw(raw_eds, app_version)
-                .map_err(|e| Error::Custom(e.to_string()))
+            Ok(eds)
         }
     }
 
+    /// Get the rows of the EDS one by one, as they arrive.
+    ///
+    /// Only the first half of the rows is fetched, as it contains all the original
+    /// data. Each row is extended with the parity shares and verified against its
+    /// row root before being yielded, so consumers can process the square incrementally
+    /// without buffering it as a whole.
+    ///
+    /// The stream ends after the first error.
+    fn share_get_eds_streamed<'a, 'b, 'fut>(
+        &'a self,
+        root: &'b ExtendedHeader,
+    ) -> impl Stream<Item = crate::Result<(u16, Vec<Share>)>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        let ods_width = root.dah.square_width() / 2;
+
+        stream::unfold(Some(0), move |next_row| async move {
+            let row = next_row.filter(|row| *row < ods_width)?;
+            let res = get_ods_row(self, root, row).await;
+            let next_row = res.is_ok().then_some(row + 1);
+
+            Some((res.map(|shares| (row, shares)), next_row))
+        })
+    }
+
     /// GetRange gets a list of shares and their corresponding proof.
     ///
     /// The start and end index ignores parity shares and corresponds to ODS.
@@ -93,13 +158,17 @@ pub trait ShareClient: ClientT {
         root: &'b ExtendedHeader,
         start: u64,
         end: u64,
-    ) -> impl Future<Output = Result<GetRangeResponse, Error>> + Send + 'fut
+    ) -> impl Future<Output = crate::Result<GetRangeResponse>> + Send + 'fut
     where
         'a: 'fut,
         'b: 'fut,
         Self: Sized + Sync + 'fut,
     {
-        rpc::ShareClient::share_get_range(self, root.height().value(), start, end)
+        async move {
+            let height = root.height().value();
+            let response = rpc::ShareClient::share_get_range(self, height, start, end).await;
+            check_sampling_window(self, height, response).await
+        }
     }
 
     /// GetShare gets a Share by coordinates in EDS.
@@ -108,21 +177,25 @@ pub trait ShareClient: ClientT {
         root: &'b ExtendedHeader,
         row: u64,
         col: u64,
-    ) -> impl Future<Output = Result<Share, Error>> + Send + 'fut
+    ) -> impl Future<Output = crate::Result<Share>> + Send + 'fut
     where
         'a: 'fut,
         'b: 'fut,
         Self: Sized + Sync + 'fut,
     {
         async move {
-            let share =
-                rpc::ShareClient::share_get_share(self, root.height().value(), row, col).await?;
-            if is_ods_square(row, col, root.dah.square_width()) {
+            let height = root.height().value();
+            let share = rpc::ShareClient::share_get_share(self, height, row, col).await;
+            let share = check_sampling_window(self, height, share).await?;
+
+            let share = if is_ods_square(row, col, root.dah.square_width()) {
                 Share::from_raw(&share.data)
             } else {
                 Share::parity(&share.data)
             }
-            .map_err(|e| Error::Custom(e.to_string()))
+            .map_err(|e| Error::Custom(e.to_string()))?;
+
+            Ok(share)
         }
     }
 
@@ -135,30 +208,278 @@ pub trait ShareClient: ClientT {
         &'a self,
         root: &'b ExtendedHeader,
         namespace: Namespace,
-    ) -> impl Future<Output = Result<NamespaceData, Error>> + Send + 'fut
+    ) -> impl Future<Output = crate::Result<NamespaceData>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let height = root.height().value();
+            let data = rpc::ShareClient::share_get_namespace_data(self, height, namespace).await;
+            check_sampling_window(self, height, data).await
+        }
+    }
+
+    /// Get the shares of the namespace within the rows `start_row..end_row` of the ODS.
+    ///
+    /// Unlike [`ShareClient::share_get_namespace_data`], only the rows of the window
+    /// are queried. The shares are returned together with a single [`ShareProof`]
+    /// covering all of them, which can be checked with [`GetRangeResponse::verify`].
+    ///
+    /// The exact boundaries of the namespace are found by first requesting all the rows
+    /// containing it as a single verified range. If the namespace doesn't fill those rows
+    /// entirely, its shares are requested once more as a narrower range, so that the proof
+    /// covers only them. This costs one or two `share.GetRange` requests, and in the latter
+    /// case the namespace shares are downloaded twice.
+    ///
+    /// Returns `None` if the namespace has no shares in the window.
+    fn share_get_namespace_range<'a, 'b, 'fut>(
+        &'a self,
+        root: &'b ExtendedHeader,
+        namespace: Namespace,
+        start_row: u16,
+        end_row: u16,
+    ) -> impl Future<Output = crate::Result<Option<GetRangeResponse>>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let ods_width = root.dah.square_width() / 2;
+
+            if start_row >= end_row || end_row > ods_width {
+                return Err(Error::Custom(format!(
+                    "Invalid row window {start_row}..{end_row} for square of width {ods_width}"
+                ))
+                .into());
+            }
+
+            let mut rows = (start_row..end_row).filter(|row| {
+                root.dah
+                    .row_root(*row)
+                    .is_some_and(|row_root| row_root.contains::<NamespacedSha2Hasher>(*namespace))
+            });
+
+            let Some(first_row) = rows.next() else {
+                return Ok(None);
+            };
+            let last_row = rows.last().unwrap_or(first_row);
+
+            let ods_width = u64::from(ods_width);
+            let rows_start = u64::from(first_row) * ods_width;
+            let rows_end = (u64::from(last_row) + 1) * ods_width;
+
+            let rows = self.share_get_range(root, rows_start, rows_end).await?;
+            rows.verify(root)?;
+
+            let Some(first) = rows
+                .shares
+                .iter()
+                .position(|share| share.namespace() == namespace)
+            else {
+                return Ok(None);
+            };
+            let last = rows
+                .shares
+                .iter()
+                .rposition(|share| share.namespace() == namespace)
+                .expect("namespace share found");
+
+            let response = if first == 0 && last + 1 == rows.shares.len() {
+                rows
+            } else {
+                let start = rows_start + first as u64;
+                let end = rows_start + last as u64 + 1;
+
+                let response = self.share_get_range(root, start, end).await?;
+                response.verify(root)?;
+                response
+            };
+
+            if response.proof.namespace_id != namespace
+                || response
+                    .shares
+                    .iter()
+                    .any(|share| share.namespace() != namespace)
+            {
+                return Err(
+                    Error::Custom("Shares outside of the namespace in the range".into()).into(),
+                );
+            }
+
+            Ok(Some(response))
+        }
+    }
+
+    /// Get the sorted list of namespaces that have data in the block.
+    ///
+    /// The whole EDS of the block is fetched, see [`ExtendedDataSquare::namespaces`].
+    fn share_get_namespaces<'a, 'b, 'fut>(
+        &'a self,
+        root: &'b ExtendedHeader,
+    ) -> impl Future<Output = crate::Result<Vec<Namespace>>> + Send + 'fut
     where
         'a: 'fut,
         'b: 'fut,
         Self: Sized + Sync + 'fut,
     {
-        rpc::ShareClient::share_get_namespace_data(self, root.height().value(), namespace)
+        async move {
+            let eds = self.share_get_eds(root).await?;
+            Ok(eds.namespaces())
+        }
     }
 
     /// SharesAvailable subjectively validates if Shares committed to the given Root are available on the Network.
     fn share_shares_available<'a, 'fut>(
         &'a self,
         height: u64,
-    ) -> impl Future<Output = Result<(), Error>> + Send + 'fut
+    ) -> impl Future<Output = crate::Result<()>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        async move {
+            let available = rpc::ShareClient::share_shares_available(self, height).await;
+            check_sampling_window(self, height, available).await
+        }
+    }
+
+    /// Probabilistically check availability of the block by sampling random shares.
+    ///
+    /// Up to `samples` distinct shares of the original data square are fetched together
+    /// with their inclusion proofs and verified against the [`DataAvailabilityHeader`].
+    /// Returns `Ok(())` only if all of the sampled shares were verified.
+    ///
+    /// [`DataAvailabilityHeader`]: celestia_types::DataAvailabilityHeader
+    fn share_shares_available_sampled<'a, 'b, 'fut>(
+        &'a self,
+        root: &'b ExtendedHeader,
+        samples: usize,
+    ) -> impl Future<Output = crate::Result<()>> + Send + 'fut
     where
         'a: 'fut,
+        'b: 'fut,
         Self: Sized + Sync + 'fut,
     {
-        rpc::ShareClient::share_shares_available(self, height)
+        self.share_shares_available_sampled_with_rng(root, samples, rand::thread_rng())
+    }
+
+    /// Same as [`ShareClient::share_shares_available_sampled`], but with coordinates
+    /// of the sampled shares chosen using the provided random number generator.
+    fn share_shares_available_sampled_with_rng<'a, 'b, 'fut, R>(
+        &'a self,
+        root: &'b ExtendedHeader,
+        samples: usize,
+        mut rng: R,
+    ) -> impl Future<Output = crate::Result<()>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        R: Rng,
+        Self: Sized + Sync + 'fut,
+    {
+        let indexes = sample_ods_indexes(root.dah.square_width(), samples, &mut rng);
+
+        async move {
+            for index in indexes {
+                let response = self.share_get_range(root, index, index + 1).await?;
+
+                response.verify(root).map_err(|e| {
+                    Error::Custom(format!("Sample {index} failed verification: {e}"))
+                })?;
+            }
+
+            Ok(())
+        }
     }
 }
 
 impl<T> ShareClient for T where T: ClientT {}
 
+/// Map the error of a request for the given height to [`crate::Error::OutsideSamplingWindow`]
+/// if the node rejected it because the height is outside of its sampling window, meaning
+/// its data was pruned or never synced. Any other error is returned as [`crate::Error::JsonRpc`].
+///
+/// Celestia nodes don't report the tail of the window in the error, so it is found with
+/// [`HeaderClient::header_available_range`].
+///
+/// [`HeaderClient::header_available_range`]: crate::HeaderClient::header_available_range
+pub(crate) async fn sampling_window_error<C>(client: &C, height: u64, error: Error) -> crate::Error
+where
+    C: ClientT + Sync,
+{
+    let outside_window =
+        is_node_error(&error, OUTSIDE_SAMPLING_WINDOW) || is_node_error(&error, HEADER_NOT_FOUND);
+
+    if outside_window {
+        if let Ok((window_tail, _)) = available_range(client).await {
+            if height < window_tail {
+                return crate::Error::OutsideSamplingWindow {
+                    height,
+                    window_tail,
+                };
+            }
+        }
+    }
+
+    error.into()
+}
+
+/// Pass through the result of a request for the given height, classifying its error
+/// with [`sampling_window_error`].
+async fn check_sampling_window<C, T>(
+    client: &C,
+    height: u64,
+    result: Result<T, Error>,
+) -> crate::Result<T>
+where
+    C: ClientT + Sync,
+{
+    match result {
+        Ok(value) => Ok(value),
+        Err(e) => Err(sampling_window_error(client, height, e).await),
+    }
+}
+
+/// Get the row of the original data square extended with parity shares and verify it.
+async fn get_ods_row<C>(client: &C, root: &ExtendedHeader, row: u16) -> crate::Result<Vec<Share>>
+where
+    C: ClientT + Sync,
+{
+    let ods_width = u64::from(root.dah.square_width() / 2);
+    let start = u64::from(row) * ods_width;
+
+    let response = client
+        .share_get_range(root, start, start + ods_width)
+        .await?;
+
+    let id = RowId::new(row, root.height().value()).map_err(|e| Error::Custom(e.to_string()))?;
+    let extended =
+        Row::from_left_half(id, response.shares).map_err(|e| Error::Custom(e.to_string()))?;
+
+    extended
+        .verify(id, &root.dah)
+        .map_err(|e| Error::Custom(format!("Row {row} failed verification: {e}")))?;
+
+    Ok(extended.shares)
+}
+
+/// Choose distinct indexes of shares in the original data square.
+fn sample_ods_indexes<R>(square_width: u16, samples: usize, rng: &mut R) -> Vec<u64>
+where
+    R: Rng + ?Sized,
+{
+    let ods_width = usize::from(square_width / 2);
+    let ods_size = ods_width * ods_width;
+
+    index::sample(rng, ods_size, samples.min(ods_size))
+        .into_iter()
+        .map(|index| index as u64)
+        .collect()
+}
+
 fn is_ods_square(row: u64, column: u64, square_width: u16) -> bool {
     let ods_width = square_width / 2;
     row < ods_width as u64 && column < ods_width as u64
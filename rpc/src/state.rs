@@ -1,108 +1,320 @@
-use celestia_types::blob::RawBlob;
+//! celestia-node rpc types and methods related to state
+
+use std::future::Future;
+use std::marker::{Send, Sync};
+
+use celestia_types::blob::{self, RawBlob};
+use celestia_types::consts::appconsts::AppVersion;
 use celestia_types::state::{
     AccAddress, Address, Balance, QueryDelegationResponse, QueryRedelegationsResponse,
     QueryUnbondingDelegationResponse, RawTxResponse, Uint, ValAddress,
 };
+use celestia_types::Blob;
+use jsonrpsee::core::client::{ClientT, Error};
 use jsonrpsee::proc_macros::rpc;
 
-use crate::TxConfig;
+use crate::{HeaderClient, TxConfig};
+
+mod rpc {
+    use super::*;
+
+    #[rpc(client)]
+    pub trait State {
+        #[method(name = "state.AccountAddress")]
+        async fn state_account_address(&self) -> Result<Address, Error>;
+
+        #[method(name = "state.Balance")]
+        async fn state_balance(&self) -> Result<Balance, Error>;
+
+        #[method(name = "state.BalanceForAddress")]
+        async fn state_balance_for_address(&self, addr: &Address) -> Result<Balance, Error>;
+
+        #[method(name = "state.BeginRedelegate")]
+        async fn state_begin_redelegate(
+            &self,
+            src: &ValAddress,
+            dest: &ValAddress,
+            amount: Uint,
+            config: TxConfig,
+        ) -> Result<RawTxResponse, Error>;
+
+        #[method(name = "state.CancelUnbondingDelegation")]
+        async fn state_cancel_unbonding_delegation(
+            &self,
+            addr: &ValAddress,
+            amount: Uint,
+            height: Uint,
+            config: TxConfig,
+        ) -> Result<RawTxResponse, Error>;
+
+        #[method(name = "state.Delegate")]
+        async fn state_delegate(
+            &self,
+            addr: &ValAddress,
+            amount: Uint,
+            config: TxConfig,
+        ) -> Result<RawTxResponse, Error>;
+
+        #[method(name = "state.IsStopped")]
+        async fn state_is_stopped(&self) -> Result<bool, Error>;
+
+        #[method(name = "state.QueryDelegation")]
+        async fn state_query_delegation(
+            &self,
+            addr: &ValAddress,
+        ) -> Result<QueryDelegationResponse, Error>;
 
-#[rpc(client)]
-pub trait State {
+        #[method(name = "state.QueryRedelegations")]
+        async fn state_query_redelegations(
+            &self,
+            src: &ValAddress,
+            dest: &ValAddress,
+        ) -> Result<QueryRedelegationsResponse, Error>;
+
+        #[method(name = "state.QueryUnbonding")]
+        async fn state_query_unbonding(
+            &self,
+            addr: &ValAddress,
+        ) -> Result<QueryUnbondingDelegationResponse, Error>;
+
+        #[method(name = "state.SubmitPayForBlob")]
+        async fn state_submit_pay_for_blob(
+            &self,
+            blobs: &[RawBlob],
+            config: TxConfig,
+        ) -> Result<RawTxResponse, Error>;
+
+        #[method(name = "state.Transfer")]
+        async fn state_transfer(
+            &self,
+            to: &AccAddress,
+            amount: Uint,
+            config: TxConfig,
+        ) -> Result<RawTxResponse, Error>;
+
+        #[method(name = "Undelegate")]
+        async fn state_undelegate(
+            &self,
+            addr: &ValAddress,
+            amount: Uint,
+            config: TxConfig,
+        ) -> Result<RawTxResponse, Error>;
+    }
+}
+
+/// Client implementation for the `State` RPC API.
+pub trait StateClient: ClientT {
     /// AccountAddress retrieves the address of the node's account/signer
-    #[method(name = "state.AccountAddress")]
-    async fn state_account_address(&self) -> Result<Address, Error>;
+    fn state_account_address<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<Address, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_account_address(self)
+    }
 
     /// Balance retrieves the Celestia coin balance for the node's account/signer and verifies it against the corresponding block's AppHash.
-    #[method(name = "state.Balance")]
-    async fn state_balance(&self) -> Result<Balance, Error>;
+    fn state_balance<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<Balance, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_balance(self)
+    }
 
     /// BalanceForAddress retrieves the Celestia coin balance for the given address and verifies the returned balance against the corresponding block's AppHash.
     ///
     /// # NOTE
     ///
     /// The balance returned is the balance reported by the block right before the node's current head (head-1). This is due to the fact that for block N, the block's `AppHash` is the result of applying the previous block's transaction list.
-    #[method(name = "state.BalanceForAddress")]
-    async fn state_balance_for_address(&self, addr: &Address) -> Result<Balance, Error>;
+    fn state_balance_for_address<'a, 'b, 'fut>(
+        &'a self,
+        addr: &'b Address,
+    ) -> impl Future<Output = Result<Balance, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_balance_for_address(self, addr)
+    }
 
     /// BeginRedelegate sends a user's delegated tokens to a new validator for redelegation.
-    #[method(name = "state.BeginRedelegate")]
-    async fn state_begin_redelegate(
-        &self,
-        src: &ValAddress,
-        dest: &ValAddress,
+    fn state_begin_redelegate<'a, 'b, 'c, 'fut>(
+        &'a self,
+        src: &'b ValAddress,
+        dest: &'c ValAddress,
         amount: Uint,
         config: TxConfig,
-    ) -> Result<RawTxResponse, Error>;
+    ) -> impl Future<Output = Result<RawTxResponse, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        'c: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_begin_redelegate(self, src, dest, amount, config)
+    }
 
     /// CancelUnbondingDelegation cancels a user's pending undelegation from a validator.
-    #[method(name = "state.CancelUnbondingDelegation")]
-    async fn state_cancel_unbonding_delegation(
-        &self,
-        addr: &ValAddress,
+    fn state_cancel_unbonding_delegation<'a, 'b, 'fut>(
+        &'a self,
+        addr: &'b ValAddress,
         amount: Uint,
         height: Uint,
         config: TxConfig,
-    ) -> Result<RawTxResponse, Error>;
+    ) -> impl Future<Output = Result<RawTxResponse, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_cancel_unbonding_delegation(self, addr, amount, height, config)
+    }
 
     /// Delegate sends a user's liquid tokens to a validator for delegation.
-    #[method(name = "state.Delegate")]
-    async fn state_delegate(
-        &self,
-        addr: &ValAddress,
+    fn state_delegate<'a, 'b, 'fut>(
+        &'a self,
+        addr: &'b ValAddress,
         amount: Uint,
         config: TxConfig,
-    ) -> Result<RawTxResponse, Error>;
+    ) -> impl Future<Output = Result<RawTxResponse, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_delegate(self, addr, amount, config)
+    }
 
     /// IsStopped checks if the Module's context has been stopped.
-    #[method(name = "state.IsStopped")]
-    async fn state_is_stopped(&self) -> Result<bool, Error>;
+    fn state_is_stopped<'a, 'fut>(
+        &'a self,
+    ) -> impl Future<Output = Result<bool, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_is_stopped(self)
+    }
 
     /// QueryDelegation retrieves the delegation information between a delegator and a validator.
-    #[method(name = "state.QueryDelegation")]
-    async fn state_query_delegation(
-        &self,
-        addr: &ValAddress,
-    ) -> Result<QueryDelegationResponse, Error>;
+    fn state_query_delegation<'a, 'b, 'fut>(
+        &'a self,
+        addr: &'b ValAddress,
+    ) -> impl Future<Output = Result<QueryDelegationResponse, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_query_delegation(self, addr)
+    }
 
     /// QueryRedelegations retrieves the status of the redelegations between a delegator and a validator.
-    #[method(name = "state.QueryRedelegations")]
-    async fn state_query_redelegations(
-        &self,
-        src: &ValAddress,
-        dest: &ValAddress,
-    ) -> Result<QueryRedelegationsResponse, Error>;
+    ///
+    /// The node doesn't accept a page request for this query, so only the first page
+    /// of the redelegations is returned. A non-empty `pagination.next_key` in the
+    /// response means that the result was truncated.
+    fn state_query_redelegations<'a, 'b, 'c, 'fut>(
+        &'a self,
+        src: &'b ValAddress,
+        dest: &'c ValAddress,
+    ) -> impl Future<Output = Result<QueryRedelegationsResponse, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        'c: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_query_redelegations(self, src, dest)
+    }
 
     /// QueryUnbonding retrieves the unbonding status between a delegator and a validator.
-    #[method(name = "state.QueryUnbonding")]
-    async fn state_query_unbonding(
-        &self,
-        addr: &ValAddress,
-    ) -> Result<QueryUnbondingDelegationResponse, Error>;
+    fn state_query_unbonding<'a, 'b, 'fut>(
+        &'a self,
+        addr: &'b ValAddress,
+    ) -> impl Future<Output = Result<QueryUnbondingDelegationResponse, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_query_unbonding(self, addr)
+    }
 
     /// SubmitPayForBlob builds, signs and submits a PayForBlob transaction.
-    #[method(name = "state.SubmitPayForBlob")]
-    async fn state_submit_pay_for_blob(
-        &self,
-        blobs: &[RawBlob],
+    fn state_submit_pay_for_blob<'a, 'b, 'fut>(
+        &'a self,
+        blobs: &'b [RawBlob],
         config: TxConfig,
-    ) -> Result<RawTxResponse, Error>;
+    ) -> impl Future<Output = Result<RawTxResponse, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_submit_pay_for_blob(self, blobs, config)
+    }
+
+    /// Estimate the gas needed for a PayForBlob transaction submitting given blobs.
+    ///
+    /// The estimation is computed locally with [`blob::estimate_gas`], using the app version
+    /// of the current network head.
+    fn state_estimate_gas_for_blobs<'a, 'b, 'fut>(
+        &'a self,
+        blobs: &'b [Blob],
+    ) -> impl Future<Output = Result<u64, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: HeaderClient + Sized + Sync + 'fut,
+    {
+        async move {
+            let head = self.header_network_head().await?;
+            let app_version = head.header.version.app;
+            let app_version = AppVersion::from_u64(app_version).ok_or_else(|| {
+                let e = format!("Invalid or unsupported AppVersion: {app_version}");
+                Error::Custom(e)
+            })?;
+
+            Ok(blob::estimate_gas(blobs, app_version))
+        }
+    }
 
     /// Transfer sends the given amount of coins from default wallet of the node to the given account address.
-    #[method(name = "state.Transfer")]
-    async fn state_transfer(
-        &self,
-        to: &AccAddress,
+    fn state_transfer<'a, 'b, 'fut>(
+        &'a self,
+        to: &'b AccAddress,
         amount: Uint,
         config: TxConfig,
-    ) -> Result<RawTxResponse, Error>;
+    ) -> impl Future<Output = Result<RawTxResponse, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_transfer(self, to, amount, config)
+    }
 
     /// Undelegate undelegates a user's delegated tokens, unbonding them from the current validator.
-    #[method(name = "Undelegate")]
-    async fn state_undelegate(
-        &self,
-        addr: &ValAddress,
+    fn state_undelegate<'a, 'b, 'fut>(
+        &'a self,
+        addr: &'b ValAddress,
         amount: Uint,
         config: TxConfig,
-    ) -> Result<RawTxResponse, Error>;
+    ) -> impl Future<Output = Result<RawTxResponse, Error>> + Send + 'fut
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: Sized + Sync + 'fut,
+    {
+        rpc::StateClient::state_undelegate(self, addr, amount, config)
+    }
 }
+
+impl<T> StateClient for T where T: ClientT {}
@@ -0,0 +1,294 @@
+//! Subscriptions sharing a single connection and surviving reconnects.
+//!
+//! All subscriptions created with [`SubscriptionManager`] are multiplexed over
+//! a single websocket connection. When the connection is lost, the manager
+//! connects again and re-subscribes all of them before any of them is handed
+//! the new subscription.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex as StdMutex, Weak};
+use std::time::{Duration, Instant};
+
+use futures::future::{BoxFuture, FutureExt};
+use futures::lock::Mutex;
+use futures::StreamExt;
+use jsonrpsee::core::client::{Error, Subscription};
+use serde::de::DeserializeOwned;
+
+use crate::Client;
+
+type SubscribeFn<N> =
+    Box<dyn Fn(Arc<Client>) -> BoxFuture<'static, Result<Subscription<N>, Error>> + Send + Sync>;
+
+type CommitFn = Box<dyn FnOnce(u64) + Send>;
+
+/// Manager of subscriptions multiplexed over a single connection.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> celestia_rpc::Result<()> {
+/// use celestia_rpc::prelude::*;
+/// use celestia_rpc::SubscriptionManager;
+///
+/// let manager = SubscriptionManager::new("ws://localhost:36658", None).await?;
+/// let mut headers = manager
+///     .subscribe(|client| async move { client.header_subscribe().await })
+///     .await?;
+///
+/// while let Some(header) = headers.next().await {
+///     println!("{}", header?.height());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct SubscriptionManager {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    conn_str: String,
+    auth_token: Option<String>,
+    connection: Mutex<Connection>,
+}
+
+struct Connection {
+    client: Arc<Client>,
+    /// Incremented on each reconnect.
+    generation: u64,
+    subscriptions: Vec<Weak<dyn Resubscribe>>,
+}
+
+/// Health of a [`ManagedSubscription`].
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionHealth {
+    /// Number of items received.
+    pub received: u64,
+    /// Time when the last item was received.
+    pub last_received: Option<Instant>,
+    /// Number of times the subscription was re-created after reconnect.
+    pub resubscriptions: u64,
+}
+
+impl SubscriptionHealth {
+    /// Time elapsed since the last item was received.
+    ///
+    /// Returns `None` if nothing was received yet.
+    pub fn lag(&self) -> Option<Duration> {
+        self.last_received.map(|instant| instant.elapsed())
+    }
+}
+
+/// A subscription created by the [`SubscriptionManager`].
+pub struct ManagedSubscription<N> {
+    subscription: Subscription<N>,
+    generation: u64,
+    shared: Arc<Shared<N>>,
+    manager: Arc<Inner>,
+}
+
+struct Shared<N> {
+    subscribe: SubscribeFn<N>,
+    /// Subscription created on reconnect, waiting to replace the current one.
+    pending: StdMutex<Option<(u64, Subscription<N>)>>,
+    health: StdMutex<SubscriptionHealth>,
+}
+
+trait Resubscribe: Send + Sync {
+    /// Subscribe using the new client.
+    ///
+    /// On success, returns a function that installs the new subscription, so that
+    /// it can be called only after all subscriptions were re-created.
+    fn resubscribe(
+        self: Arc<Self>,
+        client: Arc<Client>,
+    ) -> BoxFuture<'static, Result<CommitFn, Error>>;
+}
+
+impl SubscriptionManager {
+    /// Connect to the node.
+    ///
+    /// Only 'ws\[s\]' protocol is supported, as subscriptions aren't available over http.
+    pub async fn new(conn_str: &str, auth_token: Option<&str>) -> crate::Result<Self> {
+        if !conn_str.starts_with("ws:") && !conn_str.starts_with("wss:") {
+            return Err(crate::Error::ProtocolNotSupported(conn_str.into()));
+        }
+
+        let client = Client::new(conn_str, auth_token).await?;
+
+        Ok(SubscriptionManager {
+            inner: Arc::new(Inner {
+                conn_str: conn_str.to_owned(),
+                auth_token: auth_token.map(ToOwned::to_owned),
+                connection: Mutex::new(Connection {
+                    client: Arc::new(client),
+                    generation: 0,
+                    subscriptions: Vec::new(),
+                }),
+            }),
+        })
+    }
+
+    /// Returns the client of the current connection.
+    ///
+    /// Please note that the client is replaced on reconnect.
+    pub async fn client(&self) -> Arc<Client> {
+        self.inner.connection.lock().await.client.clone()
+    }
+
+    /// Create a new subscription.
+    ///
+    /// The `subscribe` function is called with the client of the current connection,
+    /// and again with the new one after each reconnect.
+    pub async fn subscribe<N, F, Fut>(&self, subscribe: F) -> Result<ManagedSubscription<N>, Error>
+    where
+        N: DeserializeOwned + Send + 'static,
+        F: Fn(Arc<Client>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Subscription<N>, Error>> + Send + 'static,
+    {
+        let subscribe: SubscribeFn<N> = Box::new(move |client| subscribe(client).boxed());
+        let mut connection = self.inner.connection.lock().await;
+
+        let subscription = subscribe(connection.client.clone()).await?;
+        let shared = Arc::new(Shared {
+            subscribe,
+            pending: StdMutex::new(None),
+            health: StdMutex::new(SubscriptionHealth::default()),
+        });
+
+        let weak: Weak<dyn Resubscribe> = Arc::downgrade(&shared) as _;
+        connection.subscriptions.push(weak);
+
+        Ok(ManagedSubscription {
+            subscription,
+            generation: connection.generation,
+            shared,
+            manager: self.inner.clone(),
+        })
+    }
+
+    /// Connect again and re-subscribe all the subscriptions.
+    ///
+    /// Subscriptions are replaced only if all of them were re-created successfully.
+    pub async fn reconnect(&self) -> crate::Result<()> {
+        self.inner.reconnect(None).await
+    }
+}
+
+impl Inner {
+    /// Reconnect, unless a reconnect happened already after the `seen_generation`.
+    async fn reconnect(&self, seen_generation: Option<u64>) -> crate::Result<()> {
+        let mut connection = self.connection.lock().await;
+
+        if seen_generation.is_some_and(|generation| generation < connection.generation) {
+            return Ok(());
+        }
+
+        let client = Arc::new(Client::new(&self.conn_str, self.auth_token.as_deref()).await?);
+
+        connection
+            .subscriptions
+            .retain(|sub| sub.strong_count() > 0);
+        let subscriptions: Vec<_> = connection
+            .subscriptions
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect();
+
+        let mut commits = Vec::with_capacity(subscriptions.len());
+
+        for subscription in subscriptions {
+            commits.push(subscription.resubscribe(client.clone()).await?);
+        }
+
+        connection.generation += 1;
+        connection.client = client;
+
+        for commit in commits {
+            commit(connection.generation);
+        }
+
+        Ok(())
+    }
+}
+
+impl<N> Resubscribe for Shared<N>
+where
+    N: Send + 'static,
+{
+    fn resubscribe(
+        self: Arc<Self>,
+        client: Arc<Client>,
+    ) -> BoxFuture<'static, Result<CommitFn, Error>> {
+        async move {
+            let subscription = (self.subscribe)(client).await?;
+
+            let commit = move |generation| {
+                *self.pending.lock().expect("lock poisoned") = Some((generation, subscription));
+                self.health.lock().expect("lock poisoned").resubscriptions += 1;
+            };
+
+            Ok(Box::new(commit) as CommitFn)
+        }
+        .boxed()
+    }
+}
+
+impl<N> ManagedSubscription<N>
+where
+    N: DeserializeOwned + Send + 'static,
+{
+    /// Receive the next item.
+    ///
+    /// If the connection was lost, the manager reconnects and the item is received
+    /// from the new subscription. Returns `None` only if the subscription can't be
+    /// re-created anymore.
+    pub async fn next(&mut self) -> Option<Result<N, Error>> {
+        loop {
+            self.take_pending();
+
+            match self.subscription.next().await {
+                Some(Ok(item)) => {
+                    let mut health = self.shared.health.lock().expect("lock poisoned");
+                    health.received += 1;
+                    health.last_received = Some(Instant::now());
+                    return Some(Ok(item));
+                }
+                Some(Err(e)) => return Some(Err(e.into())),
+                None => {
+                    // Connection was replaced or lost
+                    if self.take_pending() {
+                        continue;
+                    }
+
+                    if let Err(e) = self.manager.reconnect(Some(self.generation)).await {
+                        return Some(Err(Error::Custom(e.to_string())));
+                    }
+
+                    if !self.take_pending() {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the health of the subscription.
+    pub fn health(&self) -> SubscriptionHealth {
+        self.shared.health.lock().expect("lock poisoned").clone()
+    }
+
+    /// Replace the subscription with the one created on reconnect, if any.
+    fn take_pending(&mut self) -> bool {
+        let pending = self.shared.pending.lock().expect("lock poisoned").take();
+
+        match pending {
+            Some((generation, subscription)) => {
+                self.subscription = subscription;
+                self.generation = generation;
+                true
+            }
+            None => false,
+        }
+    }
+}
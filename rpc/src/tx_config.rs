@@ -4,13 +4,31 @@ use serde::{
     Serialize,
 };
 
+/// Minimum gas price accepted by celestia-app validators by default, in `utia`.
+///
+/// Used by [`GasPriceBump`] as the starting point when [`TxConfig::gas_price`] isn't set.
+pub const DEFAULT_MIN_GAS_PRICE: f64 = 0.002;
+
+/// Configuration of retrying submissions rejected due to too low gas price.
+///
+/// Each retry multiplies the gas price by `factor`, never exceeding `price_cap`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasPriceBump {
+    /// Multiplier applied to the gas price on each retry.
+    pub factor: f64,
+    /// Maximum number of submission attempts, including the first one.
+    pub max_attempts: u32,
+    /// Maximum gas price that can be used.
+    pub price_cap: f64,
+}
+
 /// [`TxConfig`] specifies additional options that are be applied to the Tx.
 ///
 /// If no options are provided, then the default ones will be used.
 /// Read more about the mechanisms of fees and gas usage in [`submitting data blobs`].
 ///
 /// [`submitting data blobs`]: https://docs.celestia.org/developers/submit-data#fees-and-gas-limits
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct TxConfig {
     /// Specifies the address from the keystore that will sign transactions.
     ///
@@ -35,6 +53,10 @@ pub struct TxConfig {
     pub gas: Option<u64>,
     /// Specifies the account that will pay for the transaction.
     pub fee_granter_address: Option<AccAddress>,
+    /// Retry the blob submission with bumped gas price if it was rejected due to too low gas price.
+    ///
+    /// This is handled by the client and not sent to the node.
+    pub auto_bump: Option<GasPriceBump>,
 }
 
 impl TxConfig {
@@ -70,6 +92,14 @@ impl TxConfig {
         self
     }
 
+    /// Sets the [`auto_bump`] of the transaction.
+    ///
+    /// [`auto_bump`]: TxConfig::auto_bump
+    pub fn with_auto_bump(&mut self, auto_bump: GasPriceBump) -> &mut Self {
+        self.auto_bump = Some(auto_bump);
+        self
+    }
+
     /// Sets the [`key_name`] of the transaction.
     ///
     /// [`key_name`]: TxConfig::key_name
@@ -1,17 +1,32 @@
 #![cfg(not(target_arch = "wasm32"))]
 
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
 use std::time::Duration;
 
-use celestia_rpc::blob::BlobsAtHeight;
+use async_trait::async_trait;
+use celestia_rpc::blob::{
+    verify_blob_proofs, BlobFilter, BlobsAtHeight, SubmitRecord, SubmitState,
+};
 use celestia_rpc::prelude::*;
+use celestia_rpc::{GasPriceBump, TxConfig, DEFAULT_MIN_GAS_PRICE};
+use celestia_types::blob::RawMsgPayForBlobs;
 use celestia_types::consts::appconsts::AppVersion;
+use celestia_types::state::{AccAddress, AddressTrait};
 use celestia_types::{Blob, Commitment};
+use futures::StreamExt;
 use jsonrpsee::core::client::Subscription;
+use jsonrpsee::core::ClientError;
+use jsonrpsee::types::ErrorObjectOwned;
+use serde_json::Value;
 
 pub mod utils;
 
-use crate::utils::client::{blob_submit, new_test_client, AuthLevel};
+use crate::utils::client::{
+    blob_submit, blob_submit_and_verify, blob_submit_tracked, new_test_client, AuthLevel,
+};
+use crate::utils::mock::{MockClient, MockHandler};
 use crate::utils::{random_bytes, random_bytes_array, random_ns};
 
 #[tokio::test]
@@ -52,6 +67,306 @@ async fn blob_submit_and_get() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn blob_submit_signed_and_get() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespace = random_ns();
+    // share version one requires the blob to be signed by the account submitting it
+    let signer = AccAddress::new(client.state_account_address().await.unwrap().id());
+    let blob =
+        Blob::new_with_signer(namespace, random_bytes(1024), signer, AppVersion::V3).unwrap();
+
+    let submitted_height = blob_submit(&client, &[blob.clone()]).await.unwrap();
+
+    // the node looks the blob up by the commitment it computed from the shares itself
+    let received_blob = client
+        .blob_get(submitted_height, namespace, blob.commitment)
+        .await
+        .unwrap();
+
+    received_blob.validate(AppVersion::V3).unwrap();
+    assert_eq!(received_blob.signer(), blob.signer());
+    assert_eq!(received_blob.commitment, blob.commitment);
+    assert_eq!(
+        received_blob.to_shares().unwrap(),
+        blob.to_shares().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn blob_submit_dry_run() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespace = random_ns();
+    let blob = Blob::new(namespace, random_bytes(5), AppVersion::V2).unwrap();
+
+    let preview = client
+        .blob_submit_dry_run(&[blob.clone()], TxConfig::default())
+        .await
+        .unwrap();
+
+    assert_eq!(preview.commitments(), &[blob.commitment]);
+    assert!(preview.gas > 0);
+
+    let submitted_height = blob_submit(&client, &[blob.clone()]).await.unwrap();
+    let received_blob = client
+        .blob_get(submitted_height, namespace, blob.commitment)
+        .await
+        .unwrap();
+
+    assert_eq!(preview.commitments(), &[received_blob.commitment]);
+}
+
+#[tokio::test]
+async fn blob_offline_pay_for_blobs_msg() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let blob = Blob::new(random_ns(), random_bytes(5), AppVersion::V2).unwrap();
+    let signer = client.state_account_address().await.unwrap();
+
+    let offline_msg = blob.to_pay_for_blobs_msg(signer).unwrap();
+    let preview = client
+        .blob_submit_dry_run(&[blob], TxConfig::default())
+        .await
+        .unwrap();
+
+    assert_eq!(offline_msg, RawMsgPayForBlobs::from(preview.msg));
+}
+
+#[tokio::test]
+async fn blob_submit_auto_bump_gas_price() {
+    let client = GasPriceMock::new(DEFAULT_MIN_GAS_PRICE * 1.5);
+    let blob = Blob::new(random_ns(), random_bytes(5), AppVersion::V2).unwrap();
+    let mut config = TxConfig::default();
+    config.with_auto_bump(GasPriceBump {
+        factor: 2.0,
+        max_attempts: 3,
+        price_cap: 1.0,
+    });
+
+    let height = client.blob_submit(&[blob], config).await.unwrap();
+
+    assert_eq!(height, 1);
+    assert_eq!(
+        *client.gas_prices.lock().unwrap(),
+        [None, Some(DEFAULT_MIN_GAS_PRICE * 2.0)]
+    );
+}
+
+#[tokio::test]
+async fn blob_submit_auto_bump_price_cap() {
+    let client = GasPriceMock::new(DEFAULT_MIN_GAS_PRICE * 2.0);
+    let blob = Blob::new(random_ns(), random_bytes(5), AppVersion::V2).unwrap();
+    let mut config = TxConfig::default();
+    config
+        .with_gas_price(DEFAULT_MIN_GAS_PRICE)
+        .with_auto_bump(GasPriceBump {
+            factor: 2.0,
+            max_attempts: 5,
+            price_cap: DEFAULT_MIN_GAS_PRICE * 1.5,
+        });
+
+    let e = client.blob_submit(&[blob], config).await.unwrap_err();
+
+    assert!(celestia_rpc::blob::is_insufficient_gas_price(&e));
+    assert_eq!(
+        *client.gas_prices.lock().unwrap(),
+        [
+            Some(DEFAULT_MIN_GAS_PRICE),
+            Some(DEFAULT_MIN_GAS_PRICE * 1.5)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn blob_submit_and_verify_inclusion() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let blobs = &[
+        Blob::new(random_ns(), random_bytes(5), AppVersion::V2).unwrap(),
+        Blob::new(random_ns(), random_bytes(1024 * 1024), AppVersion::V2).unwrap(),
+    ];
+
+    let submitted_height = blob_submit_and_verify(&client, blobs).await.unwrap();
+
+    let received_blob = client
+        .blob_get(submitted_height, blobs[0].namespace, blobs[0].commitment)
+        .await
+        .unwrap();
+    assert_blob_equal_to_sent(&received_blob, &blobs[0]);
+}
+
+#[tokio::test]
+async fn blob_submit_and_verify_tampered_commitment() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let mut blob = Blob::new(random_ns(), random_bytes(5), AppVersion::V2).unwrap();
+    blob.commitment = Commitment::new(random_bytes_array());
+
+    blob_submit_and_verify(&client, &[blob]).await.unwrap_err();
+}
+
+#[tokio::test]
+async fn verify_blob_proofs_rejects_tampering() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespace = random_ns();
+    let blob = Blob::new(namespace, random_bytes(1024), AppVersion::V2).unwrap();
+
+    let height = blob_submit(&client, &[blob.clone()]).await.unwrap();
+    let header = client.header_get_by_height(height).await.unwrap();
+    let index = client
+        .blob_get(height, namespace, blob.commitment)
+        .await
+        .unwrap()
+        .index
+        .unwrap();
+    let proofs = client
+        .blob_get_proof(height, namespace, blob.commitment)
+        .await
+        .unwrap();
+
+    verify_blob_proofs(&header, &blob, index, &proofs).unwrap();
+
+    // different data in the same namespace
+    let mut data = blob.data.clone();
+    data[0] ^= 0xff;
+    let tampered_blob = Blob::new(namespace, data, AppVersion::V2).unwrap();
+    verify_blob_proofs(&header, &tampered_blob, index, &proofs).unwrap_err();
+
+    // proofs not covering all the shares
+    verify_blob_proofs(&header, &blob, index, &proofs[..proofs.len() - 1]).unwrap_err();
+
+    // proofs checked against the next row
+    let wrong_index = index + u64::from(header.dah.square_width() / 2);
+    verify_blob_proofs(&header, &blob, wrong_index, &proofs).unwrap_err();
+}
+
+#[tokio::test]
+async fn verify_blob_proofs_past_first_row() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let mut namespaces = [random_ns(), random_ns()];
+    namespaces.sort();
+    // the first blob fills multiple rows, pushing the second one past row 0
+    let blobs = [
+        Blob::new(namespaces[0], random_bytes(16 * 1024), AppVersion::V2).unwrap(),
+        Blob::new(namespaces[1], random_bytes(1024), AppVersion::V2).unwrap(),
+    ];
+    let blob = &blobs[1];
+
+    let height = blob_submit(&client, &blobs).await.unwrap();
+    let header = client.header_get_by_height(height).await.unwrap();
+    let index = client
+        .blob_get(height, blob.namespace, blob.commitment)
+        .await
+        .unwrap()
+        .index
+        .unwrap();
+    let proofs = client
+        .blob_get_proof(height, blob.namespace, blob.commitment)
+        .await
+        .unwrap();
+
+    let ods_width = u64::from(header.dah.square_width() / 2);
+    assert!(index / ods_width > 0);
+
+    verify_blob_proofs(&header, blob, index, &proofs).unwrap();
+}
+
+#[tokio::test]
+async fn blob_submit_tracked_states() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespace = random_ns();
+    let blob = Blob::new(namespace, random_bytes(5), AppVersion::V2).unwrap();
+
+    let states = blob_submit_tracked(&client, &[blob.clone()]).await;
+
+    let [SubmitState::Broadcast, SubmitState::Committed { height }, SubmitState::Confirmed { height: confirmed }] =
+        states[..]
+    else {
+        panic!("unexpected states: {states:?}");
+    };
+    assert_eq!(height, confirmed);
+
+    let received_blob = client
+        .blob_get(height, namespace, blob.commitment)
+        .await
+        .unwrap();
+    assert_blob_equal_to_sent(&received_blob, &blob);
+}
+
+#[tokio::test]
+async fn blob_submit_tracked_failed() {
+    // rejects every submission with the default gas price
+    let client = GasPriceMock::new(1.0);
+    let blob = Blob::new(random_ns(), random_bytes(5), AppVersion::V2).unwrap();
+
+    let states: Vec<_> = client
+        .blob_submit_tracked(&[blob], TxConfig::default())
+        .collect()
+        .await;
+
+    let [SubmitState::Failed { reason }] = &states[..] else {
+        panic!("unexpected states: {states:?}");
+    };
+    assert!(reason.contains("insufficient fee"), "{reason}");
+}
+
+#[tokio::test]
+async fn blob_submit_awaited() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespace = random_ns();
+    let blob = Blob::new(namespace, random_bytes(5), AppVersion::V2).unwrap();
+
+    let height = client
+        .blob_submit_awaited(
+            &[blob.clone()],
+            TxConfig::default(),
+            Duration::from_secs(30),
+        )
+        .await
+        .unwrap();
+
+    let local_head = client.header_local_head().await.unwrap();
+    assert!(local_head.height().value() >= height);
+
+    let received_blob = client
+        .blob_get(height, namespace, blob.commitment)
+        .await
+        .unwrap();
+    assert_blob_equal_to_sent(&received_blob, &blob);
+}
+
+#[tokio::test]
+async fn blob_submit_idempotent_retry() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespace = random_ns();
+    let blob = Blob::new(namespace, random_bytes(5), AppVersion::V2).unwrap();
+    let mut log = BTreeMap::new();
+
+    // Simulate a submission which timed out before its result was recorded
+    let attempted_at = client.header_network_head().await.unwrap().height().value();
+    log.insert(
+        blob.commitment,
+        SubmitRecord {
+            attempted_at,
+            included_at: None,
+        },
+    );
+    let submitted_height = blob_submit(&client, &[blob.clone()]).await.unwrap();
+
+    // Retry must find the previous submission instead of submitting again
+    let height = client
+        .blob_submit_idempotent(&[blob.clone()], TxConfig::default(), &mut log)
+        .await
+        .unwrap();
+
+    assert_eq!(height, submitted_height);
+    assert_eq!(log[&blob.commitment].included_at, Some(submitted_height));
+
+    let blobs = client
+        .blob_get_all(submitted_height, &[namespace])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(blobs.len(), 1);
+}
+
 #[tokio::test]
 async fn blob_submit_and_get_all() {
     let client = new_test_client(AuthLevel::Write).await.unwrap();
@@ -87,6 +402,159 @@ async fn blob_submit_and_get_all() {
     }
 }
 
+#[tokio::test]
+async fn blob_get_all_filtered() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespaces = &[random_ns()];
+
+    let blobs = [5, 100, 1000]
+        .map(|size| Blob::new(namespaces[0], random_bytes(size), AppVersion::V2).unwrap());
+
+    let submitted_height = blob_submit(&client, &blobs[..]).await.unwrap();
+
+    let received_blobs = client
+        .blob_get_all_filtered(submitted_height, namespaces, BlobFilter::default())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(received_blobs.len(), 3);
+
+    let filter = BlobFilter {
+        min_size: Some(50),
+        max_size: Some(500),
+        ..Default::default()
+    };
+    let received_blobs = client
+        .blob_get_all_filtered(submitted_height, namespaces, filter)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(received_blobs.len(), 1);
+    assert_blob_equal_to_sent(&received_blobs[0], &blobs[1]);
+
+    let filter = BlobFilter {
+        min_size: Some(100),
+        ..Default::default()
+    };
+    let received_blobs = client
+        .blob_get_all_filtered(submitted_height, namespaces, filter)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(received_blobs.len(), 2);
+    assert_blob_equal_to_sent(&received_blobs[0], &blobs[1]);
+    assert_blob_equal_to_sent(&received_blobs[1], &blobs[2]);
+
+    let filter = BlobFilter {
+        share_version: Some(1),
+        ..Default::default()
+    };
+    let received_blobs = client
+        .blob_get_all_filtered(submitted_height, namespaces, filter)
+        .await
+        .unwrap();
+    assert!(received_blobs.is_none());
+}
+
+#[test]
+fn blob_filter_signer() {
+    let namespace = random_ns();
+    let signer: AccAddress = "celestia1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5wgawu3"
+        .parse()
+        .unwrap();
+    let other_signer: AccAddress = "celestia1zsf3yygspu8q6rqtpgysspcxq5zqxqspun08lw"
+        .parse()
+        .unwrap();
+
+    let unsigned = Blob::new(namespace, random_bytes(5), AppVersion::V3).unwrap();
+    let signed =
+        Blob::new_with_signer(namespace, random_bytes(5), signer.clone(), AppVersion::V3).unwrap();
+    let other_signed =
+        Blob::new_with_signer(namespace, random_bytes(5), other_signer, AppVersion::V3).unwrap();
+
+    let filter = BlobFilter {
+        signer: Some(signer),
+        ..Default::default()
+    };
+    assert!(filter.matches(&signed));
+    assert!(!filter.matches(&other_signed));
+    assert!(!filter.matches(&unsigned));
+
+    assert!(BlobFilter::default().matches(&unsigned));
+    assert!(BlobFilter::default().matches(&signed));
+}
+
+#[tokio::test]
+async fn blob_subscribe_filtered() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespace = random_ns();
+
+    let filter = BlobFilter {
+        max_size: Some(10),
+        ..Default::default()
+    };
+    let mut subscription = client
+        .blob_subscribe_filtered(namespace, filter)
+        .await
+        .unwrap();
+
+    let blobs =
+        [5, 100].map(|size| Blob::new(namespace, random_bytes(size), AppVersion::V2).unwrap());
+    let submitted_height = blob_submit(&client, &blobs[..]).await.unwrap();
+
+    loop {
+        let item = subscription.next().await.unwrap().unwrap();
+
+        if item.height == submitted_height {
+            let received_blobs = item.blobs.unwrap();
+            assert_eq!(received_blobs.len(), 1);
+            assert_blob_equal_to_sent(&received_blobs[0], &blobs[0]);
+            break;
+        }
+    }
+}
+
+#[tokio::test]
+async fn blob_stream_from() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespaces = [random_ns(), random_ns()];
+
+    // submitted before the stream is created, must be backfilled
+    let blob1 = Blob::new(namespaces[0], random_bytes(10), AppVersion::V2).unwrap();
+    let start_height = blob_submit(&client, &[blob1.clone()]).await.unwrap();
+
+    let mut stream = Box::pin(client.blob_stream_from(start_height, &namespaces));
+
+    let (height, blobs) = stream.next().await.unwrap().unwrap();
+    assert_eq!(height, start_height);
+    assert_eq!(blobs.len(), 1);
+    assert_blob_equal_to_sent(&blobs[0], &blob1);
+
+    // submitted after the stream started, delivered live
+    let blob2 = Blob::new(namespaces[1], random_bytes(10), AppVersion::V2).unwrap();
+    let other_ns_blob = Blob::new(random_ns(), random_bytes(10), AppVersion::V2).unwrap();
+    let end_height = blob_submit(&client, &[blob2.clone(), other_ns_blob])
+        .await
+        .unwrap();
+
+    let mut expected_height = start_height + 1;
+
+    loop {
+        let (height, blobs) = stream.next().await.unwrap().unwrap();
+        // no gaps and no duplicates
+        assert_eq!(height, expected_height);
+        expected_height += 1;
+
+        if height == end_height {
+            assert_eq!(blobs.len(), 1);
+            assert_blob_equal_to_sent(&blobs[0], &blob2);
+            break;
+        }
+
+        assert!(blobs.is_empty());
+    }
+}
+
 #[tokio::test]
 async fn blob_submit_and_get_large() {
     let client = new_test_client(AuthLevel::Write).await.unwrap();
@@ -240,3 +708,35 @@ fn assert_blob_equal_to_sent(received: &Blob, sent: &Blob) {
     sent.index = received.index;
     assert_eq!(&sent, received);
 }
+
+/// Handler accepting only the `blob.Submit` requests with high enough gas price.
+struct GasPriceMock {
+    min_gas_price: f64,
+    gas_prices: Mutex<Vec<Option<f64>>>,
+}
+
+impl GasPriceMock {
+    fn new(min_gas_price: f64) -> MockClient<Self> {
+        MockClient(GasPriceMock {
+            min_gas_price,
+            gas_prices: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+#[async_trait]
+impl MockHandler for GasPriceMock {
+    async fn request(&self, method: &str, params: Value) -> Result<Value, ClientError> {
+        assert_eq!(method, "blob.Submit");
+
+        let gas_price = params[1]["gas_price"].as_f64();
+        self.gas_prices.lock().unwrap().push(gas_price);
+
+        if gas_price.unwrap_or(0.0) < self.min_gas_price {
+            let msg = "insufficient minimum gas price for this node: insufficient fee";
+            return Err(ErrorObjectOwned::owned(-32000, msg, None::<()>).into());
+        }
+
+        Ok(serde_json::json!(1))
+    }
+}
@@ -0,0 +1,127 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::env::VarError;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use celestia_rpc::client::{ConcurrencyLimit, AUTH_TOKEN_ENV, URL_ENV};
+use celestia_rpc::{Client, Error};
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::core::params::ArrayParams;
+use jsonrpsee::core::ClientError;
+use serde_json::Value;
+use tokio::sync::Semaphore;
+use tokio::time::{sleep, timeout};
+
+pub mod utils;
+
+use crate::utils::mock::{MockClient, MockHandler};
+
+#[test]
+fn bearer_headers() {
+    use celestia_rpc::auth::bearer_headers;
+
+    let headers = bearer_headers(Some("secret")).unwrap();
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers["Authorization"], "Bearer secret");
+
+    assert!(bearer_headers(None).unwrap().is_empty());
+    bearer_headers(Some("invalid\ntoken")).unwrap_err();
+}
+
+#[tokio::test]
+async fn client_from_env() {
+    let vars = |vars: &'static [(&'static str, &'static str)]| {
+        move |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, val)| val.to_string())
+                .ok_or(VarError::NotPresent)
+        }
+    };
+
+    let err = Client::from_env_with(vars(&[])).await.err().unwrap();
+    assert!(matches!(err, Error::EnvVar(URL_ENV, VarError::NotPresent)));
+
+    // http client doesn't connect until the first request
+    let client = Client::from_env_with(vars(&[
+        (URL_ENV, "http://localhost:26658"),
+        (AUTH_TOKEN_ENV, "secret"),
+    ]))
+    .await
+    .unwrap();
+    assert!(matches!(client, Client::Http(_)));
+
+    let err = Client::from_env_with(vars(&[(URL_ENV, "tcp://localhost:26658")]))
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::ProtocolNotSupported(_)));
+}
+
+#[tokio::test]
+async fn concurrency_limit_queues_requests() {
+    let client = Arc::new(ConcurrencyLimit::new(GatedClient::new(), 2));
+
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.request::<u64, _>("test", ArrayParams::new()).await })
+        })
+        .collect();
+
+    wait_for_started(&client, 2).await;
+    assert_eq!(client.in_flight(), 2);
+
+    // third request must wait for a free slot
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(client.inner().started.load(Ordering::SeqCst), 2);
+
+    client.inner().release.add_permits(1);
+    wait_for_started(&client, 3).await;
+    assert_eq!(client.in_flight(), 2);
+
+    client.inner().release.add_permits(2);
+    for handle in handles {
+        assert_eq!(handle.await.unwrap().unwrap(), 1);
+    }
+    assert_eq!(client.in_flight(), 0);
+}
+
+async fn wait_for_started(client: &ConcurrencyLimit<MockClient<GatedClient>>, started: usize) {
+    timeout(Duration::from_secs(5), async {
+        while client.inner().started.load(Ordering::SeqCst) < started {
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+/// Handler which holds each request until it is released.
+#[derive(Debug)]
+struct GatedClient {
+    started: AtomicUsize,
+    release: Semaphore,
+}
+
+impl GatedClient {
+    fn new() -> MockClient<Self> {
+        MockClient(GatedClient {
+            started: AtomicUsize::new(0),
+            release: Semaphore::new(0),
+        })
+    }
+}
+
+#[async_trait]
+impl MockHandler for GatedClient {
+    async fn request(&self, _method: &str, _params: Value) -> Result<Value, ClientError> {
+        self.started.fetch_add(1, Ordering::SeqCst);
+        self.release.acquire().await.unwrap().forget();
+
+        Ok(serde_json::json!(1))
+    }
+}
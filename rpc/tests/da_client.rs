@@ -0,0 +1,105 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use celestia_rpc::{DaClient, TxConfig};
+use celestia_types::consts::appconsts::AppVersion;
+use celestia_types::nmt::Namespace;
+use celestia_types::row_namespace_data::NamespaceData;
+use celestia_types::test_utils::ExtendedHeaderGenerator;
+use celestia_types::{Blob, Commitment, ExtendedHeader};
+use jsonrpsee::core::ClientError;
+
+pub mod utils;
+
+use crate::utils::client::{new_test_client, AuthLevel};
+use crate::utils::{random_bytes, random_ns};
+
+/// In-memory client including each submission in a newly generated header.
+#[derive(Default)]
+struct MockDaClient {
+    state: Mutex<MockState>,
+}
+
+#[derive(Default)]
+struct MockState {
+    generator: ExtendedHeaderGenerator,
+    headers: Vec<ExtendedHeader>,
+    blobs: Vec<(u64, Blob)>,
+}
+
+#[async_trait]
+impl DaClient for MockDaClient {
+    async fn blob_submit(&self, blobs: &[Blob], _opts: TxConfig) -> Result<u64, ClientError> {
+        let mut state = self.state.lock().unwrap();
+        let header = state.generator.next();
+        let height = header.height().value();
+
+        state.headers.push(header);
+        state
+            .blobs
+            .extend(blobs.iter().map(|blob| (height, blob.clone())));
+
+        Ok(height)
+    }
+
+    async fn blob_get(
+        &self,
+        height: u64,
+        namespace: Namespace,
+        commitment: Commitment,
+    ) -> Result<Blob, ClientError> {
+        let state = self.state.lock().unwrap();
+        state
+            .blobs
+            .iter()
+            .find(|(h, blob)| {
+                *h == height && blob.namespace == namespace && blob.commitment == commitment
+            })
+            .map(|(_, blob)| blob.clone())
+            .ok_or_else(|| ClientError::Custom("blob: not found".into()))
+    }
+
+    async fn header_get_by_height(&self, height: u64) -> celestia_rpc::Result<ExtendedHeader> {
+        let state = self.state.lock().unwrap();
+        state
+            .headers
+            .iter()
+            .find(|header| header.height().value() == height)
+            .cloned()
+            .ok_or_else(|| ClientError::Custom("header: not found".into()).into())
+    }
+
+    async fn share_get_namespace_data(
+        &self,
+        _root: &ExtendedHeader,
+        _namespace: Namespace,
+    ) -> celestia_rpc::Result<NamespaceData> {
+        Ok(NamespaceData { rows: Vec::new() })
+    }
+}
+
+#[tokio::test]
+async fn boxed_clients() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let clients: Vec<Box<dyn DaClient>> = vec![Box::new(client), Box::<MockDaClient>::default()];
+
+    for client in &clients {
+        let namespace = random_ns();
+        let blob = Blob::new(namespace, random_bytes(5), AppVersion::V2).unwrap();
+
+        let height = client
+            .blob_submit(&[blob.clone()], TxConfig::default())
+            .await
+            .unwrap();
+        let received_blob = client
+            .blob_get(height, namespace, blob.commitment)
+            .await
+            .unwrap();
+        let header = client.header_get_by_height(height).await.unwrap();
+
+        assert_eq!(received_blob.data, blob.data);
+        assert_eq!(header.height().value(), height);
+    }
+}
@@ -1,10 +1,29 @@
 #![cfg(not(target_arch = "wasm32"))]
 
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use celestia_rpc::header::{is_header_not_found, MAX_VERIFIED_DISTANCE};
 use celestia_rpc::prelude::*;
+use celestia_rpc::{Error, SubscriptionManager};
+use celestia_types::hash::Hash;
+use celestia_types::test_utils::ExtendedHeaderGenerator;
+use celestia_types::{ExtendedHeader, TrustedHeader};
+use futures::StreamExt;
+use jsonrpsee::core::client::{BatchResponse, ClientT, Subscription, SubscriptionClientT};
+use jsonrpsee::core::params::BatchRequestBuilder;
+use jsonrpsee::core::traits::ToRpcParams;
+use jsonrpsee::core::ClientError;
+use jsonrpsee::types::ErrorObjectOwned;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 
 pub mod utils;
 
-use crate::utils::client::{new_test_client, AuthLevel};
+use crate::utils::client::{new_test_client, new_test_subscription_manager, AuthLevel};
+use crate::utils::mock::{MockClient, MockHandler};
 
 #[tokio::test]
 async fn local_head() {
@@ -35,6 +54,66 @@ async fn get_by_height() {
     second_header.validate().unwrap();
 }
 
+#[tokio::test]
+async fn get_by_height_verified() {
+    let client = new_test_client(AuthLevel::Read).await.unwrap();
+    let trusting_period = Duration::from_secs(7 * 24 * 60 * 60);
+
+    let head_height = client.header_local_head().await.unwrap().height().value();
+    let trusted_height = head_height.saturating_sub(5).max(1);
+    let trusted_header = client.header_get_by_height(trusted_height).await.unwrap();
+    let trusted = TrustedHeader {
+        height: trusted_height,
+        hash: trusted_header.hash(),
+    };
+
+    // forward, backward and the trusted header itself
+    for height in [
+        head_height,
+        trusted_height.saturating_sub(5).max(1),
+        trusted_height,
+    ] {
+        let header = client
+            .header_get_by_height_verified(height, &trusted, trusting_period)
+            .await
+            .unwrap();
+        assert_eq!(header, client.header_get_by_height(height).await.unwrap());
+    }
+
+    // anchor pointing to the hash of a header from a different height
+    let forged = TrustedHeader {
+        height: trusted_height,
+        hash: client
+            .header_get_by_height(head_height)
+            .await
+            .unwrap()
+            .hash(),
+    };
+    client
+        .header_get_by_height_verified(head_height, &forged, trusting_period)
+        .await
+        .unwrap_err();
+
+    let forged = TrustedHeader {
+        height: trusted_height,
+        hash: Hash::Sha256([0xab; 32]),
+    };
+    client
+        .header_get_by_height_verified(head_height, &forged, trusting_period)
+        .await
+        .unwrap_err();
+
+    // too far from the anchor
+    client
+        .header_get_by_height_verified(
+            trusted_height + MAX_VERIFIED_DISTANCE + 1,
+            &trusted,
+            trusting_period,
+        )
+        .await
+        .unwrap_err();
+}
+
 #[tokio::test]
 async fn get_by_height_non_existent() {
     let client = new_test_client(AuthLevel::Read).await.unwrap();
@@ -42,6 +121,54 @@ async fn get_by_height_non_existent() {
     client.header_get_by_height(999_999_999).await.unwrap_err();
 }
 
+#[tokio::test]
+async fn available_range() {
+    let client = new_test_client(AuthLevel::Read).await.unwrap();
+
+    let (from, to) = client.header_available_range().await.unwrap();
+    let local_head = client.header_local_head().await.unwrap();
+
+    assert!(from <= to);
+    assert!(to <= local_head.height().value());
+    client.header_get_by_height(from).await.unwrap();
+    client.header_get_by_height(to).await.unwrap();
+
+    if from > 1 {
+        let err = client.header_get_by_height(from - 1).await.unwrap_err();
+        assert!(
+            matches!(err, Error::OutsideSamplingWindow { window_tail, .. } if window_tail == from),
+            "{err}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn available_range_of_pruned_store() {
+    let head = ExtendedHeaderGenerator::new_from_height(1000).next();
+    let client = MockClient(PrunedStoreMock {
+        tail: 321,
+        head,
+        probes: AtomicU64::new(0),
+    });
+
+    let (from, to) = client.header_available_range().await.unwrap();
+
+    assert_eq!((from, to), (321, 1000));
+    assert!(client.probes.load(Ordering::SeqCst) <= 10);
+
+    let err = client.header_get_by_height(from - 1).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::OutsideSamplingWindow {
+                height: 320,
+                window_tail: 321
+            }
+        ),
+        "{err}"
+    );
+}
+
 #[tokio::test]
 async fn get_by_hash() {
     let client = new_test_client(AuthLevel::Read).await.unwrap();
@@ -55,6 +182,20 @@ async fn get_by_hash() {
     assert_eq!(genesis_header, genesis_header2);
 }
 
+#[tokio::test]
+async fn get_by_hash_non_existent() {
+    let client = new_test_client(AuthLevel::Read).await.unwrap();
+
+    let err = client
+        .header_get_by_hash(Hash::Sha256([0xff; 32]))
+        .await
+        .unwrap_err();
+    assert!(is_header_not_found(&err), "{err}");
+
+    let err = client.header_get_by_hash(Hash::None).await.unwrap_err();
+    assert!(!is_header_not_found(&err));
+}
+
 #[tokio::test]
 async fn get_range_by_height() {
     let client = new_test_client(AuthLevel::Read).await.unwrap();
@@ -88,6 +229,26 @@ async fn network_head() {
     adjacent_header.verify(&network_head).unwrap();
 }
 
+#[tokio::test]
+async fn subjective_head() {
+    // Default trusting period of celestia-node
+    const TRUSTING_PERIOD: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+    let client = new_test_client(AuthLevel::Read).await.unwrap();
+
+    let subjective_head = client.header_subjective_head().await.unwrap();
+    let network_head = client.header_network_head().await.unwrap();
+
+    subjective_head.validate().unwrap();
+    assert!(subjective_head.height() <= network_head.height());
+
+    let lag = network_head
+        .time()
+        .duration_since(subjective_head.time())
+        .unwrap_or_default();
+    assert!(lag <= TRUSTING_PERIOD);
+}
+
 #[tokio::test]
 async fn subscribe() {
     let client = new_test_client(AuthLevel::Read).await.unwrap();
@@ -116,3 +277,149 @@ async fn sync_state() {
     let state2 = client.header_sync_state().await.unwrap();
     assert!(state2.height > state1.height);
 }
+
+#[tokio::test]
+async fn subscribe_gapless() {
+    let client = new_test_client(AuthLevel::Read).await.unwrap();
+
+    // Simulate a dropped subscription by resuming from a header that
+    // is a few blocks behind the network head.
+    let last_delivered = client.header_local_head().await.unwrap();
+    let last_height = last_delivered.height().value();
+    client
+        .header_wait_for_height(last_height + 3)
+        .await
+        .unwrap();
+
+    let incoming_headers = client.header_subscribe_gapless(Some(last_delivered.clone()));
+    let headers: Vec<_> = incoming_headers
+        .take(5)
+        .map(|header| header.unwrap())
+        .collect()
+        .await;
+
+    let mut prev = last_delivered;
+    for header in headers {
+        assert_eq!(header.height().value(), prev.height().value() + 1);
+        prev.verify(&header).unwrap();
+        prev = header;
+    }
+}
+
+#[tokio::test]
+async fn subscribe_gapless_after_drop() {
+    let client = new_test_client(AuthLevel::Read).await.unwrap();
+    let (manager, proxy) = new_test_subscription_manager(AuthLevel::Read)
+        .await
+        .unwrap();
+    let managed = ManagedClient(manager);
+
+    let mut incoming_headers = Box::pin(managed.header_subscribe_gapless(None));
+    let mut prev = incoming_headers.next().await.unwrap().unwrap();
+    let first_height = prev.height().value();
+
+    // Kill the subscription mid-stream and let the node produce headers in the
+    // meantime, so that they must be backfilled after subscribing again.
+    proxy.drop_connections();
+    managed.0.reconnect().await.unwrap();
+    client
+        .header_wait_for_height(first_height + 3)
+        .await
+        .unwrap();
+
+    for _ in 0..5 {
+        let header = incoming_headers.next().await.unwrap().unwrap();
+        assert_eq!(header.height().value(), prev.height().value() + 1);
+        prev.verify(&header).unwrap();
+        prev = header;
+    }
+}
+
+/// Client sending the requests over the current connection of the [`SubscriptionManager`].
+struct ManagedClient(SubscriptionManager);
+
+#[async_trait]
+impl ClientT for ManagedClient {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), ClientError>
+    where
+        Params: ToRpcParams + Send,
+    {
+        self.0.client().await.notification(method, params).await
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, ClientError>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        self.0.client().await.request(method, params).await
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, ClientError>
+    where
+        R: DeserializeOwned + fmt::Debug + 'a,
+    {
+        self.0.client().await.batch_request(batch).await
+    }
+}
+
+#[async_trait]
+impl SubscriptionClientT for ManagedClient {
+    async fn subscribe<'a, N, Params>(
+        &self,
+        subscribe_method: &'a str,
+        params: Params,
+        unsubscribe_method: &'a str,
+    ) -> Result<Subscription<N>, ClientError>
+    where
+        Params: ToRpcParams + Send,
+        N: DeserializeOwned,
+    {
+        self.0
+            .client()
+            .await
+            .subscribe(subscribe_method, params, unsubscribe_method)
+            .await
+    }
+
+    async fn subscribe_to_method<'a, N>(
+        &self,
+        method: &'a str,
+    ) -> Result<Subscription<N>, ClientError>
+    where
+        N: DeserializeOwned,
+    {
+        self.0.client().await.subscribe_to_method(method).await
+    }
+}
+
+/// Handler of a node which pruned the headers below the `tail`.
+struct PrunedStoreMock {
+    tail: u64,
+    head: ExtendedHeader,
+    probes: AtomicU64,
+}
+
+#[async_trait]
+impl MockHandler for PrunedStoreMock {
+    async fn request(&self, method: &str, params: Value) -> Result<Value, ClientError> {
+        match method {
+            "header.LocalHead" => Ok(serde_json::to_value(&self.head)?),
+            "header.GetByHeight" => {
+                self.probes.fetch_add(1, Ordering::SeqCst);
+
+                if params[0].as_u64().unwrap() < self.tail {
+                    let msg = "header: not found";
+                    return Err(ErrorObjectOwned::owned(-32000, msg, None::<()>).into());
+                }
+
+                // content of the available headers doesn't matter for the tests
+                Ok(serde_json::to_value(&self.head)?)
+            }
+            method => unreachable!("unexpected request: {method}"),
+        }
+    }
+}
@@ -2,6 +2,7 @@
 use crate::utils::client::{new_test_client, AuthLevel};
 use celestia_rpc::prelude::*;
 use celestia_types::p2p;
+use jsonrpsee::core::client::Error;
 use libp2p::{identity, PeerId};
 use tokio::time::{sleep, Duration};
 
@@ -13,6 +14,32 @@ async fn info_test() {
     client.p2p_info().await.expect("Failed to get node info");
 }
 
+#[tokio::test]
+async fn info_resolved_test() {
+    let client = new_test_client(AuthLevel::Admin).await.unwrap();
+    let info = client.p2p_info().await.unwrap();
+    let resolved = client.p2p_info_resolved().await.unwrap();
+
+    assert_eq!(resolved.id, info.id);
+    assert!(!resolved.addrs.is_empty());
+    assert!(resolved.addrs.len() <= info.addrs.len());
+
+    for (i, addr) in resolved.addrs.iter().enumerate() {
+        assert_eq!(p2p::addr::require_peer_id(addr).unwrap(), info.id.0);
+        assert!(!resolved.addrs[i + 1..].contains(addr));
+    }
+
+    // bridge in devnet doesn't advertise any dnsaddr
+    let resolved_with = client
+        .p2p_info_resolved_with(|host| async move {
+            let e = format!("Unexpected dnsaddr resolution of {host}");
+            Err::<Vec<p2p::Multiaddr>, _>(Error::Custom(e))
+        })
+        .await
+        .unwrap();
+    assert_eq!(resolved_with.addrs, resolved.addrs);
+}
+
 #[tokio::test]
 async fn add_remove_peer_test() {
     // add and then remove a peer, testing outputs from `p2p.Peers` and `p2p.Connectedness`
@@ -0,0 +1,104 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use celestia_rpc::{Reorg, ReorgDetector};
+use celestia_types::test_utils::ExtendedHeaderGenerator;
+use celestia_types::ExtendedHeader;
+
+/// Generate chains of 10 and 11 headers, forking at height 6.
+fn reorged_chains() -> (Vec<ExtendedHeader>, Vec<ExtendedHeader>) {
+    let mut gen = ExtendedHeaderGenerator::new();
+    let chain_a = gen.next_many(10);
+
+    let header6 = gen.another_of(&chain_a[5]);
+    let mut chain_b = chain_a[..5].to_vec();
+    chain_b.push(header6.clone());
+    chain_b.extend(gen.next_many_of(&header6, 5));
+
+    (chain_a, chain_b)
+}
+
+#[test]
+fn detect_reorg() {
+    let (chain_a, chain_b) = reorged_chains();
+    let mut detector = ReorgDetector::new();
+
+    for header in &chain_a {
+        assert_eq!(detector.observe(header), None);
+    }
+
+    // the same headers again are not a reorg
+    for header in &chain_a {
+        assert_eq!(detector.observe(header), None);
+    }
+
+    let reorg = detector.observe(&chain_b[5]).unwrap();
+    assert_eq!(
+        reorg,
+        Reorg {
+            from_height: 6,
+            old_hash: chain_a[5].hash(),
+            new_hash: chain_b[5].hash(),
+            fork_point: Some(5),
+        }
+    );
+
+    for header in &chain_b[6..] {
+        assert_eq!(detector.observe(header), None);
+    }
+
+    assert_eq!(detector.hash(5), Some(chain_a[4].hash()));
+    assert_eq!(detector.hash(10), Some(chain_b[9].hash()));
+}
+
+#[test]
+fn detect_reorg_from_new_tip() {
+    let (chain_a, chain_b) = reorged_chains();
+
+    let mut detector = ReorgDetector::new();
+
+    for header in &chain_a {
+        assert_eq!(detector.observe(header), None);
+    }
+
+    // only parent of the new tip is known to be replaced
+    let reorg = detector.observe(&chain_b[10]).unwrap();
+    assert_eq!(
+        reorg,
+        Reorg {
+            from_height: 10,
+            old_hash: chain_a[9].hash(),
+            new_hash: chain_b[9].hash(),
+            fork_point: None,
+        }
+    );
+
+    // backfilling the new chain finds the fork point
+    let reorg = detector.observe(&chain_b[5]).unwrap();
+    assert_eq!(reorg.from_height, 6);
+    assert_eq!(reorg.fork_point, Some(5));
+
+    for header in &chain_b[6..] {
+        assert_eq!(detector.observe(header), None);
+    }
+}
+
+#[test]
+fn forget_oldest_heights() {
+    let (chain_a, chain_b) = reorged_chains();
+    let mut detector = ReorgDetector::with_tracked_heights(3);
+
+    for header in &chain_a {
+        detector.observe(header);
+    }
+
+    assert_eq!(detector.hash(7), None);
+    assert_eq!(detector.hash(8), Some(chain_a[7].hash()));
+
+    // height 6 is not tracked anymore, so the reorg is noticed only at height 8
+    assert_eq!(detector.observe(&chain_b[5]), None);
+    assert_eq!(detector.observe(&chain_b[6]), None);
+
+    let reorg = detector.observe(&chain_b[7]).unwrap();
+    assert_eq!(reorg.from_height, 8);
+    assert_eq!(reorg.fork_point, None);
+}
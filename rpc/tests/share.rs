@@ -1,18 +1,44 @@
 #![cfg(not(target_arch = "wasm32"))]
 
+use std::pin::pin;
+use std::time::Duration;
+
+use async_trait::async_trait;
 use celestia_rpc::prelude::*;
+use celestia_rpc::{Client, Error};
 use celestia_types::consts::appconsts::AppVersion;
 use celestia_types::nmt::{Namespace, NamespacedSha2Hasher};
-use celestia_types::Blob;
+use celestia_types::test_utils::ExtendedHeaderGenerator;
+use celestia_types::{Blob, DataAvailabilityHeader, ExtendedHeader, Share};
+use futures::StreamExt;
+use jsonrpsee::core::ClientError;
+use jsonrpsee::types::ErrorObjectOwned;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::Value;
 
 pub mod utils;
 
-use crate::utils::client::{blob_submit, new_test_client, AuthLevel};
+use crate::utils::client::{
+    blob_submit, new_test_client, new_test_client_with_config, wait_for_sync, AuthLevel,
+    TestClientConfig,
+};
+use crate::utils::mock::{MockClient, MockHandler};
 use crate::utils::{random_bytes, random_ns, random_ns_range};
 
 #[tokio::test]
 async fn get_share() {
-    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let config = TestClientConfig {
+        request_timeout: Duration::from_secs(10),
+        ..Default::default()
+    };
+    let client = new_test_client_with_config(AuthLevel::Write, config)
+        .await
+        .unwrap();
+    wait_for_sync(&client, Duration::from_secs(30))
+        .await
+        .unwrap();
+
     let header = client.header_network_head().await.unwrap();
     let square_width = header.dah.square_width() as u64;
 
@@ -46,11 +72,7 @@ async fn get_shares_by_namespace() {
         .await
         .unwrap();
 
-    let reconstructed = Blob::reconstruct_all(
-        ns_shares.rows.iter().flat_map(|row| row.shares.iter()),
-        AppVersion::V2,
-    )
-    .unwrap();
+    let reconstructed = ns_shares.into_blobs(namespace, AppVersion::V2).unwrap();
 
     assert_eq!(reconstructed, blobs);
 }
@@ -104,6 +126,57 @@ async fn get_shares_range() {
     }
 }
 
+#[tokio::test]
+async fn get_namespace_range() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespace = random_ns();
+    let blob = Blob::new(namespace, random_bytes(1024), AppVersion::V2).unwrap();
+    let commitment = blob.commitment;
+
+    let submitted_height = blob_submit(&client, &[blob]).await.unwrap();
+
+    let header = client.header_get_by_height(submitted_height).await.unwrap();
+    let blob_on_chain = client
+        .blob_get(submitted_height, namespace, commitment)
+        .await
+        .unwrap();
+    let index = blob_on_chain.index.unwrap();
+    let shares = blob_on_chain.to_shares().unwrap();
+
+    let ods_width = u64::from(header.dah.square_width() / 2);
+    let row = index / ods_width;
+    // the blob may span multiple rows, only the ones in the first row are expected
+    let in_row = ((row + 1) * ods_width - index).min(shares.len() as u64) as usize;
+
+    let response = client
+        .share_get_namespace_range(&header, namespace, row as u16, row as u16 + 1)
+        .await
+        .unwrap()
+        .unwrap();
+
+    response.verify(&header).unwrap();
+    assert_eq!(response.proof.namespace_id, namespace);
+    assert_eq!(response.shares, shares[..in_row]);
+
+    // namespace not present in the window
+    let response = client
+        .share_get_namespace_range(&header, random_ns(), row as u16, row as u16 + 1)
+        .await
+        .unwrap();
+    assert!(response.is_none());
+
+    // window outside of the original data square
+    let ods_width = ods_width as u16;
+    client
+        .share_get_namespace_range(&header, namespace, 0, ods_width + 1)
+        .await
+        .unwrap_err();
+    client
+        .share_get_namespace_range(&header, namespace, row as u16, row as u16)
+        .await
+        .unwrap_err();
+}
+
 #[tokio::test]
 async fn get_shares_range_not_existing() {
     let client = new_test_client(AuthLevel::Write).await.unwrap();
@@ -245,3 +318,196 @@ async fn get_eds() {
         assert_eq!(column_root, header.dah.column_root(i).unwrap());
     }
 }
+
+#[tokio::test]
+async fn get_eds_streamed() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let blob = Blob::new(random_ns(), random_bytes(4096), AppVersion::V2).unwrap();
+
+    let submitted_height = blob_submit(&client, &[blob]).await.unwrap();
+
+    let header = client.header_get_by_height(submitted_height).await.unwrap();
+    let eds = client.share_get_eds(&header).await.unwrap();
+
+    let rows: Vec<_> = client
+        .share_get_eds_streamed(&header)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(rows.len(), usize::from(header.dah.square_width() / 2));
+
+    for (expected_index, (index, shares)) in rows.into_iter().enumerate() {
+        assert_eq!(usize::from(index), expected_index);
+        assert_eq!(shares, eds.row(index).unwrap());
+    }
+}
+
+#[tokio::test]
+async fn get_eds_streamed_invalid_row() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let blob = Blob::new(random_ns(), random_bytes(4096), AppVersion::V2).unwrap();
+
+    let submitted_height = blob_submit(&client, &[blob]).await.unwrap();
+
+    let mut header = client.header_get_by_height(submitted_height).await.unwrap();
+
+    // Replace the root of the first row with the root of the last one
+    let mut row_roots = header.dah.row_roots().to_vec();
+    row_roots[0] = header.dah.row_root(header.dah.square_width() - 1).unwrap();
+    header.dah =
+        DataAvailabilityHeader::new_unchecked(row_roots, header.dah.column_roots().to_vec());
+
+    let mut rows = pin!(client.share_get_eds_streamed(&header));
+
+    rows.next().await.unwrap().unwrap_err();
+    assert!(rows.next().await.is_none());
+}
+
+#[tokio::test]
+async fn get_namespaces() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let namespace = random_ns();
+    let blob = Blob::new(namespace, random_bytes(1024), AppVersion::V2).unwrap();
+
+    let submitted_height = blob_submit(&client, &[blob]).await.unwrap();
+
+    let header = client.header_get_by_height(submitted_height).await.unwrap();
+    let namespaces = client.share_get_namespaces(&header).await.unwrap();
+
+    assert!(namespaces.contains(&namespace));
+    assert!(namespaces.windows(2).all(|w| w[0] < w[1]));
+    assert!(namespaces.iter().all(|ns| !ns.is_reserved()));
+}
+
+#[tokio::test]
+async fn shares_available_sampled() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let blob = Blob::new(random_ns(), random_bytes(4096), AppVersion::V2).unwrap();
+    let submitted_height = blob_submit(&client, &[blob]).await.unwrap();
+    let header = client.header_get_by_height(submitted_height).await.unwrap();
+
+    client
+        .share_shares_available_sampled_with_rng(&header, 8, StdRng::seed_from_u64(7))
+        .await
+        .unwrap();
+    client
+        .share_shares_available_sampled(&header, 8)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn shares_available_tampered_share() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let blob = Blob::new(random_ns(), random_bytes(1024), AppVersion::V2).unwrap();
+    let submitted_height = blob_submit(&client, &[blob]).await.unwrap();
+    let header = client.header_get_by_height(submitted_height).await.unwrap();
+
+    let tampering = MockClient(TamperedSharesMock {
+        client,
+        header: header.clone(),
+    });
+
+    tampering
+        .share_shares_available_sampled_with_rng(&header, 8, StdRng::seed_from_u64(7))
+        .await
+        .unwrap_err();
+    tampering
+        .share_shares_available_sampled(&header, 8)
+        .await
+        .unwrap_err();
+}
+
+#[tokio::test]
+async fn outside_sampling_window_error() {
+    let pruned = ExtendedHeaderGenerator::new_from_height(100).next();
+    let head = ExtendedHeaderGenerator::new_from_height(1000).next();
+    let client = MockClient(PrunedSharesMock { tail: 321, head });
+
+    let err = client.share_get_eds(&pruned).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::OutsideSamplingWindow {
+                height: 100,
+                window_tail: 321
+            }
+        ),
+        "{err}"
+    );
+
+    let err = client.header_get_by_height(320).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::OutsideSamplingWindow {
+                height: 320,
+                window_tail: 321
+            }
+        ),
+        "{err}"
+    );
+
+    // not found above the head isn't related to the sampling window
+    let err = client.header_get_by_height(1001).await.unwrap_err();
+    assert!(matches!(err, Error::JsonRpc(_)), "{err}");
+}
+
+/// Node which pruned everything below `tail`.
+struct PrunedSharesMock {
+    tail: u64,
+    head: ExtendedHeader,
+}
+
+#[async_trait]
+impl MockHandler for PrunedSharesMock {
+    async fn request(&self, method: &str, params: Value) -> Result<Value, ClientError> {
+        let height = params[0].as_u64().unwrap_or_default();
+        let available = self.tail..=self.head.height().value();
+
+        match method {
+            "header.LocalHead" => Ok(serde_json::to_value(&self.head)?),
+            "header.GetByHeight" if !available.contains(&height) => {
+                let msg = "header: not found";
+                Err(ErrorObjectOwned::owned(-32000, msg, None::<()>).into())
+            }
+            // content of the available headers doesn't matter for the tests
+            "header.GetByHeight" => Ok(serde_json::to_value(&self.head)?),
+            "share.GetEDS" if height < self.tail => {
+                let msg = "given height is outside of the sampling window";
+                Err(ErrorObjectOwned::owned(1, msg, None::<()>).into())
+            }
+            method => unreachable!("unexpected request: {method}"),
+        }
+    }
+}
+
+/// Node which flips the last byte of the first share in each returned range.
+struct TamperedSharesMock {
+    client: Client,
+    header: ExtendedHeader,
+}
+
+#[async_trait]
+impl MockHandler for TamperedSharesMock {
+    async fn request(&self, method: &str, params: Value) -> Result<Value, ClientError> {
+        match method {
+            "share.GetRange" => {
+                let (_, start, end): (u64, u64, u64) = serde_json::from_value(params)?;
+                let mut response = self
+                    .client
+                    .share_get_range(&self.header, start, end)
+                    .await
+                    .map_err(|e| ClientError::Custom(e.to_string()))?;
+
+                let mut data = response.shares[0].data().to_vec();
+                *data.last_mut().unwrap() ^= 0xff;
+                response.shares[0] = Share::from_raw(&data).unwrap();
+
+                Ok(serde_json::to_value(&response)?)
+            }
+            method => unreachable!("unexpected request: {method}"),
+        }
+    }
+}
@@ -3,6 +3,8 @@
 use crate::utils::{random_bytes, random_ns};
 use celestia_rpc::prelude::*;
 use celestia_rpc::TxConfig;
+use celestia_types::blob::{BYTES_PER_BLOB_INFO, PFB_GAS_FIXED_COST};
+use celestia_types::consts::appconsts::{self, SHARE_SIZE};
 use celestia_types::{AppVersion, Blob};
 
 pub mod utils;
@@ -53,3 +55,32 @@ async fn submit_pay_for_blob() {
     received_blob.validate(AppVersion::V2).unwrap();
     assert_eq!(received_blob.data, blob.data);
 }
+
+#[tokio::test]
+async fn estimate_gas_for_blobs() {
+    let client = new_test_client(AuthLevel::Write).await.unwrap();
+    let blob = Blob::new(random_ns(), random_bytes(1024), AppVersion::V2).unwrap();
+
+    let estimate = client
+        .state_estimate_gas_for_blobs(&[blob.clone()])
+        .await
+        .unwrap();
+    // 1024 bytes of data span 3 shares
+    let blob_bytes = 3 * SHARE_SIZE as u64;
+    assert_eq!(
+        estimate,
+        blob_bytes * appconsts::gas_per_blob_byte(AppVersion::V2)
+            + appconsts::tx_size_cost_per_byte(AppVersion::V2) * BYTES_PER_BLOB_INFO
+            + PFB_GAS_FIXED_COST
+    );
+
+    let tx_response = client
+        .state_submit_pay_for_blob(&[blob.into()], TxConfig::default())
+        .await
+        .unwrap();
+    let gas_used = tx_response.gas_used as u64;
+
+    // estimation is conservative, but shouldn't be too far off
+    assert!(estimate >= gas_used);
+    assert!(estimate - gas_used <= PFB_GAS_FIXED_COST / 2);
+}
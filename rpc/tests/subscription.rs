@@ -0,0 +1,63 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use celestia_rpc::prelude::*;
+
+pub mod utils;
+
+use crate::utils::client::{new_test_subscription_manager, AuthLevel};
+use crate::utils::random_ns;
+
+#[tokio::test]
+async fn resubscribe_on_reconnect() {
+    let (manager, proxy) = new_test_subscription_manager(AuthLevel::Read)
+        .await
+        .unwrap();
+    let namespace = random_ns();
+
+    let mut headers = manager
+        .subscribe(|client| async move { client.header_subscribe().await })
+        .await
+        .unwrap();
+    let mut blobs = manager
+        .subscribe(move |client| async move { client.blob_subscribe(namespace).await })
+        .await
+        .unwrap();
+
+    let header = headers.next().await.unwrap().unwrap();
+    let blobs_at_height = blobs.next().await.unwrap().unwrap();
+
+    // break the connection while both subscriptions are active
+    proxy.drop_connections();
+
+    // items already in flight may still be delivered from the old connection,
+    // the first subscription to notice the lost one re-creates both of them
+    let next_header = loop {
+        let header = headers.next().await.unwrap().unwrap();
+        if headers.health().resubscriptions == 1 {
+            break header;
+        }
+    };
+    assert!(next_header.height() > header.height());
+
+    // heights after the reconnect can only come from the new subscription
+    loop {
+        let at_height = blobs.next().await.unwrap().unwrap();
+        assert!(at_height.height > blobs_at_height.height);
+        if at_height.height >= next_header.height() {
+            break;
+        }
+    }
+
+    for health in [headers.health(), blobs.health()] {
+        assert!(health.received >= 2);
+        assert_eq!(health.resubscriptions, 1);
+        assert!(health.lag().is_some());
+    }
+}
+
+#[tokio::test]
+async fn http_not_supported() {
+    celestia_rpc::SubscriptionManager::new("http://localhost:26658", None)
+        .await
+        .unwrap_err();
+}
@@ -0,0 +1,9 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use celestia_rpc::Client;
+
+#[tokio::test]
+async fn https_client_with_tls_backend() {
+    // building the http client doesn't connect, but fails if TLS isn't compiled in
+    Client::new("https://127.0.0.1:1", None).await.unwrap();
+}
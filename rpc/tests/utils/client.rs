@@ -1,16 +1,33 @@
 use std::env;
+use std::future::Future;
 use std::sync::OnceLock;
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use celestia_rpc::auth::bearer_headers;
+use celestia_rpc::blob::SubmitState;
 use celestia_rpc::prelude::*;
-use celestia_rpc::{Client, TxConfig};
-use celestia_types::Blob;
+use celestia_rpc::{Client, SubscriptionManager, TxConfig};
+use celestia_types::{Blob, ExtendedHeader};
+use futures::StreamExt;
+use http::Uri;
 use jsonrpsee::core::client::SubscriptionClientT;
 use jsonrpsee::core::ClientError;
+use jsonrpsee::http_client::HttpClientBuilder;
+use jsonrpsee::ws_client::WsClientBuilder;
 use tokio::sync::{Mutex, MutexGuard};
 
+use crate::utils::proxy::TcpProxy;
+
 const CELESTIA_RPC_URL: &str = "ws://localhost:36658";
 
+/// Default timeout of a single request made by the test client.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+/// Default maximum number of concurrent requests of the test client.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 256;
+/// Default timeout for waiting on the node in test helpers.
+pub const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
 async fn write_lock() -> MutexGuard<'static, ()> {
     static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
     LOCK.get_or_init(|| Mutex::new(())).lock().await
@@ -37,19 +54,117 @@ fn env_or(var_name: &str, or_value: &str) -> String {
     env::var(var_name).unwrap_or_else(|_| or_value.to_owned())
 }
 
+/// Configuration of the test client.
+#[derive(Debug, Clone, Copy)]
+pub struct TestClientConfig {
+    /// Timeout of a single request.
+    pub request_timeout: Duration,
+    /// Maximum number of concurrent requests.
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for TestClientConfig {
+    fn default() -> Self {
+        TestClientConfig {
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+        }
+    }
+}
+
 pub async fn new_test_client(auth_level: AuthLevel) -> Result<Client> {
+    new_test_client_with_config(auth_level, TestClientConfig::default()).await
+}
+
+pub async fn new_test_client_with_config(
+    auth_level: AuthLevel,
+    config: TestClientConfig,
+) -> Result<Client> {
     let _ = dotenvy::dotenv();
     let token = token_from_env(auth_level)?;
     let url = env_or("CELESTIA_RPC_URL", CELESTIA_RPC_URL);
 
-    let client = Client::new(&url, token.as_deref()).await?;
+    let headers = bearer_headers(token.as_deref())?;
+
+    // Tests may request whole EDS, so don't limit response size.
+    let client = if url.starts_with("http") {
+        Client::Http(
+            HttpClientBuilder::default()
+                .max_response_size(u32::MAX)
+                .request_timeout(config.request_timeout)
+                .max_concurrent_requests(config.max_concurrent_requests)
+                .set_headers(headers)
+                .build(&url)?,
+        )
+    } else {
+        Client::Ws(
+            WsClientBuilder::default()
+                .max_response_size(u32::MAX)
+                .request_timeout(config.request_timeout)
+                .max_concurrent_requests(config.max_concurrent_requests)
+                .set_headers(headers)
+                .build(&url)
+                .await?,
+        )
+    };
 
     // minimum 2 blocks
-    client.header_wait_for_height(2).await?;
+    wait_for_height(&client, 2, DEFAULT_WAIT_TIMEOUT).await?;
 
     Ok(client)
 }
 
+/// Create a subscription manager connected to the node through a [`TcpProxy`].
+///
+/// The proxy allows tests to break the connection of the manager.
+pub async fn new_test_subscription_manager(
+    auth_level: AuthLevel,
+) -> Result<(SubscriptionManager, TcpProxy)> {
+    let _ = dotenvy::dotenv();
+    let token = token_from_env(auth_level)?;
+    let url: Uri = env_or("CELESTIA_RPC_URL", CELESTIA_RPC_URL).parse()?;
+
+    let upstream = url.authority().context("Missing node address")?;
+    let proxy = TcpProxy::new(upstream.as_str()).await?;
+
+    let scheme = url.scheme_str().unwrap_or("ws");
+    let proxied_url = format!("{scheme}://{}", proxy.local_addr());
+    let manager = SubscriptionManager::new(&proxied_url, token.as_deref()).await?;
+
+    Ok((manager, proxy))
+}
+
+/// Wait until the node is synced to the network head, failing after `timeout`.
+pub async fn wait_for_sync<C>(client: &C, timeout: Duration) -> Result<()>
+where
+    C: SubscriptionClientT + Sync,
+{
+    with_timeout(timeout, "sync", client.header_sync_wait()).await
+}
+
+/// Wait for the header at `height`, failing after `timeout`.
+pub async fn wait_for_height<C>(
+    client: &C,
+    height: u64,
+    timeout: Duration,
+) -> Result<ExtendedHeader>
+where
+    C: SubscriptionClientT + Sync,
+{
+    let what = format!("height {height}");
+    with_timeout(timeout, &what, client.header_wait_for_height(height)).await
+}
+
+async fn with_timeout<T, F>(timeout: Duration, what: &str, fut: F) -> Result<T>
+where
+    F: Future<Output = Result<T, ClientError>>,
+{
+    match tokio::time::timeout(timeout, fut).await {
+        Ok(res) => res.with_context(|| format!("Waiting for {what} failed")),
+        Err(_) => bail!("Waiting for {what} timed out after {timeout:?}"),
+    }
+}
+
 pub async fn blob_submit<C>(client: &C, blobs: &[Blob]) -> Result<u64, ClientError>
 where
     C: SubscriptionClientT + Sync,
@@ -57,3 +172,24 @@ where
     let _guard = write_lock().await;
     client.blob_submit(blobs, TxConfig::default()).await
 }
+
+pub async fn blob_submit_and_verify<C>(client: &C, blobs: &[Blob]) -> Result<u64, ClientError>
+where
+    C: SubscriptionClientT + Sync,
+{
+    let _guard = write_lock().await;
+    client
+        .blob_submit_and_verify(blobs, TxConfig::default())
+        .await
+}
+
+pub async fn blob_submit_tracked<C>(client: &C, blobs: &[Blob]) -> Vec<SubmitState>
+where
+    C: SubscriptionClientT + Sync,
+{
+    let _guard = write_lock().await;
+    client
+        .blob_submit_tracked(blobs, TxConfig::default())
+        .collect()
+        .await
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+use std::ops::Deref;
+
+use async_trait::async_trait;
+use jsonrpsee::core::client::{BatchResponse, ClientT, Subscription, SubscriptionClientT};
+use jsonrpsee::core::params::BatchRequestBuilder;
+use jsonrpsee::core::traits::ToRpcParams;
+use jsonrpsee::core::ClientError;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Handler of the requests sent to the [`MockClient`].
+#[async_trait]
+pub trait MockHandler: Send + Sync {
+    /// Respond to the request with the given method and json encoded params.
+    async fn request(&self, method: &str, params: Value) -> Result<Value, ClientError>;
+}
+
+/// Client answering only plain requests, using the provided [`MockHandler`].
+#[derive(Debug)]
+pub struct MockClient<H>(pub H);
+
+impl<H> Deref for MockClient<H> {
+    type Target = H;
+
+    fn deref(&self) -> &H {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<H> ClientT for MockClient<H>
+where
+    H: MockHandler,
+{
+    async fn notification<Params>(&self, _method: &str, _params: Params) -> Result<(), ClientError>
+    where
+        Params: ToRpcParams + Send,
+    {
+        unreachable!("notifications are not supported by the mock client")
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, ClientError>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = match params.to_rpc_params()? {
+            Some(params) => serde_json::from_str(params.get())?,
+            None => Value::Null,
+        };
+        let response = self.0.request(method, params).await?;
+
+        Ok(serde_json::from_value(response)?)
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        _batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, ClientError>
+    where
+        R: DeserializeOwned + fmt::Debug + 'a,
+    {
+        unreachable!("batch requests are not supported by the mock client")
+    }
+}
+
+#[async_trait]
+impl<H> SubscriptionClientT for MockClient<H>
+where
+    H: MockHandler,
+{
+    async fn subscribe<'a, N, Params>(
+        &self,
+        _subscribe_method: &'a str,
+        _params: Params,
+        _unsubscribe_method: &'a str,
+    ) -> Result<Subscription<N>, ClientError>
+    where
+        Params: ToRpcParams + Send,
+        N: DeserializeOwned,
+    {
+        unreachable!("subscriptions are not supported by the mock client")
+    }
+
+    async fn subscribe_to_method<'a, N>(
+        &self,
+        _method: &'a str,
+    ) -> Result<Subscription<N>, ClientError>
+    where
+        N: DeserializeOwned,
+    {
+        unreachable!("subscriptions are not supported by the mock client")
+    }
+}
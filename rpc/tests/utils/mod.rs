@@ -4,6 +4,8 @@ use celestia_types::nmt::{Namespace, NS_ID_V0_SIZE};
 use rand::{Rng, RngCore};
 
 pub mod client;
+pub mod mock;
+pub mod proxy;
 #[cfg(feature = "p2p")]
 pub mod tiny_node;
 
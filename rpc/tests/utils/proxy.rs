@@ -0,0 +1,67 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use tokio::io::copy_bidirectional;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::AbortHandle;
+
+/// Tcp proxy in front of the node, allowing tests to break the connections.
+pub struct TcpProxy {
+    local_addr: SocketAddr,
+    connections: Arc<Mutex<Vec<AbortHandle>>>,
+    accept_task: AbortHandle,
+}
+
+impl TcpProxy {
+    /// Start proxying connections to the `upstream` address.
+    pub async fn new(upstream: &str) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let local_addr = listener.local_addr()?;
+        let connections = Arc::new(Mutex::new(Vec::new()));
+
+        let upstream = upstream.to_owned();
+        let accept_connections = connections.clone();
+        let accept_task = tokio::spawn(async move {
+            while let Ok((mut inbound, _)) = listener.accept().await {
+                let upstream = upstream.clone();
+                let connection = tokio::spawn(async move {
+                    if let Ok(mut outbound) = TcpStream::connect(&upstream).await {
+                        let _ = copy_bidirectional(&mut inbound, &mut outbound).await;
+                    }
+                });
+
+                accept_connections
+                    .lock()
+                    .unwrap()
+                    .push(connection.abort_handle());
+            }
+        })
+        .abort_handle();
+
+        Ok(TcpProxy {
+            local_addr,
+            connections,
+            accept_task,
+        })
+    }
+
+    /// Address the proxy listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Close all the currently proxied connections, new ones are still accepted.
+    pub fn drop_connections(&self) {
+        for connection in self.connections.lock().unwrap().drain(..) {
+            connection.abort();
+        }
+    }
+}
+
+impl Drop for TcpProxy {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        self.drop_connections();
+    }
+}
@@ -1,8 +1,10 @@
 //! Types related to creation and submission of blobs.
 
+use std::collections::BTreeMap;
 use std::iter;
 
 use serde::{Deserialize, Serialize};
+use tendermint::account::Id;
 
 mod commitment;
 mod msg_pay_for_blobs;
@@ -10,6 +12,7 @@ mod msg_pay_for_blobs;
 use crate::consts::appconsts;
 use crate::consts::appconsts::{subtree_root_threshold, AppVersion};
 use crate::nmt::Namespace;
+use crate::state::{AccAddress, Address, AddressTrait};
 use crate::{bail_validation, Error, Result, Share};
 
 pub use self::commitment::Commitment;
@@ -20,6 +23,30 @@ pub use celestia_proto::proto::blob::v1::BlobTx as RawBlobTx;
 #[cfg(all(feature = "wasm-bindgen", target_arch = "wasm32"))]
 use wasm_bindgen::prelude::*;
 
+// source https://github.com/celestiaorg/celestia-app/blob/v3.0.2/x/blob/types/payforblob.go#L21
+// PFBGasFixedCost is a rough estimate for the "fixed cost" in the gas cost
+// formula: gas cost = gas per byte * bytes per share * shares occupied by
+// blob + "fixed cost". In this context, "fixed cost" accounts for the gas
+// consumed by operations outside the blob's GasToConsume function (i.e.
+// signature verification, tx size, read access to accounts).
+//
+// Since the gas cost of these operations is not easy to calculate, linear
+// regression was performed on a set of observed data points to derive an
+// approximate formula for gas cost. Assuming gas per byte = 8 and bytes per
+// share = 512, we can solve for "fixed cost" and arrive at 65,000. gas cost
+// = 8 * 512 * number of shares occupied by the blob + 65,000 has a
+// correlation coefficient of 0.996. To be conservative, we round up "fixed
+// cost" to 75,000 because the first tx always takes up 10,000 more gas than
+// subsequent txs.
+/// Rough estimate of the gas consumed by the [`MsgPayForBlobs`] transaction
+/// besides the blobs themselves.
+pub const PFB_GAS_FIXED_COST: u64 = 75000;
+
+// BytesPerBlobInfo is a rough estimation for the amount of extra bytes in
+// information a blob adds to the size of the underlying transaction.
+/// Rough estimate of the amount of bytes each blob adds to the size of the transaction.
+pub const BYTES_PER_BLOB_INFO: u64 = 70;
+
 /// Arbitrary data that can be stored in the network within certain [`Namespace`].
 // NOTE: We don't use the `serde(try_from)` pattern for this type
 // becase JSON representation needs to have `commitment` field but
@@ -41,6 +68,15 @@ pub struct Blob {
     pub share_version: u8,
     /// A [`Commitment`] computed from the [`Blob`]s data.
     pub commitment: Commitment,
+    /// Address of the account which signed the blob, present only in share version one.
+    ///
+    /// Set with [`Blob::new_with_signer`] and read with [`Blob::signer`].
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "signer_serde"
+    )]
+    signer: Option<AccAddress>,
     /// Index of the blob's first share in the EDS. Only set for blobs retrieved from chain.
     // note: celestia supports deserializing blobs without index, so we should too
     #[serde(default, with = "index_serde")]
@@ -84,37 +120,112 @@ impl Blob {
     /// );
     /// ```
     pub fn new(namespace: Namespace, data: Vec<u8>, app_version: AppVersion) -> Result<Blob> {
-        let subtree_root_threshold = subtree_root_threshold(app_version);
-        let commitment = Commitment::from_blob(namespace, &data[..], 0, subtree_root_threshold)?;
+        Blob::with_share_version(
+            namespace,
+            data,
+            appconsts::SHARE_VERSION_ZERO,
+            None,
+            app_version,
+        )
+    }
 
-        Ok(Blob {
+    /// Create a new blob with the given data within the [`Namespace`], signed by the `signer`.
+    ///
+    /// Such blobs use [`SHARE_VERSION_ONE`], which stores the signer in the first share,
+    /// so it is also a part of the [`Commitment`]. They are supported since [`AppVersion::V3`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the `app_version` is lower than
+    /// [`AppVersion::V3`] and propagates any error from the [`Commitment`] creation.
+    ///
+    /// [`SHARE_VERSION_ONE`]: appconsts::SHARE_VERSION_ONE
+    pub fn new_with_signer(
+        namespace: Namespace,
+        data: Vec<u8>,
+        signer: AccAddress,
+        app_version: AppVersion,
+    ) -> Result<Blob> {
+        Blob::with_share_version(
             namespace,
             data,
-            share_version: 0,
-            commitment,
+            appconsts::SHARE_VERSION_ONE,
+            Some(signer),
+            app_version,
+        )
+    }
+
+    fn with_share_version(
+        namespace: Namespace,
+        data: Vec<u8>,
+        share_version: u8,
+        signer: Option<AccAddress>,
+        app_version: AppVersion,
+    ) -> Result<Blob> {
+        if share_version == appconsts::SHARE_VERSION_ONE && app_version < AppVersion::V3 {
+            bail_validation!("share version 1 requires app version 3 or later");
+        }
+
+        let mut blob = Blob {
+            namespace,
+            data,
+            share_version,
+            commitment: Commitment::new([0; 32]),
+            signer,
             index: None,
-        })
+        };
+        blob.commitment = blob.compute_commitment(app_version)?;
+
+        Ok(blob)
     }
 
     /// Creates a `Blob` from [`RawBlob`] and an [`AppVersion`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the signer is present in a blob with
+    /// share version zero or missing in a blob with share version one, or if share
+    /// version one is used before [`AppVersion::V3`].
     pub fn from_raw(raw: RawBlob, app_version: AppVersion) -> Result<Blob> {
-        let subtree_root_threshold = subtree_root_threshold(app_version);
-
         let namespace = Namespace::new(raw.namespace_version as u8, &raw.namespace_id)?;
-        let commitment = Commitment::from_blob(
+        let signer = if raw.signer.is_empty() {
+            None
+        } else {
+            Some(signer_from_bytes(&raw.signer)?)
+        };
+
+        Blob::with_share_version(
             namespace,
-            &raw.data[..],
+            raw.data,
             raw.share_version as u8,
-            subtree_root_threshold,
-        )?;
+            signer,
+            app_version,
+        )
+    }
 
-        Ok(Blob {
-            namespace,
-            data: raw.data,
-            share_version: raw.share_version as u8,
-            commitment,
-            index: None,
-        })
+    /// Get the address of the account which signed the blob.
+    ///
+    /// Only blobs with [`SHARE_VERSION_ONE`] have a signer.
+    ///
+    /// [`SHARE_VERSION_ONE`]: appconsts::SHARE_VERSION_ONE
+    pub fn signer(&self) -> Option<&AccAddress> {
+        self.signer.as_ref()
+    }
+
+    /// Compute the [`Commitment`] of the blob's data.
+    ///
+    /// For blobs with [`SHARE_VERSION_ONE`] the signer is stored in the first
+    /// share, so it is a part of the commitment.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the share version is unsupported or
+    /// if the signer doesn't match the share version.
+    ///
+    /// [`SHARE_VERSION_ONE`]: appconsts::SHARE_VERSION_ONE
+    pub fn compute_commitment(&self, app_version: AppVersion) -> Result<Commitment> {
+        let shares = self.to_shares()?;
+        Commitment::from_shares(self.namespace, &shares, subtree_root_threshold(app_version))
     }
 
     /// Validate [`Blob`]s data with the [`Commitment`] it has.
@@ -142,14 +253,7 @@ impl Blob {
     /// assert!(blob.validate(AppVersion::V2).is_err());
     /// ```
     pub fn validate(&self, app_version: AppVersion) -> Result<()> {
-        let subtree_root_threshold = subtree_root_threshold(app_version);
-
-        let computed_commitment = Commitment::from_blob(
-            self.namespace,
-            &self.data,
-            self.share_version,
-            subtree_root_threshold,
-        )?;
+        let computed_commitment = self.compute_commitment(app_version)?;
 
         if self.commitment != computed_commitment {
             bail_validation!("blob commitment != localy computed commitment")
@@ -184,7 +288,15 @@ impl Blob {
     /// [`Share`]: crate::share::Share
     /// [`InfoByte`]: crate::share::InfoByte
     pub fn to_shares(&self) -> Result<Vec<Share>> {
-        commitment::split_blob_to_shares(self.namespace, self.share_version, &self.data)
+        match (self.share_version, &self.signer) {
+            (appconsts::SHARE_VERSION_ONE, Some(signer)) => {
+                commitment::split_signed_blob_to_shares(self.namespace, signer, &self.data)
+            }
+            (share_version, Some(_)) => Err(Error::SignerNotSupported(share_version)),
+            (share_version, None) => {
+                commitment::split_blob_to_shares(self.namespace, share_version, &self.data)
+            }
+        }
     }
 
     /// Reconstructs a blob from shares.
@@ -226,10 +338,23 @@ impl Blob {
         }
         let share_version = first_share.info_byte().expect("non parity").version();
 
-        let shares_needed = shares_needed_for_blob(blob_len as usize);
+        let mut payload = first_share.payload().expect("non parity");
+        let signer = if share_version == appconsts::SHARE_VERSION_ONE {
+            let (signer, rest) = payload.split_at(appconsts::SIGNER_SIZE);
+            payload = rest;
+            Some(signer_from_bytes(signer)?)
+        } else {
+            None
+        };
+
+        let shares_needed = if signer.is_some() {
+            shares_needed_for_signed_blob(blob_len as usize)
+        } else {
+            shares_needed_for_blob(blob_len as usize)
+        };
         let mut data =
             Vec::with_capacity(shares_needed * appconsts::CONTINUATION_SPARSE_SHARE_CONTENT_SIZE);
-        data.extend_from_slice(first_share.payload().expect("non parity"));
+        data.extend_from_slice(payload);
 
         for _ in 1..shares_needed {
             let share = shares.next().ok_or(Error::MissingShares)?;
@@ -256,7 +381,10 @@ impl Blob {
         // remove padding
         data.truncate(blob_len as usize);
 
-        Self::new(namespace, data, app_version)
+        match signer {
+            Some(signer) => Self::new_with_signer(namespace, data, signer, app_version),
+            None => Self::new(namespace, data, app_version),
+        }
     }
 
     /// Reconstructs all the blobs from shares.
@@ -331,15 +459,81 @@ impl Blob {
     /// assert_eq!(shares_len, blob_shares.len());
     /// ```
     pub fn shares_len(&self) -> usize {
-        let Some(without_first_share) = self
-            .data
-            .len()
-            .checked_sub(appconsts::FIRST_SPARSE_SHARE_CONTENT_SIZE)
-        else {
-            return 1;
-        };
-        1 + without_first_share.div_ceil(appconsts::CONTINUATION_SPARSE_SHARE_CONTENT_SIZE)
+        if self.signer.is_some() {
+            shares_needed_for_signed_blob(self.data.len())
+        } else {
+            shares_needed_for_blob(self.data.len())
+        }
+    }
+
+    /// Group blobs by their [`Namespace`].
+    ///
+    /// The order of blobs within each namespace is preserved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use celestia_types::{AppVersion, Blob};
+    /// use celestia_types::nmt::Namespace;
+    ///
+    /// let ns1 = Namespace::new_v0(&[1]).unwrap();
+    /// let ns2 = Namespace::new_v0(&[2]).unwrap();
+    ///
+    /// let blobs = vec![
+    ///     Blob::new(ns2, b"foo".to_vec(), AppVersion::V3).unwrap(),
+    ///     Blob::new(ns1, b"bar".to_vec(), AppVersion::V3).unwrap(),
+    ///     Blob::new(ns2, b"baz".to_vec(), AppVersion::V3).unwrap(),
+    /// ];
+    ///
+    /// let grouped = Blob::group_by_namespace(blobs);
+    ///
+    /// assert_eq!(grouped[&ns1].len(), 1);
+    /// assert_eq!(grouped[&ns2][0].data, b"foo");
+    /// assert_eq!(grouped[&ns2][1].data, b"baz");
+    /// ```
+    pub fn group_by_namespace(blobs: Vec<Blob>) -> BTreeMap<Namespace, Vec<Blob>> {
+        let mut grouped: BTreeMap<Namespace, Vec<Blob>> = BTreeMap::new();
+
+        for blob in blobs {
+            grouped.entry(blob.namespace).or_default().push(blob);
+        }
+
+        grouped
     }
+
+    /// Build the [`RawMsgPayForBlobs`] paying for this blob, without any node connection.
+    ///
+    /// The message can be signed and broadcasted elsewhere. To pay for multiple blobs
+    /// in a single transaction, use [`MsgPayForBlobs::new`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the blob's data is larger than [`u32::MAX`].
+    pub fn to_pay_for_blobs_msg(&self, signer: Address) -> Result<RawMsgPayForBlobs> {
+        let msg = MsgPayForBlobs::new(std::slice::from_ref(self), signer)?;
+        Ok(msg.into())
+    }
+}
+
+/// Estimate the gas needed for [`MsgPayForBlobs`] transaction submitting given blobs.
+///
+/// Each share occupied by the blobs costs [`SHARE_SIZE`] times [`gas_per_blob_byte`],
+/// each blob adds [`BYTES_PER_BLOB_INFO`] bytes to the transaction size charged
+/// with [`tx_size_cost_per_byte`] and [`PFB_GAS_FIXED_COST`] is added on top.
+///
+/// [`SHARE_SIZE`]: appconsts::SHARE_SIZE
+/// [`gas_per_blob_byte`]: appconsts::gas_per_blob_byte
+/// [`tx_size_cost_per_byte`]: appconsts::tx_size_cost_per_byte
+pub fn estimate_gas(blobs: &[Blob], app_version: AppVersion) -> u64 {
+    let gas_per_blob_byte = appconsts::gas_per_blob_byte(app_version);
+    let tx_size_cost_per_byte = appconsts::tx_size_cost_per_byte(app_version);
+
+    let blobs_bytes =
+        blobs.iter().map(Blob::shares_len).sum::<usize>() as u64 * appconsts::SHARE_SIZE as u64;
+
+    blobs_bytes * gas_per_blob_byte
+        + (tx_size_cost_per_byte * BYTES_PER_BLOB_INFO * blobs.len() as u64)
+        + PFB_GAS_FIXED_COST
 }
 
 impl From<Blob> for RawBlob {
@@ -349,7 +543,10 @@ impl From<Blob> for RawBlob {
             namespace_version: value.namespace.version() as u32,
             data: value.data,
             share_version: value.share_version as u32,
-            signer: Vec::new(),
+            signer: value
+                .signer
+                .map(|signer| signer.as_bytes().to_vec())
+                .unwrap_or_default(),
         }
     }
 }
@@ -372,17 +569,77 @@ impl Blob {
     pub fn js_clone(&self) -> Blob {
         self.clone()
     }
+
+    /// Address of the account which signed the blob, present only in share version one.
+    #[wasm_bindgen(getter, js_name = signer)]
+    pub fn js_signer(&self) -> Option<String> {
+        self.signer.as_ref().map(|signer| signer.to_string())
+    }
+}
+
+pub(crate) fn shares_needed_for_blob(blob_len: usize) -> usize {
+    sparse_shares_needed(blob_len, appconsts::FIRST_SPARSE_SHARE_CONTENT_SIZE)
 }
 
-fn shares_needed_for_blob(blob_len: usize) -> usize {
-    let Some(without_first_share) =
-        blob_len.checked_sub(appconsts::FIRST_SPARSE_SHARE_CONTENT_SIZE)
-    else {
+/// Like [`shares_needed_for_blob`], but the signer takes a part of the first share.
+pub(crate) fn shares_needed_for_signed_blob(blob_len: usize) -> usize {
+    sparse_shares_needed(
+        blob_len,
+        appconsts::FIRST_SPARSE_SHARE_CONTENT_SIZE - appconsts::SIGNER_SIZE,
+    )
+}
+
+fn sparse_shares_needed(blob_len: usize, first_share_content_size: usize) -> usize {
+    let Some(without_first_share) = blob_len.checked_sub(first_share_content_size) else {
         return 1;
     };
     1 + without_first_share.div_ceil(appconsts::CONTINUATION_SPARSE_SHARE_CONTENT_SIZE)
 }
 
+fn signer_from_bytes(bytes: &[u8]) -> Result<AccAddress> {
+    let Ok(id) = <[u8; appconsts::SIGNER_SIZE]>::try_from(bytes) else {
+        bail_validation!("signer must be {} bytes", appconsts::SIGNER_SIZE);
+    };
+    Ok(AccAddress::new(Id::new(id)))
+}
+
+mod signer_serde {
+    use base64::prelude::*;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::state::{AccAddress, AddressTrait};
+
+    /// Serialize signer's [`AccAddress`] as base64 encoded bytes.
+    pub fn serialize<S>(value: &Option<AccAddress>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(signer) => serializer.serialize_str(&BASE64_STANDARD.encode(signer.as_bytes())),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize signer's [`AccAddress`] from base64 encoded bytes.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<AccAddress>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Some(encoded) = Option::<String>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        if encoded.is_empty() {
+            return Ok(None);
+        }
+
+        let bytes = BASE64_STANDARD.decode(encoded).map_err(D::Error::custom)?;
+        super::signer_from_bytes(&bytes)
+            .map(Some)
+            .map_err(D::Error::custom)
+    }
+}
+
 mod index_serde {
     use serde::ser::Error;
     use serde::{Deserialize, Deserializer, Serializer};
@@ -466,6 +723,146 @@ mod tests {
         .unwrap();
     }
 
+    fn random_signer() -> AccAddress {
+        AccAddress::new(Id::new(rand::random()))
+    }
+
+    #[test]
+    fn signed_blob_shares() {
+        let first_share_len = appconsts::FIRST_SPARSE_SHARE_CONTENT_SIZE - appconsts::SIGNER_SIZE;
+        let signer_offset = NS_SIZE + appconsts::SHARE_INFO_BYTES + appconsts::SEQUENCE_LEN_BYTES;
+
+        for (len, shares_len) in [(1, 1), (first_share_len, 1), (first_share_len + 1, 2)] {
+            let ns = Namespace::const_v0(rand::random());
+            let signer = random_signer();
+            let blob = Blob::new_with_signer(ns, random_bytes(len), signer.clone(), AppVersion::V3)
+                .unwrap();
+            assert_eq!(blob.share_version, appconsts::SHARE_VERSION_ONE);
+            assert_eq!(blob.signer(), Some(&signer));
+
+            let shares = blob.to_shares().unwrap();
+            assert_eq!(shares.len(), shares_len);
+            assert_eq!(blob.shares_len(), shares_len);
+
+            let info_byte = shares[0].info_byte().unwrap();
+            assert_eq!(info_byte.version(), appconsts::SHARE_VERSION_ONE);
+            assert_eq!(shares[0].sequence_length(), Some(len as u32));
+            assert_eq!(
+                &shares[0].data()[signer_offset..signer_offset + appconsts::SIGNER_SIZE],
+                signer.as_bytes()
+            );
+
+            assert_eq!(blob, Blob::reconstruct(&shares, AppVersion::V3).unwrap());
+        }
+    }
+
+    #[test]
+    fn signed_blob_commitment() {
+        let ns = Namespace::const_v0(rand::random());
+        let data = random_bytes(1024);
+        let signer = random_signer();
+
+        let unsigned = Blob::new(ns, data.clone(), AppVersion::V3).unwrap();
+        let signed = Blob::new_with_signer(ns, data.clone(), signer, AppVersion::V3).unwrap();
+        assert_ne!(unsigned.commitment, signed.commitment);
+        signed.validate(AppVersion::V3).unwrap();
+
+        // signer is a part of the commitment
+        let other = Blob::new_with_signer(ns, data, random_signer(), AppVersion::V3).unwrap();
+        assert_ne!(signed.commitment, other.commitment);
+
+        let raw = RawBlob::from(signed.clone());
+        assert_eq!(raw.signer, signed.signer().unwrap().as_bytes());
+        assert_eq!(Blob::from_raw(raw, AppVersion::V3).unwrap(), signed);
+
+        let json = serde_json::to_string(&signed).unwrap();
+        assert_eq!(serde_json::from_str::<Blob>(&json).unwrap(), signed);
+    }
+
+    #[test]
+    fn signer_share_version_mismatch() {
+        let ns = Namespace::const_v0(rand::random());
+        let raw = RawBlob {
+            namespace_id: ns.id().to_vec(),
+            namespace_version: ns.version() as u32,
+            data: random_bytes(100),
+            share_version: appconsts::SHARE_VERSION_ZERO as u32,
+            signer: random_signer().as_bytes().to_vec(),
+        };
+        assert!(matches!(
+            Blob::from_raw(raw.clone(), AppVersion::V3),
+            Err(Error::SignerNotSupported(0))
+        ));
+
+        let raw = RawBlob {
+            share_version: appconsts::SHARE_VERSION_ONE as u32,
+            signer: Vec::new(),
+            ..raw
+        };
+        assert!(matches!(
+            Blob::from_raw(raw, AppVersion::V3),
+            Err(Error::MissingSigner)
+        ));
+
+        Blob::new_with_signer(ns, random_bytes(100), random_signer(), AppVersion::V2).unwrap_err();
+    }
+
+    #[test]
+    fn share_version_one_requires_app_v3() {
+        let ns = Namespace::const_v0(rand::random());
+        let blob =
+            Blob::new_with_signer(ns, random_bytes(100), random_signer(), AppVersion::V3).unwrap();
+
+        let raw = RawBlob::from(blob.clone());
+        Blob::from_raw(raw.clone(), AppVersion::V2).unwrap_err();
+        Blob::from_raw(raw, AppVersion::V3).unwrap();
+
+        let shares = blob.to_shares().unwrap();
+        Blob::reconstruct(&shares, AppVersion::V2).unwrap_err();
+        Blob::reconstruct(&shares, AppVersion::V3).unwrap();
+    }
+
+    #[test]
+    fn signed_blob_commitment_matches_share_layout() {
+        let ns = Namespace::const_v0(rand::random());
+        let data = random_bytes(1000);
+        let signer = random_signer();
+        let blob = Blob::new_with_signer(ns, data.clone(), signer.clone(), AppVersion::V3).unwrap();
+
+        // first share: namespace | info byte | sequence length | signer | data
+        let first_share_len = appconsts::FIRST_SPARSE_SHARE_CONTENT_SIZE - appconsts::SIGNER_SIZE;
+        let mut first_share = ns.as_bytes().to_vec();
+        first_share.push((appconsts::SHARE_VERSION_ONE << 1) | 1);
+        first_share.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        first_share.extend_from_slice(signer.as_bytes());
+        first_share.extend_from_slice(&data[..first_share_len]);
+
+        let mut raw_shares = vec![first_share];
+
+        // continuation shares: namespace | info byte | data
+        for chunk in
+            data[first_share_len..].chunks(appconsts::CONTINUATION_SPARSE_SHARE_CONTENT_SIZE)
+        {
+            let mut share = ns.as_bytes().to_vec();
+            share.push(appconsts::SHARE_VERSION_ONE << 1);
+            share.extend_from_slice(chunk);
+            raw_shares.push(share);
+        }
+
+        let shares: Vec<_> = raw_shares
+            .into_iter()
+            .map(|mut share| {
+                share.resize(appconsts::SHARE_SIZE, 0);
+                Share::from_raw(&share).unwrap()
+            })
+            .collect();
+        assert_eq!(blob.to_shares().unwrap(), shares);
+
+        let threshold = subtree_root_threshold(AppVersion::V3);
+        let commitment = Commitment::from_shares(ns, &shares, threshold).unwrap();
+        assert_eq!(blob.commitment, commitment);
+    }
+
     #[test]
     fn reconstruct() {
         for _ in 0..10 {
@@ -624,4 +1021,68 @@ mod tests {
 
         assert_eq!(blobs, reconstructed);
     }
+
+    #[test]
+    fn group_by_namespace() {
+        let namespaces: Vec<_> = (1..=3)
+            .map(|id| Namespace::new_v0(&[id]).unwrap())
+            .collect();
+
+        let blobs: Vec<_> = (0..9u8)
+            .map(|i| {
+                // interleave namespaces in reverse order
+                let ns = namespaces[2 - i as usize % 3];
+                Blob::new(ns, vec![i; 16], AppVersion::V2).unwrap()
+            })
+            .collect();
+
+        let grouped = Blob::group_by_namespace(blobs);
+
+        assert_eq!(grouped.keys().copied().collect::<Vec<_>>(), namespaces);
+
+        for (ns, blobs) in grouped {
+            assert_eq!(blobs.len(), 3);
+
+            let first = blobs[0].data[0];
+            for (blob, i) in blobs.iter().zip((first..).step_by(3)) {
+                assert_eq!(blob.namespace, ns);
+                assert_eq!(blob.data, vec![i; 16]);
+            }
+        }
+    }
+
+    #[test]
+    fn to_pay_for_blobs_msg() {
+        let blob = sample_blob();
+        let signer: Address = "celestia1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5wgawu3"
+            .parse()
+            .unwrap();
+
+        let msg = blob.to_pay_for_blobs_msg(signer.clone()).unwrap();
+
+        assert_eq!(msg.signer, signer.to_string());
+        assert_eq!(msg.namespaces, [blob.namespace.as_bytes()]);
+        assert_eq!(msg.blob_sizes, [blob.data.len() as u32]);
+        assert_eq!(msg.share_commitments, [blob.commitment.hash()]);
+        assert_eq!(msg.share_versions, [u32::from(blob.share_version)]);
+    }
+
+    #[test]
+    fn estimate_gas_for_known_sizes() {
+        let ns = Namespace::new_v0(&[1, 2, 3]).unwrap();
+        // 1024 bytes of data occupy 3 shares
+        let blob = Blob::new(ns, vec![1; 1024], AppVersion::V3).unwrap();
+        assert_eq!(blob.shares_len(), 3);
+
+        let expected = 3 * 512 * 8 + 10 * BYTES_PER_BLOB_INFO + PFB_GAS_FIXED_COST;
+        assert_eq!(estimate_gas(&[blob.clone()], AppVersion::V3), expected);
+
+        let expected = 6 * 512 * 8 + 2 * 10 * BYTES_PER_BLOB_INFO + PFB_GAS_FIXED_COST;
+        assert_eq!(
+            estimate_gas(&[blob.clone(), blob], AppVersion::V3),
+            expected
+        );
+
+        assert_eq!(estimate_gas(&[], AppVersion::V3), PFB_GAS_FIXED_COST);
+    }
 }
@@ -13,6 +13,7 @@ use wasm_bindgen::prelude::*;
 
 use crate::consts::appconsts;
 use crate::nmt::{Namespace, NamespacedHashExt, NamespacedSha2Hasher, Nmt, RawNamespacedHash};
+use crate::state::{AccAddress, AddressTrait};
 use crate::{Error, Result};
 use crate::{InfoByte, Share};
 
@@ -69,6 +70,12 @@ impl Commitment {
     }
 
     /// Generate the share commitment from the given blob data.
+    ///
+    /// Blobs with [`SHARE_VERSION_ONE`] include the signer in their shares,
+    /// use [`Blob::compute_commitment`] for them.
+    ///
+    /// [`SHARE_VERSION_ONE`]: appconsts::SHARE_VERSION_ONE
+    /// [`Blob::compute_commitment`]: crate::Blob::compute_commitment
     pub fn from_blob(
         namespace: Namespace,
         blob_data: &[u8],
@@ -178,23 +185,52 @@ pub(crate) fn split_blob_to_shares(
     share_version: u8,
     blob_data: &[u8],
 ) -> Result<Vec<Share>> {
-    if share_version != appconsts::SHARE_VERSION_ZERO {
-        return Err(Error::UnsupportedShareVersion(share_version));
+    match share_version {
+        appconsts::SHARE_VERSION_ZERO => {
+            split_sparse_shares(namespace, share_version, None, blob_data)
+        }
+        appconsts::SHARE_VERSION_ONE => Err(Error::MissingSigner),
+        _ => Err(Error::UnsupportedShareVersion(share_version)),
     }
+}
+
+/// Splits signed blob's data to the sequence of shares with [`SHARE_VERSION_ONE`]
+///
+/// [`SHARE_VERSION_ONE`]: appconsts::SHARE_VERSION_ONE
+pub(crate) fn split_signed_blob_to_shares(
+    namespace: Namespace,
+    signer: &AccAddress,
+    blob_data: &[u8],
+) -> Result<Vec<Share>> {
+    split_sparse_shares(
+        namespace,
+        appconsts::SHARE_VERSION_ONE,
+        Some(signer),
+        blob_data,
+    )
+}
 
+fn split_sparse_shares(
+    namespace: Namespace,
+    share_version: u8,
+    signer: Option<&AccAddress>,
+    blob_data: &[u8],
+) -> Result<Vec<Share>> {
     let mut shares = Vec::new();
     let mut cursor = Cursor::new(blob_data);
 
     while cursor.has_remaining() {
-        let share = build_sparse_share_v0(namespace, &mut cursor)?;
+        let share = build_sparse_share(namespace, share_version, signer, &mut cursor)?;
         shares.push(share);
     }
     Ok(shares)
 }
 
 /// Build a sparse share from a cursor over data
-fn build_sparse_share_v0(
+fn build_sparse_share(
     namespace: Namespace,
+    share_version: u8,
+    signer: Option<&AccAddress>,
     data: &mut Cursor<impl AsRef<[u8]>>,
 ) -> Result<Share> {
     let is_first_share = data.position() == 0;
@@ -204,15 +240,20 @@ fn build_sparse_share_v0(
     // Write the namespace
     bytes.put_slice(namespace.as_bytes());
     // Write the info byte
-    let info_byte = InfoByte::new(appconsts::SHARE_VERSION_ZERO, is_first_share)?;
+    let info_byte = InfoByte::new(share_version, is_first_share)?;
     bytes.put_u8(info_byte.as_u8());
 
     // If this share is first in the sequence, write the bytes len of the sequence
+    // and the signer, if any
     if is_first_share {
         let data_len = data_len
             .try_into()
             .map_err(|_| Error::ShareSequenceLenExceeded(data_len))?;
         bytes.put_u32(data_len);
+
+        if let Some(signer) = signer {
+            bytes.put_slice(signer.as_bytes());
+        }
     }
 
     // Calculate amount of bytes to read
@@ -331,7 +372,8 @@ mod tests {
         let data = vec![1, 2, 3, 4, 5, 6, 7];
         let mut cursor = Cursor::new(&data);
 
-        let share = build_sparse_share_v0(namespace, &mut cursor).unwrap();
+        let share = build_sparse_share(namespace, appconsts::SHARE_VERSION_ZERO, None, &mut cursor)
+            .unwrap();
 
         // check cursor
         assert!(!cursor.has_remaining());
@@ -363,7 +405,9 @@ mod tests {
         let data = vec![7; appconsts::FIRST_SPARSE_SHARE_CONTENT_SIZE + continuation_len];
         let mut cursor = Cursor::new(&data);
 
-        let first_share = build_sparse_share_v0(namespace, &mut cursor).unwrap();
+        let first_share =
+            build_sparse_share(namespace, appconsts::SHARE_VERSION_ZERO, None, &mut cursor)
+                .unwrap();
 
         // check cursor
         assert_eq!(
@@ -390,7 +434,9 @@ mod tests {
         );
 
         // Continuation share
-        let continuation_share = build_sparse_share_v0(namespace, &mut cursor).unwrap();
+        let continuation_share =
+            build_sparse_share(namespace, appconsts::SHARE_VERSION_ZERO, None, &mut cursor)
+                .unwrap();
 
         // check cursor
         assert!(!cursor.has_remaining());
@@ -427,7 +473,8 @@ mod tests {
             0, 0, 0, 0, // sequence len
         ];
 
-        let share = build_sparse_share_v0(namespace, &mut cursor).unwrap();
+        let share = build_sparse_share(namespace, appconsts::SHARE_VERSION_ZERO, None, &mut cursor)
+            .unwrap();
 
         // check cursor
         assert!(!cursor.has_remaining());
@@ -299,7 +299,7 @@ pub(crate) mod test_utils {
     use rand::seq::index;
     use rand::Rng;
 
-    use crate::consts::appconsts::{FIRST_SPARSE_SHARE_CONTENT_SIZE, SHARE_SIZE};
+    use crate::consts::appconsts::first_share_payload_range;
     use crate::eds::is_ods_square;
     use crate::test_utils::{random_bytes, ExtendedHeaderGenerator};
     use crate::{DataAvailabilityHeader, ExtendedDataSquare};
@@ -329,8 +329,8 @@ pub(crate) mod test_utils {
             };
             // only trash data after the namespace, info byte and seq length so that we don't
             // need to care whether the share is original or parity
-            let offset = SHARE_SIZE - FIRST_SPARSE_SHARE_CONTENT_SIZE;
-            share.as_mut()[offset..].copy_from_slice(&random_bytes(SHARE_SIZE - offset));
+            let payload = first_share_payload_range();
+            share.as_mut()[payload.clone()].copy_from_slice(&random_bytes(payload.len()));
         }
 
         // create extended header with proof
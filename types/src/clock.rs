@@ -0,0 +1,100 @@
+//! Sources of the current time for the time dependent verification.
+//!
+//! Verification of [`ExtendedHeader`]s checks that headers don't come from the future
+//! and that they are within the trusting period, both of which depend on the current
+//! time. Methods taking a [`Clock`], like [`ExtendedHeader::verify_with_clock`], allow
+//! providing it explicitly, e.g. with a [`MockClock`] in tests.
+//!
+//! [`ExtendedHeader`]: crate::ExtendedHeader
+//! [`ExtendedHeader::verify_with_clock`]: crate::ExtendedHeader::verify_with_clock
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tendermint::Time;
+
+/// A source of the current time.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> Time;
+}
+
+/// [`Clock`] using the system time.
+///
+/// In the browser, it uses the JavaScript `Date`, as the system time
+/// isn't available in `wasm32-unknown-unknown`.
+#[cfg(any(
+    not(any(target_arch = "wasm32", target_arch = "riscv32")),
+    all(target_arch = "wasm32", feature = "wasm-bindgen")
+))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(not(any(target_arch = "wasm32", target_arch = "riscv32")))]
+impl Clock for SystemClock {
+    fn now(&self) -> Time {
+        Time::now()
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm-bindgen"))]
+impl Clock for SystemClock {
+    fn now(&self) -> Time {
+        let millis = js_sys::Date::now();
+        let secs = (millis / 1000.0).floor();
+        let nanos = (millis - secs * 1000.0) * 1_000_000.0;
+
+        Time::from_unix_timestamp(secs as i64, nanos as u32).expect("valid unix timestamp")
+    }
+}
+
+/// Current time of the [`SystemClock`], if it is available on the target.
+pub(crate) fn system_now() -> Option<Time> {
+    #[cfg(any(
+        not(any(target_arch = "wasm32", target_arch = "riscv32")),
+        all(target_arch = "wasm32", feature = "wasm-bindgen")
+    ))]
+    {
+        Some(SystemClock.now())
+    }
+
+    #[cfg(not(any(
+        not(any(target_arch = "wasm32", target_arch = "riscv32")),
+        all(target_arch = "wasm32", feature = "wasm-bindgen")
+    )))]
+    {
+        None
+    }
+}
+
+/// [`Clock`] returning a manually controlled time.
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<Time>,
+}
+
+impl MockClock {
+    /// Create a new [`MockClock`] set to the given time.
+    pub fn new(now: Time) -> Self {
+        MockClock {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Set the current time.
+    pub fn set(&self, now: Time) {
+        *self.now.lock().expect("poisoned lock") = now;
+    }
+
+    /// Move the current time forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().expect("poisoned lock");
+        *now = now.checked_add(duration).expect("time overflow");
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Time {
+        *self.now.lock().expect("poisoned lock")
+    }
+}
@@ -194,6 +194,8 @@ pub mod appconsts {
 
     // celestia-app/pkg/appconsts/global_consts
     mod global_consts {
+        use std::ops::Range;
+
         use crate::nmt::NS_SIZE;
 
         /// The size of the namespace.
@@ -214,6 +216,13 @@ pub mod appconsts {
         /// The first share version format.
         pub const SHARE_VERSION_ZERO: u8 = 0;
 
+        /// The share version format of blobs including the signer in their first share.
+        pub const SHARE_VERSION_ONE: u8 = 1;
+
+        /// The number of bytes reserved for the signer in the first share of a blob.
+        /// It is present only in blobs with [`SHARE_VERSION_ONE`].
+        pub const SIGNER_SIZE: usize = 20;
+
         /// The number of bytes reserved for the location of the first unit (transaction, ISR) in a compact share.
         pub const COMPACT_SHARE_RESERVED_BYTES: usize = 4;
 
@@ -236,6 +245,20 @@ pub mod appconsts {
         pub const CONTINUATION_SPARSE_SHARE_CONTENT_SIZE: usize =
             SHARE_SIZE - NAMESPACE_SIZE - SHARE_INFO_BYTES;
 
+        /// The byte range of the payload in the first sparse share of a sequence.
+        ///
+        /// The payload follows the namespace, the info byte and the sequence length.
+        pub const fn first_share_payload_range() -> Range<usize> {
+            SHARE_SIZE - FIRST_SPARSE_SHARE_CONTENT_SIZE..SHARE_SIZE
+        }
+
+        /// The byte range of the payload in a continuation sparse share of a sequence.
+        ///
+        /// The payload follows the namespace and the info byte.
+        pub const fn continuation_share_payload_range() -> Range<usize> {
+            SHARE_SIZE - CONTINUATION_SPARSE_SHARE_CONTENT_SIZE..SHARE_SIZE
+        }
+
         /// The smallest original square width.
         pub const MIN_SQUARE_SIZE: usize = 1;
 
@@ -294,3 +317,24 @@ pub mod cosmos {
     /// Bech32PrefixConsPub defines the Bech32 prefix of a consensus node public key.
     pub const BECH32_PREFIX_CONS_PUB: &str = concatcp!(BECH32_PREFIX_CONS_ADDR, PREFIX_PUBLIC);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::appconsts::*;
+
+    #[test]
+    fn share_payload_ranges() {
+        let first = first_share_payload_range();
+        assert_eq!(
+            first.start,
+            NAMESPACE_SIZE + SHARE_INFO_BYTES + SEQUENCE_LEN_BYTES
+        );
+        assert_eq!(first.end, SHARE_SIZE);
+        assert_eq!(first.len(), FIRST_SPARSE_SHARE_CONTENT_SIZE);
+
+        let continuation = continuation_share_payload_range();
+        assert_eq!(continuation.start, NAMESPACE_SIZE + SHARE_INFO_BYTES);
+        assert_eq!(continuation.end, SHARE_SIZE);
+        assert_eq!(continuation.len(), CONTINUATION_SPARSE_SHARE_CONTENT_SIZE);
+    }
+}
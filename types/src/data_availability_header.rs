@@ -196,6 +196,21 @@ impl DataAvailabilityHeader {
             .expect("len is bigger than u16::MAX")
     }
 
+    /// Get the size of the original data square, which is half of the [`square_width`].
+    ///
+    /// [`square_width`]: DataAvailabilityHeader::square_width
+    pub fn original_square_width(&self) -> u16 {
+        self.square_width() / 2
+    }
+
+    /// Get the total amount of shares in the [`ExtendedDataSquare`], including parity shares.
+    ///
+    /// [`ExtendedDataSquare`]: crate::eds::ExtendedDataSquare
+    pub fn total_shares(&self) -> usize {
+        let square_width = usize::from(self.square_width());
+        square_width * square_width
+    }
+
     /// Get the [`RowProof`] for given rows.
     pub fn row_proof(&self, rows: RangeInclusive<u16>) -> Result<RowProof> {
         let all_roots: Vec<_> = self
@@ -329,6 +344,13 @@ impl ValidateBasicWithAppVersion for DataAvailabilityHeader {
             )
         }
 
+        if !self.row_roots.len().is_power_of_two() {
+            bail_validation!(
+                "row_roots len ({}) is not a power of two",
+                self.row_roots.len(),
+            )
+        }
+
         if self.row_roots.len() > max_extended_square_width {
             bail_validation!(
                 "row_roots len ({}) > maximum ({})",
@@ -545,6 +567,46 @@ mod tests {
         dah.validate_basic(AppVersion::V2).unwrap_err();
     }
 
+    fn dah_with_width(square_width: usize) -> DataAvailabilityHeader {
+        let dah = sample_dah();
+        DataAvailabilityHeader {
+            row_roots: dah
+                .row_roots
+                .into_iter()
+                .cycle()
+                .take(square_width)
+                .collect(),
+            column_roots: dah
+                .column_roots
+                .into_iter()
+                .cycle()
+                .take(square_width)
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn square_sizes() {
+        for (square_width, original_width) in [(2, 1), (4, 2), (16, 8), (128, 64)] {
+            let dah = dah_with_width(square_width);
+
+            dah.validate_basic(AppVersion::V2).unwrap();
+            assert_eq!(usize::from(dah.square_width()), square_width);
+            assert_eq!(dah.original_square_width(), original_width);
+            assert_eq!(dah.total_shares(), square_width * square_width);
+        }
+    }
+
+    #[test]
+    fn validate_square_width_not_power_of_two() {
+        dah_with_width(6)
+            .validate_basic(AppVersion::V2)
+            .unwrap_err();
+        dah_with_width(12)
+            .validate_basic(AppVersion::V2)
+            .unwrap_err();
+    }
+
     #[test]
     fn row_proof_serde() {
         let raw_row_proof = r#"
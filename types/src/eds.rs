@@ -1,17 +1,29 @@
 //! Types related to EDS.
 
 use std::cmp::Ordering;
+use std::collections::BTreeSet;
 use std::fmt::Display;
 
+use prost::encoding::decode_varint;
 use serde::{Deserialize, Serialize};
 
-use crate::consts::appconsts::{AppVersion, SHARE_SIZE};
+pub mod coords;
+#[cfg(any(test, feature = "test-utils"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+pub mod fixtures;
+
+use crate::blob::{shares_needed_for_blob, shares_needed_for_signed_blob};
+use crate::consts::appconsts::{
+    AppVersion, COMPACT_SHARE_RESERVED_BYTES, SHARE_SIZE, SHARE_VERSION_ONE,
+};
 use crate::consts::data_availability_header::{
     max_extended_square_width, MIN_EXTENDED_SQUARE_WIDTH,
 };
 use crate::nmt::{Namespace, NamespacedSha2Hasher, Nmt, NmtExt, NS_SIZE};
 use crate::row_namespace_data::{RowNamespaceData, RowNamespaceDataId};
-use crate::{bail_validation, DataAvailabilityHeader, Error, InfoByte, Result, Share};
+use crate::{
+    bail_validation, Blob, Commitment, DataAvailabilityHeader, Error, InfoByte, Result, Share,
+};
 
 /// Represents either column or row of the [`ExtendedDataSquare`].
 ///
@@ -46,6 +58,48 @@ impl Display for AxisType {
     }
 }
 
+/// Ordering of the shares in a serialized [`ExtendedDataSquare`].
+///
+/// See [`ExtendedDataSquare::to_bytes`] and [`ExtendedDataSquare::from_bytes`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EdsLayout {
+    /// Shares of the whole square, row by row.
+    RowMajor,
+    /// Shares of the whole square, column by column.
+    ColMajor,
+    /// Shares of the original data square only, row by row.
+    ///
+    /// Parity data is recomputed when deserializing.
+    OriginalOnly,
+}
+
+/// Location of a [`Blob`] within the [`ExtendedDataSquare`].
+///
+/// See [`ExtendedDataSquare::blob_locations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobLocation {
+    /// A [`Namespace`] the [`Blob`] belongs to.
+    pub namespace: Namespace,
+    /// A [`Commitment`] computed from the [`Blob`]s data.
+    pub commitment: Commitment,
+    /// Index of the blob's first share in the original data square, in a row-major order.
+    pub start_index: u64,
+    /// Amount of shares the blob occupies.
+    pub share_count: u64,
+}
+
+/// Contents of the block, split into transactions and blobs.
+///
+/// See [`ExtendedDataSquare::split_contents`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockContents {
+    /// Raw transactions from the [`Namespace::TRANSACTION`] and [`Namespace::PAY_FOR_BLOB`]
+    /// compact shares, in that order.
+    pub transactions: Vec<Vec<u8>>,
+    /// Blobs from the user namespaces, in the order of the square.
+    pub blobs: Vec<Blob>,
+}
+
 /// The data matrix in Celestia blocks extended with parity data.
 ///
 /// It is created by a fixed size chunks of data, called [`Share`]s.
@@ -311,6 +365,76 @@ impl ExtendedDataSquare {
         ExtendedDataSquare::new(eds_shares, "Leopard".to_string(), app_version)
     }
 
+    /// Create a new EDS out of the shares serialized with the given [`EdsLayout`].
+    ///
+    /// `square_width` is the width of the extended data square, regardless of the layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length of `bytes` doesn't match the amount of shares
+    /// expected for the `square_width` in the given layout. Otherwise the same errors
+    /// as in [`ExtendedDataSquare::new`] apply.
+    pub fn from_bytes(
+        bytes: &[u8],
+        square_width: u16,
+        layout: EdsLayout,
+        app_version: AppVersion,
+    ) -> Result<ExtendedDataSquare> {
+        if !square_width.is_power_of_two() {
+            return Err(Error::EdsInvalidDimentions);
+        }
+
+        let width = usize::from(square_width);
+        let shares_width = match layout {
+            EdsLayout::RowMajor | EdsLayout::ColMajor => width,
+            EdsLayout::OriginalOnly => width / 2,
+        };
+
+        if bytes.len() != shares_width * shares_width * SHARE_SIZE {
+            return Err(Error::EdsInvalidDimentions);
+        }
+
+        let shares = bytes.chunks_exact(SHARE_SIZE).map(ToOwned::to_owned);
+
+        match layout {
+            EdsLayout::RowMajor => {
+                ExtendedDataSquare::new(shares.collect(), "Leopard".to_string(), app_version)
+            }
+            EdsLayout::ColMajor => {
+                let mut shares: Vec<_> = shares.collect();
+                transpose(&mut shares, width);
+                ExtendedDataSquare::new(shares, "Leopard".to_string(), app_version)
+            }
+            EdsLayout::OriginalOnly => ExtendedDataSquare::from_ods(shares.collect(), app_version),
+        }
+    }
+
+    /// Serialize the shares of the EDS using the given [`EdsLayout`].
+    pub fn to_bytes(&self, layout: EdsLayout) -> Vec<u8> {
+        let width = self.square_width;
+
+        let coords: Vec<(u16, u16)> = match layout {
+            EdsLayout::RowMajor => (0..width)
+                .flat_map(|row| (0..width).map(move |col| (row, col)))
+                .collect(),
+            EdsLayout::ColMajor => (0..width)
+                .flat_map(|col| (0..width).map(move |row| (row, col)))
+                .collect(),
+            EdsLayout::OriginalOnly => (0..width / 2)
+                .flat_map(|row| (0..width / 2).map(move |col| (row, col)))
+                .collect(),
+        };
+
+        let mut bytes = Vec::with_capacity(coords.len() * SHARE_SIZE);
+
+        for (row, col) in coords {
+            let share = &self.data_square[flatten_index(row, col, width)];
+            bytes.extend_from_slice(share.as_ref());
+        }
+
+        bytes
+    }
+
     /// The raw data of the EDS.
     pub fn data_square(&self) -> &[Share] {
         &self.data_square
@@ -323,11 +447,8 @@ impl ExtendedDataSquare {
 
     /// Returns the share of the provided coordinates.
     pub fn share(&self, row: u16, column: u16) -> Result<&Share> {
-        let index = usize::from(row) * usize::from(self.square_width) + usize::from(column);
-
-        self.data_square
-            .get(index)
-            .ok_or(Error::EdsIndexOutOfRange(row, column))
+        let index = coords::to_linear(row, column, self.square_width)?;
+        Ok(&self.data_square[index])
     }
 
     /// Returns the mutable share of the provided coordinates.
@@ -398,6 +519,24 @@ impl ExtendedDataSquare {
         self.square_width
     }
 
+    /// Returns the sorted list of namespaces that have data in the EDS.
+    ///
+    /// Only the original data square is inspected, and reserved namespaces, including
+    /// the padding and parity ones, are skipped.
+    pub fn namespaces(&self) -> Vec<Namespace> {
+        let ods_width = self.square_width / 2;
+
+        (0..ods_width)
+            .flat_map(|row| (0..ods_width).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                self.data_square[flatten_index(row, col, self.square_width)].namespace()
+            })
+            .filter(|ns| !ns.is_reserved())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
     /// Return all the shares that belong to the provided namespace in the EDS.
     /// Results are returned as a list of rows of shares with the inclusion proof.
     pub fn get_namespace_data(
@@ -443,6 +582,193 @@ impl ExtendedDataSquare {
 
         Ok(rows)
     }
+
+    /// Return the locations of all the blobs in the original data square.
+    ///
+    /// Shares of the original data square are walked in a row-major order and
+    /// grouped into blobs by the shares which start a new sequence. Shares from
+    /// reserved namespaces and namespace padding shares are skipped.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any errors from [`Blob::reconstruct`].
+    pub fn blob_locations(&self, app_version: AppVersion) -> Result<Vec<BlobLocation>> {
+        let locations = self
+            .located_blobs(app_version)?
+            .into_iter()
+            .map(|(location, _)| location)
+            .collect();
+
+        Ok(locations)
+    }
+
+    /// Split the original data square into transactions and blobs.
+    ///
+    /// Transactions are parsed from the compact shares of [`Namespace::TRANSACTION`]
+    /// and [`Namespace::PAY_FOR_BLOB`], where each of them may span multiple shares.
+    /// Transactions from the latter are wrapped together with the indexes of their blobs.
+    /// Blobs are reconstructed from the sparse shares of user namespaces, the same way
+    /// as in [`blob_locations`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the compact shares are malformed and
+    /// propagates any errors from [`Blob::reconstruct`].
+    ///
+    /// [`blob_locations`]: ExtendedDataSquare::blob_locations
+    pub fn split_contents(&self, app_version: AppVersion) -> Result<BlockContents> {
+        let ods_shares = self.ods_shares();
+        let mut transactions = Vec::new();
+
+        for namespace in [Namespace::TRANSACTION, Namespace::PAY_FOR_BLOB] {
+            let shares = ods_shares
+                .iter()
+                .copied()
+                .filter(|share| share.namespace() == namespace);
+            transactions.extend(parse_compact_shares(shares)?);
+        }
+
+        let blobs = self
+            .located_blobs(app_version)?
+            .into_iter()
+            .map(|(_, blob)| blob)
+            .collect();
+
+        Ok(BlockContents {
+            transactions,
+            blobs,
+        })
+    }
+
+    /// Shares of the original data square in a row-major order.
+    fn ods_shares(&self) -> Vec<&Share> {
+        let width = usize::from(self.square_width);
+        let ods_width = width / 2;
+
+        self.data_square
+            .chunks(width)
+            .take(ods_width)
+            .flat_map(|row| &row[..ods_width])
+            .collect()
+    }
+
+    /// Reconstruct all the blobs in the original data square together with their locations.
+    fn located_blobs(&self, app_version: AppVersion) -> Result<Vec<(BlobLocation, Blob)>> {
+        let ods_shares = self.ods_shares();
+
+        let mut blobs = Vec::new();
+        let mut index = 0;
+
+        while index < ods_shares.len() {
+            let share = ods_shares[index];
+
+            let sequence_len = match share.sequence_length() {
+                // namespace padding shares have a sequence length of 0
+                Some(len) if len > 0 && !share.namespace().is_reserved() => len,
+                _ => {
+                    index += 1;
+                    continue;
+                }
+            };
+
+            let share_version = share.info_byte().expect("non parity").version();
+            let share_count = if share_version == SHARE_VERSION_ONE {
+                shares_needed_for_signed_blob(sequence_len as usize)
+            } else {
+                shares_needed_for_blob(sequence_len as usize)
+            };
+            let end = ods_shares.len().min(index + share_count);
+            let blob = Blob::reconstruct(ods_shares[index..end].iter().copied(), app_version)?;
+
+            let location = BlobLocation {
+                namespace: blob.namespace,
+                commitment: blob.commitment,
+                start_index: index as u64,
+                share_count: share_count as u64,
+            };
+            blobs.push((location, blob));
+
+            index += share_count;
+        }
+
+        Ok(blobs)
+    }
+
+    /// Verify that the [`Blob`] is included in the original data square.
+    ///
+    /// Blobs are located using [`blob_locations`], which recomputes their commitments
+    /// from the shares, so no additional inclusion proof is needed.
+    ///
+    /// This only proves that the blob is a part of this square. To prove that it is
+    /// included in a block, the square itself must be verified against the block's
+    /// [`DataAvailabilityHeader`] first, e.g. by comparing it with the one computed with
+    /// [`DataAvailabilityHeader::from_eds`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the blob's commitment doesn't match its
+    /// data, [`Error::BlobNamespaceNotFound`] if the square has no blobs within the
+    /// blob's namespace and [`Error::BlobCommitmentMismatch`] if none of them has
+    /// a matching commitment.
+    ///
+    /// [`blob_locations`]: ExtendedDataSquare::blob_locations
+    pub fn verify_blob(&self, blob: &Blob, app_version: AppVersion) -> Result<BlobLocation> {
+        blob.validate(app_version)?;
+
+        let mut locations = self
+            .blob_locations(app_version)?
+            .into_iter()
+            .filter(|location| location.namespace == blob.namespace)
+            .peekable();
+
+        if locations.peek().is_none() {
+            return Err(Error::BlobNamespaceNotFound);
+        }
+
+        locations
+            .find(|location| location.commitment == blob.commitment)
+            .ok_or(Error::BlobCommitmentMismatch)
+    }
+
+    /// Verify that the parity shares of the EDS are correctly encoded.
+    ///
+    /// Each row and column is encoded again from its original half using the
+    /// [`leopard_codec`] and the result is compared with the parity half of the axis.
+    /// An incorrectly encoded axis is what a [`BadEncodingFraudProof`] is raised for.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EdsBadEncoding`] with the first axis whose parity shares
+    /// don't match, checking rows before columns.
+    ///
+    /// [`BadEncodingFraudProof`]: crate::fraud_proof::BadEncodingFraudProof
+    pub fn verify_encoding(&self) -> Result<()> {
+        let ods_width = usize::from(self.square_width / 2);
+
+        for axis in [AxisType::Row, AxisType::Col] {
+            for index in 0..self.square_width {
+                let shares = self.axis(axis, index)?;
+
+                let mut encoded: Vec<_> = shares[..ods_width]
+                    .iter()
+                    .map(Share::to_vec)
+                    .chain(std::iter::repeat(vec![0; SHARE_SIZE]).take(ods_width))
+                    .collect();
+                leopard_codec::encode(&mut encoded, ods_width)?;
+
+                let matches = shares[ods_width..]
+                    .iter()
+                    .zip(&encoded[ods_width..])
+                    .all(|(share, parity)| share.as_ref() == parity.as_slice());
+
+                if !matches {
+                    return Err(Error::EdsBadEncoding(axis, index));
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Raw representation of [`ExtendedDataSquare`].
@@ -479,10 +805,76 @@ fn flatten_index(row: u16, col: u16, square_width: u16) -> usize {
     usize::from(row) * usize::from(square_width) + usize::from(col)
 }
 
+/// Transpose a square of `width` elements stored in a row-major order.
+fn transpose<T>(square: &mut [T], width: usize) {
+    for row in 0..width {
+        for col in row + 1..width {
+            square.swap(row * width + col, col * width + row);
+        }
+    }
+}
+
+/// Parse the length delimited units, e.g. transactions, from the compact shares of a namespace.
+fn parse_compact_shares<'a, I>(shares: I) -> Result<Vec<Vec<u8>>>
+where
+    I: IntoIterator<Item = &'a Share>,
+{
+    let mut shares = shares.into_iter();
+    let mut units = Vec::new();
+
+    while let Some(first_share) = shares.next() {
+        let sequence_len = first_share
+            .sequence_length()
+            .ok_or(Error::ExpectedShareWithSequenceStart)? as usize;
+
+        // the sequence length comes from the share, so don't trust it beyond
+        // what the remaining shares could possibly hold
+        let max_len = shares
+            .size_hint()
+            .1
+            .map_or(SHARE_SIZE, |remaining| (remaining + 1) * SHARE_SIZE);
+        let mut data = Vec::with_capacity(sequence_len.min(max_len));
+        data.extend_from_slice(compact_share_content(first_share));
+
+        // units can span multiple shares
+        while data.len() < sequence_len {
+            let share = shares.next().ok_or(Error::MissingShares)?;
+            if share.sequence_length().is_some() {
+                return Err(Error::UnexpectedSequenceStart);
+            }
+            data.extend_from_slice(compact_share_content(share));
+        }
+
+        // remove padding
+        data.truncate(sequence_len);
+
+        let mut buf = &data[..];
+        while !buf.is_empty() {
+            let len = decode_varint(&mut buf)? as usize;
+            if len > buf.len() {
+                return Err(Error::MalformedCompactShares);
+            }
+            let (unit, rest) = buf.split_at(len);
+            units.push(unit.to_vec());
+            buf = rest;
+        }
+    }
+
+    Ok(units)
+}
+
+/// Payload of the compact share without the reserved bytes.
+fn compact_share_content(share: &Share) -> &[u8] {
+    &share.payload().expect("non parity")[COMPACT_SHARE_RESERVED_BYTES..]
+}
+
 #[cfg(test)]
 mod tests {
+    use prost::encoding::encode_varint;
+
     use super::*;
-    use crate::{test_utils::generate_eds, Blob, ExtendedHeader};
+    use crate::test_utils::{generate_eds, random_bytes};
+    use crate::{Blob, ExtendedHeader};
 
     #[test]
     fn axis_type_serialization() {
@@ -531,6 +923,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn namespaces() {
+        let eds_json = include_str!("../test_data/shwap_samples/eds.json");
+        let raw_eds: RawExtendedDataSquare = serde_json::from_str(eds_json).unwrap();
+        let eds = ExtendedDataSquare::from_raw(raw_eds, AppVersion::V2).unwrap();
+
+        assert_eq!(
+            eds.namespaces(),
+            vec![
+                Namespace::new_v0(&[1, 170]).unwrap(),
+                Namespace::new_v0(&[1, 187]).unwrap(),
+            ]
+        );
+
+        assert!(ExtendedDataSquare::empty().namespaces().is_empty());
+    }
+
     #[test]
     fn nmt_roots() {
         let eds_json = include_str!("../test_data/shwap_samples/eds.json");
@@ -865,6 +1274,85 @@ mod tests {
         assert_eq!(dah, genesis.dah);
     }
 
+    #[test]
+    fn blob_locations() {
+        let eds = generate_eds(16, AppVersion::V2);
+        let ods_width = eds.square_width() as u64 / 2;
+
+        let locations = eds.blob_locations(AppVersion::V2).unwrap();
+        let blobs = Blob::reconstruct_all(eds.data_square(), AppVersion::V2).unwrap();
+
+        // first ods row has PFB's, one blob occupies 2 rows, and rest rows have 1 blob each
+        assert_eq!(locations.len(), ods_width as usize - 2);
+        assert_eq!(locations.len(), blobs.len());
+
+        // the first blob starts at the second row and spans into the third one
+        assert_eq!(locations[0].start_index, ods_width);
+        assert!(locations[0].share_count > ods_width);
+
+        for (i, location) in locations.iter().enumerate().skip(1) {
+            assert_eq!(location.start_index, (i as u64 + 2) * ods_width);
+            assert!(location.share_count < ods_width);
+        }
+
+        for (location, blob) in locations.iter().zip(blobs) {
+            assert_eq!(location.namespace, blob.namespace);
+            assert_eq!(location.commitment, blob.commitment);
+            assert_eq!(location.share_count, blob.shares_len() as u64);
+        }
+    }
+
+    #[test]
+    fn verify_blob() {
+        let eds = generate_eds(16, AppVersion::V2);
+        let locations = eds.blob_locations(AppVersion::V2).unwrap();
+        let blobs = Blob::reconstruct_all(eds.data_square(), AppVersion::V2).unwrap();
+
+        for (location, blob) in locations.iter().zip(&blobs) {
+            assert_eq!(&eds.verify_blob(blob, AppVersion::V2).unwrap(), location);
+        }
+
+        // same namespace, different data
+        let blob = Blob::new(blobs[0].namespace, vec![1, 2, 3], AppVersion::V2).unwrap();
+        assert!(matches!(
+            eds.verify_blob(&blob, AppVersion::V2).unwrap_err(),
+            Error::BlobCommitmentMismatch
+        ));
+
+        // namespace not present in the square
+        let namespace = Namespace::new_v0(&[0xff; 10]).unwrap();
+        assert!(!eds.namespaces().contains(&namespace));
+        let blob = Blob::new(namespace, vec![1, 2, 3], AppVersion::V2).unwrap();
+        assert!(matches!(
+            eds.verify_blob(&blob, AppVersion::V2).unwrap_err(),
+            Error::BlobNamespaceNotFound
+        ));
+    }
+
+    #[test]
+    fn verify_encoding() {
+        let eds = generate_eds(8, AppVersion::V2);
+        eds.verify_encoding().unwrap();
+
+        let last = eds.square_width() - 1;
+
+        // parity of the first row
+        let mut corrupted = eds.clone();
+        corrupted.share_mut(0, last).unwrap().as_mut()[100] ^= 0xff;
+        assert!(matches!(
+            corrupted.verify_encoding().unwrap_err(),
+            Error::EdsBadEncoding(AxisType::Row, 0)
+        ));
+
+        // parity of the first column, which is original data of the last row
+        let mut corrupted = eds.clone();
+        corrupted.share_mut(last, 0).unwrap().as_mut()[100] ^= 0xff;
+        assert!(matches!(
+            corrupted.verify_encoding().unwrap_err(),
+            Error::EdsBadEncoding(AxisType::Row, i) if i == last
+        ));
+    }
+
     #[test]
     fn reconstruct_all() {
         let eds = generate_eds(8 << (rand::random::<usize>() % 6), AppVersion::V2);
@@ -874,4 +1362,212 @@ mod tests {
         let expected = eds.square_width() as usize / 2 - 2;
         assert_eq!(blobs.len(), expected);
     }
+
+    #[test]
+    fn bytes_layouts_round_trip() {
+        let eds = generate_eds(8, AppVersion::V3);
+
+        for layout in [
+            EdsLayout::RowMajor,
+            EdsLayout::ColMajor,
+            EdsLayout::OriginalOnly,
+        ] {
+            let bytes = eds.to_bytes(layout);
+            let decoded =
+                ExtendedDataSquare::from_bytes(&bytes, 8, layout, AppVersion::V3).unwrap();
+
+            assert_eq!(decoded.data_square(), eds.data_square());
+            assert_eq!(decoded.square_width(), 8);
+        }
+    }
+
+    #[test]
+    fn bytes_layouts_cross_conversion() {
+        let eds = generate_eds(8, AppVersion::V3);
+        let row_major = eds.to_bytes(EdsLayout::RowMajor);
+        let col_major = eds.to_bytes(EdsLayout::ColMajor);
+        let ods = eds.to_bytes(EdsLayout::OriginalOnly);
+
+        assert_eq!(row_major.len(), 64 * SHARE_SIZE);
+        assert_eq!(col_major.len(), 64 * SHARE_SIZE);
+        assert_eq!(ods.len(), 16 * SHARE_SIZE);
+        let shares: Vec<u8> = eds
+            .data_square()
+            .iter()
+            .flat_map(|share| share.as_ref().to_vec())
+            .collect();
+        assert_eq!(row_major, shares);
+
+        for row in 0..8 {
+            for col in 0..8 {
+                let row_major_idx = (row * 8 + col) * SHARE_SIZE;
+                let col_major_idx = (col * 8 + row) * SHARE_SIZE;
+
+                assert_eq!(
+                    row_major[row_major_idx..row_major_idx + SHARE_SIZE],
+                    col_major[col_major_idx..col_major_idx + SHARE_SIZE]
+                );
+            }
+        }
+
+        for (ods_row, row) in ods
+            .chunks(4 * SHARE_SIZE)
+            .zip(row_major.chunks(8 * SHARE_SIZE))
+        {
+            assert_eq!(ods_row, &row[..4 * SHARE_SIZE]);
+        }
+
+        // column-major bytes read as row-major describe a transposed square
+        let transposed =
+            ExtendedDataSquare::from_bytes(&col_major, 8, EdsLayout::RowMajor, AppVersion::V3)
+                .unwrap();
+        assert_eq!(transposed.to_bytes(EdsLayout::ColMajor), row_major);
+    }
+
+    #[test]
+    fn bytes_invalid_length() {
+        let eds = generate_eds(8, AppVersion::V3);
+
+        for layout in [
+            EdsLayout::RowMajor,
+            EdsLayout::ColMajor,
+            EdsLayout::OriginalOnly,
+        ] {
+            let bytes = eds.to_bytes(layout);
+
+            let err = ExtendedDataSquare::from_bytes(
+                &bytes[..bytes.len() - 1],
+                8,
+                layout,
+                AppVersion::V3,
+            )
+            .unwrap_err();
+            assert!(matches!(err, Error::EdsInvalidDimentions));
+
+            let err =
+                ExtendedDataSquare::from_bytes(&bytes, 16, layout, AppVersion::V3).unwrap_err();
+            assert!(matches!(err, Error::EdsInvalidDimentions));
+
+            let err =
+                ExtendedDataSquare::from_bytes(&bytes, 6, layout, AppVersion::V3).unwrap_err();
+            assert!(matches!(err, Error::EdsInvalidDimentions));
+        }
+    }
+
+    #[test]
+    fn split_contents() {
+        let eds_json = include_str!("../test_data/shwap_samples/eds.json");
+        let raw_eds: RawExtendedDataSquare = serde_json::from_str(eds_json).unwrap();
+        let eds = ExtendedDataSquare::from_raw(raw_eds, AppVersion::V2).unwrap();
+
+        let contents = eds.split_contents(AppVersion::V2).unwrap();
+
+        // single wrapped PayForBlobs transaction
+        assert_eq!(contents.transactions.len(), 1);
+        let pfb = &contents.transactions[0];
+        assert_eq!(pfb.len(), 416);
+
+        assert_eq!(contents.blobs.len(), 2);
+        assert_eq!(
+            contents.blobs[0].namespace,
+            Namespace::new_v0(&[1, 170]).unwrap()
+        );
+        assert_eq!(
+            contents.blobs[1].namespace,
+            Namespace::new_v0(&[1, 187]).unwrap()
+        );
+
+        // transaction pays for both of the blobs
+        for blob in &contents.blobs {
+            let commitment = blob.commitment.hash();
+            assert!(pfb.windows(commitment.len()).any(|w| w == commitment));
+        }
+    }
+
+    #[test]
+    fn split_contents_multi_share_transactions() {
+        let txs = vec![random_bytes(300), random_bytes(700), random_bytes(10)];
+        let blob = Blob::new(
+            Namespace::new_v0(&[1, 2, 3]).unwrap(),
+            random_bytes(100),
+            AppVersion::V3,
+        )
+        .unwrap();
+
+        let mut shares = compact_shares(Namespace::TRANSACTION, &txs);
+        assert_eq!(shares.len(), 3);
+        shares.push(padding_share(Namespace::PRIMARY_RESERVED_PADDING));
+        shares.extend(blob.to_shares().unwrap().iter().map(Share::to_vec));
+        shares.resize_with(16, || padding_share(Namespace::TAIL_PADDING));
+
+        let eds = ExtendedDataSquare::from_ods(shares, AppVersion::V3).unwrap();
+        let contents = eds.split_contents(AppVersion::V3).unwrap();
+
+        assert_eq!(contents.transactions, txs);
+        assert_eq!(contents.blobs, vec![blob]);
+    }
+
+    #[test]
+    fn split_contents_malformed_compact_shares() {
+        let mut shares = compact_shares(Namespace::TRANSACTION, &[random_bytes(100)]);
+        // claim a transaction longer than the sequence
+        let content_start = NS_SIZE + 1 + 4 + COMPACT_SHARE_RESERVED_BYTES;
+        shares[0][content_start] = 120;
+        shares.resize_with(4, || padding_share(Namespace::TAIL_PADDING));
+
+        let eds = ExtendedDataSquare::from_ods(shares, AppVersion::V3).unwrap();
+        let err = eds.split_contents(AppVersion::V3).unwrap_err();
+        assert!(matches!(err, Error::MalformedCompactShares));
+    }
+
+    #[test]
+    fn split_contents_oversized_sequence_length() {
+        let mut shares = compact_shares(Namespace::TRANSACTION, &[random_bytes(100)]);
+        // claim a sequence far longer than the square
+        let sequence_len_start = NS_SIZE + 1;
+        shares[0][sequence_len_start..sequence_len_start + 4]
+            .copy_from_slice(&u32::MAX.to_be_bytes());
+        shares.resize_with(4, || padding_share(Namespace::TAIL_PADDING));
+
+        let eds = ExtendedDataSquare::from_ods(shares, AppVersion::V3).unwrap();
+        let err = eds.split_contents(AppVersion::V3).unwrap_err();
+        assert!(matches!(err, Error::MissingShares));
+    }
+
+    /// Encode the transactions into compact shares of the namespace.
+    fn compact_shares(namespace: Namespace, txs: &[Vec<u8>]) -> Vec<Vec<u8>> {
+        let mut data = Vec::new();
+        for tx in txs {
+            encode_varint(tx.len() as u64, &mut data);
+            data.extend_from_slice(tx);
+        }
+
+        let sequence_len = data.len() as u32;
+        let mut data = &data[..];
+        let mut shares = Vec::new();
+
+        while !data.is_empty() {
+            let first = shares.is_empty();
+            let mut share = namespace.as_bytes().to_vec();
+            share.push(first as u8);
+            if first {
+                share.extend_from_slice(&sequence_len.to_be_bytes());
+            }
+            // location of the first unit isn't needed for parsing
+            share.extend_from_slice(&[0; COMPACT_SHARE_RESERVED_BYTES]);
+
+            let len = data.len().min(SHARE_SIZE - share.len());
+            share.extend_from_slice(&data[..len]);
+            share.resize(SHARE_SIZE, 0);
+            data = &data[len..];
+
+            shares.push(share);
+        }
+
+        shares
+    }
+
+    fn padding_share(namespace: Namespace) -> Vec<u8> {
+        [namespace.as_bytes(), &[1], &[0; SHARE_SIZE - NS_SIZE - 1]].concat()
+    }
 }
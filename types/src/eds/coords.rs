@@ -0,0 +1,197 @@
+//! Conversions between the linear indexes of shares and their `(row, column)`
+//! coordinates in the [`ExtendedDataSquare`].
+//!
+//! Shares in the square are indexed in a row-major order.
+//!
+//! [`ExtendedDataSquare`]: crate::eds::ExtendedDataSquare
+
+use crate::{Error, Result};
+
+/// Quadrant of the [`ExtendedDataSquare`].
+///
+/// [`ExtendedDataSquare`]: crate::eds::ExtendedDataSquare
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Quadrant {
+    /// Upper left quadrant, the original data square.
+    Q0,
+    /// Upper right quadrant, the row parity data of the original data.
+    Q1,
+    /// Lower left quadrant, the column parity data of the original data.
+    Q2,
+    /// Lower right quadrant, the parity data of the parity data.
+    Q3,
+}
+
+impl Quadrant {
+    /// Returns `true` if the quadrant holds parity data.
+    pub fn is_parity(&self) -> bool {
+        *self != Quadrant::Q0
+    }
+}
+
+/// Convert the coordinates of a share to its linear index.
+///
+/// # Errors
+///
+/// This function will return an error if coordinates are out of the square bounds.
+///
+/// # Example
+///
+/// ```
+/// use celestia_types::eds::coords::to_linear;
+///
+/// assert_eq!(to_linear(1, 2, 4).unwrap(), 6);
+/// assert!(to_linear(0, 4, 4).is_err());
+/// ```
+pub fn to_linear(row: u16, column: u16, square_width: u16) -> Result<usize> {
+    check_bounds(row, column, square_width)?;
+    Ok(usize::from(row) * usize::from(square_width) + usize::from(column))
+}
+
+/// Convert the linear index of a share to its `(row, column)` coordinates.
+///
+/// # Errors
+///
+/// This function will return an error if index is out of the square bounds.
+///
+/// # Example
+///
+/// ```
+/// use celestia_types::eds::coords::from_linear;
+///
+/// assert_eq!(from_linear(6, 4).unwrap(), (1, 2));
+/// assert!(from_linear(16, 4).is_err());
+/// ```
+pub fn from_linear(index: usize, square_width: u16) -> Result<(u16, u16)> {
+    let width = usize::from(square_width);
+    let size = width * width;
+
+    if index >= size {
+        return Err(Error::IndexOutOfRange(index, size));
+    }
+
+    // both fit into u16, as they are smaller than `square_width`
+    let row = (index / width) as u16;
+    let column = (index % width) as u16;
+
+    Ok((row, column))
+}
+
+/// Get the [`Quadrant`] which the share with given coordinates belongs to.
+///
+/// # Errors
+///
+/// This function will return an error if coordinates are out of the square bounds.
+pub fn quadrant(row: u16, column: u16, square_width: u16) -> Result<Quadrant> {
+    check_bounds(row, column, square_width)?;
+
+    let ods_width = square_width / 2;
+    let quadrant = match (row < ods_width, column < ods_width) {
+        (true, true) => Quadrant::Q0,
+        (true, false) => Quadrant::Q1,
+        (false, true) => Quadrant::Q2,
+        (false, false) => Quadrant::Q3,
+    };
+
+    Ok(quadrant)
+}
+
+/// Returns `true` if the share with given coordinates holds parity data.
+///
+/// # Errors
+///
+/// This function will return an error if coordinates are out of the square bounds.
+pub fn is_parity(row: u16, column: u16, square_width: u16) -> Result<bool> {
+    Ok(quadrant(row, column, square_width)?.is_parity())
+}
+
+fn check_bounds(row: u16, column: u16, square_width: u16) -> Result<()> {
+    if row >= square_width || column >= square_width {
+        return Err(Error::EdsIndexOutOfRange(row, column));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const WIDTHS: [u16; 4] = [2, 4, 8, 128];
+
+    #[test]
+    fn linear_round_trip() {
+        for width in WIDTHS {
+            let mut expected_index = 0;
+
+            for row in 0..width {
+                for column in 0..width {
+                    let index = to_linear(row, column, width).unwrap();
+
+                    assert_eq!(index, expected_index);
+                    assert_eq!(from_linear(index, width).unwrap(), (row, column));
+
+                    expected_index += 1;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        for width in WIDTHS {
+            let size = usize::from(width) * usize::from(width);
+
+            assert!(matches!(
+                to_linear(width, 0, width),
+                Err(Error::EdsIndexOutOfRange(..))
+            ));
+            assert!(matches!(
+                to_linear(0, width, width),
+                Err(Error::EdsIndexOutOfRange(..))
+            ));
+            assert!(matches!(
+                from_linear(size, width),
+                Err(Error::IndexOutOfRange(..))
+            ));
+            assert!(quadrant(width, width, width).is_err());
+            assert!(is_parity(0, width, width).is_err());
+        }
+
+        to_linear(0, 0, 0).unwrap_err();
+        from_linear(0, 0).unwrap_err();
+    }
+
+    #[test]
+    fn quadrants() {
+        for width in WIDTHS {
+            let half = width / 2;
+
+            for row in 0..width {
+                for column in 0..width {
+                    let expected = match (row < half, column < half) {
+                        (true, true) => Quadrant::Q0,
+                        (true, false) => Quadrant::Q1,
+                        (false, true) => Quadrant::Q2,
+                        (false, false) => Quadrant::Q3,
+                    };
+
+                    assert_eq!(quadrant(row, column, width).unwrap(), expected);
+                    assert_eq!(
+                        is_parity(row, column, width).unwrap(),
+                        expected != Quadrant::Q0
+                    );
+                }
+            }
+
+            // quadrant boundaries
+            assert_eq!(quadrant(half - 1, half - 1, width).unwrap(), Quadrant::Q0);
+            assert_eq!(quadrant(half - 1, half, width).unwrap(), Quadrant::Q1);
+            assert_eq!(quadrant(half, half - 1, width).unwrap(), Quadrant::Q2);
+            assert_eq!(quadrant(half, half, width).unwrap(), Quadrant::Q3);
+        }
+    }
+}
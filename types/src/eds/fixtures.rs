@@ -0,0 +1,148 @@
+//! Deterministic [`ExtendedDataSquare`] fixtures for tests.
+//!
+//! **WARNING: ALL FUNCTIONS PANIC! DO NOT USE IT IN PRODUCTION!**
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+use crate::consts::appconsts::{AppVersion, SHARE_SIZE};
+use crate::eds::ExtendedDataSquare;
+use crate::nmt::{Namespace, NS_SIZE};
+use crate::share::InfoByte;
+use crate::{Blob, DataAvailabilityHeader};
+
+/// [`ExtendedDataSquare`] together with its [`DataAvailabilityHeader`] and the blobs it holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdsFixture {
+    /// Generated square.
+    pub eds: ExtendedDataSquare,
+    /// Header with the roots of the generated square.
+    pub dah: DataAvailabilityHeader,
+    /// Blobs included in the square, in the order they are laid out.
+    pub blobs: Vec<Blob>,
+}
+
+/// Generate an [`EdsFixture`] with blobs of the given namespaces and sizes.
+///
+/// Data of the blobs is generated from the `seed`, so the same arguments always
+/// produce the same square. Blobs are laid out one after another in the original
+/// data square, sorted by namespace, and the rest of the square is filled with
+/// tail padding. The square has the smallest width that fits all the blobs.
+///
+/// # Example
+///
+/// ```
+/// use celestia_types::consts::appconsts::AppVersion;
+/// use celestia_types::eds::fixtures::generate;
+/// use celestia_types::nmt::Namespace;
+///
+/// let namespace = Namespace::new_v0(&[1, 2, 3]).unwrap();
+/// let fixture = generate(42, &[(namespace, 1024)], AppVersion::V2);
+///
+/// assert_eq!(fixture, generate(42, &[(namespace, 1024)], AppVersion::V2));
+/// assert_eq!(fixture.blobs[0].data.len(), 1024);
+/// ```
+pub fn generate(seed: u64, blobs: &[(Namespace, usize)], app_version: AppVersion) -> EdsFixture {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut blobs: Vec<_> = blobs
+        .iter()
+        .map(|(namespace, size)| {
+            let mut data = vec![0; *size];
+            rng.fill_bytes(&mut data);
+            Blob::new(*namespace, data, app_version).expect("invalid blob")
+        })
+        .collect();
+    // shares in the square must be sorted by namespace
+    blobs.sort_by_key(|blob| blob.namespace);
+
+    let mut shares: Vec<_> = blobs
+        .iter()
+        .flat_map(|blob| blob.to_shares().expect("invalid blob"))
+        .map(|share| share.to_vec())
+        .collect();
+
+    let mut ods_width = 1;
+    while ods_width * ods_width < shares.len() {
+        ods_width *= 2;
+    }
+
+    let tail_padding = [
+        Namespace::TAIL_PADDING.as_bytes(),
+        &[InfoByte::new(0, true).unwrap().as_u8()],
+        &[0; SHARE_SIZE - NS_SIZE - 1],
+    ]
+    .concat();
+    shares.resize(ods_width * ods_width, tail_padding);
+
+    let eds = ExtendedDataSquare::from_ods(shares, app_version).expect("invalid square");
+    let dah = DataAvailabilityHeader::from_eds(&eds);
+
+    EdsFixture { eds, dah, blobs }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValidateBasicWithAppVersion;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn namespaces() -> [Namespace; 2] {
+        [
+            Namespace::new_v0(&[1]).unwrap(),
+            Namespace::new_v0(&[2]).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn deterministic() {
+        let [ns1, ns2] = namespaces();
+        let blobs = [(ns2, 100), (ns1, 2000)];
+
+        let fixture = generate(1, &blobs, AppVersion::V2);
+
+        assert_eq!(fixture, generate(1, &blobs, AppVersion::V2));
+        assert_ne!(fixture.eds, generate(2, &blobs, AppVersion::V2).eds);
+        fixture.dah.validate_basic(AppVersion::V2).unwrap();
+    }
+
+    #[test]
+    fn blobs_layout() {
+        let [ns1, ns2] = namespaces();
+        let fixture = generate(1, &[(ns2, 100), (ns1, 2000), (ns2, 600)], AppVersion::V2);
+
+        let namespaces: Vec<_> = fixture.blobs.iter().map(|blob| blob.namespace).collect();
+        assert_eq!(namespaces, [ns1, ns2, ns2]);
+        assert_eq!(fixture.blobs[1].data.len(), 100);
+        assert_eq!(fixture.blobs[2].data.len(), 600);
+
+        let locations = fixture.eds.blob_locations(AppVersion::V2).unwrap();
+        let commitments: Vec<_> = locations.iter().map(|loc| loc.commitment).collect();
+        let expected: Vec<_> = fixture.blobs.iter().map(|blob| blob.commitment).collect();
+        assert_eq!(commitments, expected);
+    }
+
+    #[test]
+    fn verify_blob_inclusion() {
+        let [ns1, ns2] = namespaces();
+        let fixture = generate(7, &[(ns1, 3000), (ns2, 50)], AppVersion::V2);
+
+        for blob in &fixture.blobs {
+            let rows = fixture
+                .eds
+                .get_namespace_data(blob.namespace, &fixture.dah, 1)
+                .unwrap();
+            assert!(!rows.is_empty());
+
+            for (id, data) in &rows {
+                data.verify(*id, &fixture.dah).unwrap();
+            }
+
+            let shares = rows.iter().flat_map(|(_, data)| &data.shares);
+            let reconstructed = Blob::reconstruct(shares, AppVersion::V2).unwrap();
+            assert_eq!(&reconstructed, blob);
+        }
+    }
+}
@@ -102,6 +102,28 @@ pub enum Error {
     #[error("Invalid share size: {0}")]
     InvalidShareSize(usize),
 
+    /// Data share within the [`Namespace::PARITY_SHARE`].
+    ///
+    /// [`Namespace::PARITY_SHARE`]: crate::nmt::Namespace::PARITY_SHARE
+    #[error("Data share within parity namespace")]
+    DataShareInParityNamespace,
+
+    /// Share version other than zero within a reserved namespace.
+    #[error("Share version {0} is not allowed in reserved namespaces")]
+    ShareVersionInReservedNamespace(u8),
+
+    /// Padding share which doesn't start an empty sequence.
+    #[error("Padding share must start an empty sequence")]
+    InvalidPaddingShare,
+
+    /// Signer provided for a blob whose share version doesn't support it.
+    #[error("Signer is not supported in share version {0}")]
+    SignerNotSupported(u8),
+
+    /// Blob with share version one is missing a signer.
+    #[error("Share version 1 requires a signer")]
+    MissingSigner,
+
     /// Invalid nmt leaf size.
     #[error("Invalid nmt leaf size: {0}")]
     InvalidNmtLeafSize(usize),
@@ -220,10 +242,18 @@ pub enum Error {
     #[error("Invalid dimensions of EDS")]
     EdsInvalidDimentions,
 
+    /// Parity shares of the EDS axis don't match the encoding of its original data.
+    #[error("Parity shares of {0} {1} are incorrectly encoded")]
+    EdsBadEncoding(crate::eds::AxisType, u16),
+
     /// Zero block height.
     #[error("Invalid zero block height")]
     ZeroBlockHeight,
 
+    /// Invalid amount of headers requested.
+    #[error("Invalid amount of requested headers: {0}")]
+    InvalidHeaderRequestAmount(u64),
+
     /// Expected first share of a blob
     #[error("Expected first share of a blob")]
     ExpectedShareWithSequenceStart,
@@ -240,6 +270,18 @@ pub enum Error {
     #[error("Metadata mismatch between shares in blob: {0}")]
     BlobSharesMetadataMismatch(String),
 
+    /// Namespace of the blob is absent from the data square.
+    #[error("Blob namespace not found in the data square")]
+    BlobNamespaceNotFound,
+
+    /// No blob in the data square has a matching commitment.
+    #[error("Blob commitment doesn't match any blob in its namespace")]
+    BlobCommitmentMismatch,
+
+    /// Transaction in compact shares exceeds their sequence length.
+    #[error("Malformed compact shares")]
+    MalformedCompactShares,
+
     /// Blob too large, length must fit u32
     #[error("Blob too large")]
     BlobTooLarge,
@@ -251,6 +293,10 @@ pub enum Error {
     /// Empty blob list provided when creating MsgPayForBlobs
     #[error("Empty blob list")]
     EmptyBlobList,
+
+    /// Error propagated from the [`serde_json`].
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
 }
 
 impl From<prost::DecodeError> for Error {
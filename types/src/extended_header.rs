@@ -1,8 +1,4 @@
 use std::fmt::{Display, Formatter};
-#[cfg(any(
-    not(any(target_arch = "wasm32", target_arch = "riscv32")),
-    feature = "wasm-bindgen"
-))]
 use std::time::Duration;
 
 use celestia_proto::header::pb::ExtendedHeader as RawExtendedHeader;
@@ -17,6 +13,7 @@ use tendermint_proto::Protobuf;
 #[cfg(all(feature = "wasm-bindgen", target_arch = "wasm32"))]
 use wasm_bindgen::prelude::*;
 
+use crate::clock::{system_now, Clock};
 use crate::consts::appconsts::AppVersion;
 use crate::hash::Hash;
 use crate::trust_level::DEFAULT_TRUST_LEVEL;
@@ -31,12 +28,23 @@ pub type Validator = validator::Info;
 /// A collection of the tendermint validators.
 pub type ValidatorSet = validator::Set;
 
-#[cfg(any(
-    not(any(target_arch = "wasm32", target_arch = "riscv32")),
-    feature = "wasm-bindgen"
-))]
 const VERIFY_CLOCK_DRIFT: Duration = Duration::from_secs(10);
 
+/// JSON schema of the [`ExtendedHeader`], see [`ExtendedHeader::json_schema`].
+const JSON_SCHEMA: &str = include_str!("../schema/extended_header.json");
+
+/// A trust anchor, identifying a header which is trusted without verification.
+///
+/// Usually it is a hardcoded or user provided checkpoint from which the
+/// headers are verified forward, see [`ExtendedHeader::verify_against_trusted`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustedHeader {
+    /// Height of the trusted header.
+    pub height: u64,
+    /// Hash of the trusted header.
+    pub hash: Hash,
+}
+
 /// Block header together with the relevant Data Availability metadata.
 ///
 /// [`ExtendedHeader`]s are used to announce and describe the blocks
@@ -110,6 +118,27 @@ impl ExtendedHeader {
         Ok(header)
     }
 
+    /// Serialize the header to JSON, in the same representation as used by celestia-node.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserialize the header from JSON, in the same representation as used by celestia-node.
+    ///
+    /// Note that the header isn't validated, use [`ExtendedHeader::validate`] for that.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Get the JSON schema of the header, describing the representation used by
+    /// [`ExtendedHeader::to_json`] and celestia-node.
+    ///
+    /// Integers are accepted both as numbers and as strings, since celestia-node and
+    /// the tendermint types encode some of them differently.
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::from_str(JSON_SCHEMA).expect("schema must be valid JSON")
+    }
+
     /// Get the app version.
     ///
     /// # Errors
@@ -178,6 +207,53 @@ impl ExtendedHeader {
         self.commit.validate_basic()?;
         self.validator_set.validate_basic()?;
 
+        self.validate_dah()?;
+        self.verify_commit()?;
+
+        let app_version = self.app_version()?;
+        self.dah.validate_basic(app_version)?;
+
+        Ok(())
+    }
+
+    /// Check that the data root of the [`DataAvailabilityHeader`] matches the `data_hash`
+    /// of the header.
+    ///
+    /// This is a part of [`validate`], which should be preferred unless only the
+    /// data root is of interest.
+    ///
+    /// [`validate`]: ExtendedHeader::validate
+    pub fn validate_dah(&self) -> Result<()> {
+        let data_hash = self.header.data_hash.unwrap_or_default();
+
+        if self.dah.hash() != data_hash {
+            bail_validation!(
+                "dah hash ({}) != header dah hash ({})",
+                self.dah.hash(),
+                data_hash,
+            )
+        }
+
+        Ok(())
+    }
+
+    /// Verify the commit of the header.
+    ///
+    /// Checks that the validator set is the one committed in the header, that the commit
+    /// is for this header and that its signatures are valid and represent more than 2/3
+    /// of the voting power of the validator set.
+    ///
+    /// This is a part of [`validate`], which should be preferred unless only the
+    /// commit is of interest.
+    ///
+    /// # Errors
+    ///
+    /// If the signed voting power is insufficient, this function returns
+    /// [`VerificationError::NotEnoughVotingPower`].
+    ///
+    /// [`validate`]: ExtendedHeader::validate
+    /// [`VerificationError::NotEnoughVotingPower`]: crate::VerificationError::NotEnoughVotingPower
+    pub fn verify_commit(&self) -> Result<()> {
         // make sure the validator set is consistent with the header
         if self.validator_set.hash() != self.header.validators_hash {
             bail_validation!(
@@ -187,15 +263,6 @@ impl ExtendedHeader {
             )
         }
 
-        // ensure data root from raw header matches computed root
-        if self.dah.hash() != self.header.data_hash.unwrap_or_default() {
-            bail_validation!(
-                "dah hash ({}) != header dah hash ({})",
-                self.dah.hash(),
-                self.header.data_hash.unwrap_or_default(),
-            )
-        }
-
         // Make sure the header is consistent with the commit.
         if self.commit.height != self.height() {
             bail_validation!(
@@ -213,16 +280,8 @@ impl ExtendedHeader {
             )
         }
 
-        self.validator_set.verify_commit_light(
-            &self.header.chain_id,
-            &self.height(),
-            &self.commit,
-        )?;
-
-        let app_version = self.app_version()?;
-        self.dah.validate_basic(app_version)?;
-
-        Ok(())
+        self.validator_set
+            .verify_commit_light(&self.header.chain_id, &self.height(), &self.commit)
     }
 
     /// Verify an untrusted header.
@@ -237,6 +296,18 @@ impl ExtendedHeader {
     /// fail if the validator set commiting those blocks was changed. If that is the case,
     /// consider verifying the untrusted header with a more recent or even previous header.
     pub fn verify(&self, untrusted: &ExtendedHeader) -> Result<()> {
+        self.verify_at(untrusted, system_now())
+    }
+
+    /// Verify an untrusted header, using the [`Clock`] as the source of the current time.
+    ///
+    /// See [`ExtendedHeader::verify`] for details.
+    pub fn verify_with_clock(&self, untrusted: &ExtendedHeader, clock: &impl Clock) -> Result<()> {
+        self.verify_at(untrusted, Some(clock.now()))
+    }
+
+    /// Time dependent checks are skipped if `now` is unknown.
+    fn verify_at(&self, untrusted: &ExtendedHeader, now: Option<Time>) -> Result<()> {
         if untrusted.height() <= self.height() {
             bail_verification!(
                 "untrusted header height({}) <= current trusted header({})",
@@ -261,12 +332,7 @@ impl ExtendedHeader {
             );
         }
 
-        #[cfg(any(
-            not(any(target_arch = "wasm32", target_arch = "riscv32")),
-            feature = "wasm-bindgen"
-        ))]
-        {
-            let now = Time::now();
+        if let Some(now) = now {
             let valid_until = now.checked_add(VERIFY_CLOCK_DRIFT).unwrap();
 
             if !untrusted.time().before(valid_until) {
@@ -341,6 +407,22 @@ impl ExtendedHeader {
     /// assert!(genesis_header.verify_range(&next_headers).is_ok());
     /// ```
     pub fn verify_range(&self, untrusted: &[ExtendedHeader]) -> Result<()> {
+        self.verify_range_at(untrusted, system_now())
+    }
+
+    /// Verify a chain of adjacent untrusted headers, using the [`Clock`] as the source
+    /// of the current time.
+    ///
+    /// See [`ExtendedHeader::verify_range`] for details.
+    pub fn verify_range_with_clock(
+        &self,
+        untrusted: &[ExtendedHeader],
+        clock: &impl Clock,
+    ) -> Result<()> {
+        self.verify_range_at(untrusted, Some(clock.now()))
+    }
+
+    fn verify_range_at(&self, untrusted: &[ExtendedHeader], now: Option<Time>) -> Result<()> {
         let mut trusted = self;
 
         for (i, untrusted) in untrusted.iter().enumerate() {
@@ -355,7 +437,7 @@ impl ExtendedHeader {
                 );
             }
 
-            trusted.verify(untrusted)?;
+            trusted.verify_at(untrusted, now)?;
             trusted = untrusted;
         }
 
@@ -398,6 +480,26 @@ impl ExtendedHeader {
     /// genesis_header.verify_adjacent_range(&next_headers).unwrap();
     /// ```
     pub fn verify_adjacent_range(&self, untrusted: &[ExtendedHeader]) -> Result<()> {
+        self.verify_adjacent_range_at(untrusted, system_now())
+    }
+
+    /// Verify a chain of adjacent untrusted headers and make sure they are adjacent
+    /// to `self`, using the [`Clock`] as the source of the current time.
+    ///
+    /// See [`ExtendedHeader::verify_adjacent_range`] for details.
+    pub fn verify_adjacent_range_with_clock(
+        &self,
+        untrusted: &[ExtendedHeader],
+        clock: &impl Clock,
+    ) -> Result<()> {
+        self.verify_adjacent_range_at(untrusted, Some(clock.now()))
+    }
+
+    fn verify_adjacent_range_at(
+        &self,
+        untrusted: &[ExtendedHeader],
+        now: Option<Time>,
+    ) -> Result<()> {
         if untrusted.is_empty() {
             return Ok(());
         }
@@ -411,7 +513,111 @@ impl ExtendedHeader {
             );
         }
 
-        self.verify_range(untrusted)
+        self.verify_range_at(untrusted, now)
+    }
+
+    /// Verify that the header chains back to the trusted anchor.
+    ///
+    /// The header is [`validate`]d first, so that its data and commit are consistent
+    /// with the hash being checked. Then it must either be the trusted one, with matching
+    /// hash, or be adjacent to it and point to its hash. Headers further from the anchor
+    /// must be verified using the intermediate headers with
+    /// [`ExtendedHeader::verify_adjacent_range`].
+    ///
+    /// Additionally the header must be within the `trusting_period`, i.e. its time
+    /// plus the trusting period must not be in the past.
+    ///
+    /// # Errors
+    ///
+    /// If validation or verification fails, this function will return an error with
+    /// a reason of failure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use celestia_types::{ExtendedHeader, TrustedHeader};
+    /// # let s = include_str!("../test_data/chain3/extended_header_block_1_to_256.json");
+    /// # let headers: Vec<ExtendedHeader> = serde_json::from_str(s).unwrap();
+    /// let anchor = TrustedHeader {
+    ///     height: 1,
+    ///     hash: headers[0].hash(),
+    /// };
+    /// let trusting_period = Duration::from_secs(100 * 365 * 24 * 60 * 60);
+    ///
+    /// headers[1].verify_against_trusted(&anchor, trusting_period).unwrap();
+    /// ```
+    ///
+    /// [`validate`]: ExtendedHeader::validate
+    pub fn verify_against_trusted(
+        &self,
+        trusted: &TrustedHeader,
+        trusting_period: Duration,
+    ) -> Result<()> {
+        self.verify_against_trusted_at(trusted, trusting_period, system_now())
+    }
+
+    /// Verify that the header chains back to the trusted anchor, using the [`Clock`]
+    /// as the source of the current time.
+    ///
+    /// See [`ExtendedHeader::verify_against_trusted`] for details.
+    pub fn verify_against_trusted_with_clock(
+        &self,
+        trusted: &TrustedHeader,
+        trusting_period: Duration,
+        clock: &impl Clock,
+    ) -> Result<()> {
+        self.verify_against_trusted_at(trusted, trusting_period, Some(clock.now()))
+    }
+
+    fn verify_against_trusted_at(
+        &self,
+        trusted: &TrustedHeader,
+        trusting_period: Duration,
+        now: Option<Time>,
+    ) -> Result<()> {
+        self.validate()?;
+
+        let height = self.height().value();
+
+        if height == trusted.height {
+            if self.hash() != trusted.hash {
+                bail_verification!(
+                    "header hash ({}) != trusted hash ({})",
+                    self.hash(),
+                    trusted.hash
+                );
+            }
+        } else if height == trusted.height + 1 {
+            if self.last_header_hash() != trusted.hash {
+                bail_verification!(
+                    "expected header to point to trusted hash ({}), but got {}",
+                    trusted.hash,
+                    self.last_header_hash()
+                );
+            }
+        } else {
+            bail_verification!(
+                "header height ({}) is not adjacent to the trusted height ({})",
+                height,
+                trusted.height
+            );
+        }
+
+        if let Some(now) = now {
+            // Overflow means the header will never expire
+            if let Some(expires_at) = self.time().checked_add(trusting_period) {
+                if expires_at.before(now) {
+                    bail_verification!(
+                        "header is outside of the trusting period (expired at {}, now: {})",
+                        expires_at,
+                        now
+                    );
+                }
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -655,6 +861,20 @@ impl Serialize for ExtendedHeader {
     }
 }
 
+#[cfg(feature = "schemars")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+impl schemars::JsonSchema for ExtendedHeader {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "ExtendedHeader".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        ExtendedHeader::json_schema()
+            .try_into()
+            .expect("schema must be a JSON object")
+    }
+}
+
 impl<'de> Deserialize<'de> for ExtendedHeader {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -669,7 +889,10 @@ impl<'de> Deserialize<'de> for ExtendedHeader {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::MockClock;
     use crate::test_utils::{invalidate, unverify};
+    use crate::VerificationError;
+    use tendermint::block::CommitSig;
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test as test;
@@ -709,6 +932,165 @@ mod tests {
         serde_json::from_str(s).unwrap()
     }
 
+    #[test]
+    fn verify_against_trusted() {
+        let headers = sample_eh_chain_3_block_1_to_256();
+        let trusting_period = Duration::from_secs(100 * 365 * 24 * 60 * 60);
+        let anchor = TrustedHeader {
+            height: 1,
+            hash: headers[0].hash(),
+        };
+
+        headers[0]
+            .verify_against_trusted(&anchor, trusting_period)
+            .unwrap();
+        headers[1]
+            .verify_against_trusted(&anchor, trusting_period)
+            .unwrap();
+
+        // not adjacent to the anchor
+        headers[2]
+            .verify_against_trusted(&anchor, trusting_period)
+            .unwrap_err();
+
+        // expired
+        headers[1]
+            .verify_against_trusted(&anchor, Duration::from_secs(1))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn verify_against_trusted_tampered() {
+        let headers = sample_eh_chain_3_block_1_to_256();
+        let trusting_period = Duration::from_secs(100 * 365 * 24 * 60 * 60);
+        let anchor = TrustedHeader {
+            height: 1,
+            hash: headers[0].hash(),
+        };
+
+        // hash of the header stays the same, only the validation can catch it
+        let mut eh = headers[1].clone();
+        eh.dah = DataAvailabilityHeader::new_unchecked(vec![], vec![]);
+        eh.verify_against_trusted(&anchor, trusting_period)
+            .unwrap_err();
+
+        let mut eh = headers[1].clone();
+        for sig in &mut eh.commit.signatures {
+            *sig = CommitSig::BlockIdFlagAbsent;
+        }
+        eh.verify_against_trusted(&anchor, trusting_period)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn verify_against_trusted_mock_clock() {
+        let headers = sample_eh_chain_3_block_1_to_256();
+        let trusting_period = Duration::from_secs(60 * 60);
+        let anchor = TrustedHeader {
+            height: 1,
+            hash: headers[0].hash(),
+        };
+
+        let expires_at = headers[1].time().checked_add(trusting_period).unwrap();
+        let clock = MockClock::new(expires_at);
+
+        headers[1]
+            .verify_against_trusted_with_clock(&anchor, trusting_period, &clock)
+            .unwrap();
+
+        // just expired
+        clock.advance(Duration::from_nanos(1));
+        let e = headers[1]
+            .verify_against_trusted_with_clock(&anchor, trusting_period, &clock)
+            .unwrap_err();
+        assert!(matches!(e, Error::Verification(_)));
+    }
+
+    #[test]
+    fn verify_header_from_the_future_mock_clock() {
+        let headers = sample_eh_chain_3_block_1_to_256();
+        let clock = MockClock::new(headers[1].time());
+
+        headers[0].verify_with_clock(&headers[1], &clock).unwrap();
+
+        // the untrusted header is further in the future than the allowed clock drift
+        clock.set((headers[1].time() - VERIFY_CLOCK_DRIFT - Duration::from_secs(1)).unwrap());
+        let e = headers[0]
+            .verify_with_clock(&headers[1], &clock)
+            .unwrap_err();
+        assert!(matches!(e, Error::Verification(_)));
+        let e = headers[0]
+            .verify_adjacent_range_with_clock(&headers[1..10], &clock)
+            .unwrap_err();
+        assert!(matches!(e, Error::Verification(_)));
+    }
+
+    #[test]
+    fn verify_against_trusted_wrong_hash() {
+        let headers = sample_eh_chain_3_block_1_to_256();
+        let trusting_period = Duration::from_secs(100 * 365 * 24 * 60 * 60);
+        let anchor = TrustedHeader {
+            height: 1,
+            hash: headers[2].hash(),
+        };
+
+        let e = headers[0]
+            .verify_against_trusted(&anchor, trusting_period)
+            .unwrap_err();
+        assert!(matches!(e, Error::Verification(_)));
+
+        let e = headers[1]
+            .verify_against_trusted(&anchor, trusting_period)
+            .unwrap_err();
+        assert!(matches!(e, Error::Verification(_)));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let s = include_str!("../test_data/chain1/extended_header_block_27.json");
+        let header = ExtendedHeader::from_json(s).unwrap();
+
+        let json = header.to_json().unwrap();
+        let decoded = ExtendedHeader::from_json(&json).unwrap();
+
+        assert_eq!(decoded, header);
+        assert_eq!(decoded.to_json().unwrap(), json);
+        decoded.validate().unwrap();
+    }
+
+    #[test]
+    fn json_schema_accepts_headers() {
+        let validator = jsonschema::validator_for(&ExtendedHeader::json_schema()).unwrap();
+        let s = include_str!("../test_data/chain3/extended_header_block_1_to_256.json");
+        let captured: Vec<serde_json::Value> = serde_json::from_str(s).unwrap();
+
+        for json in captured {
+            assert!(validator.is_valid(&json));
+
+            let header: ExtendedHeader = serde_json::from_value(json).unwrap();
+            let serialized = serde_json::to_value(&header).unwrap();
+            assert!(validator.is_valid(&serialized));
+        }
+    }
+
+    #[test]
+    fn json_schema_rejects_malformed_headers() {
+        let validator = jsonschema::validator_for(&ExtendedHeader::json_schema()).unwrap();
+        let header = serde_json::to_value(sample_eh_chain_1_block_27()).unwrap();
+
+        let mut missing_dah = header.clone();
+        missing_dah.as_object_mut().unwrap().remove("dah");
+        assert!(!validator.is_valid(&missing_dah));
+
+        let mut invalid_hash = header.clone();
+        invalid_hash["header"]["data_hash"] = "not a hash".into();
+        assert!(!validator.is_valid(&invalid_hash));
+
+        let mut invalid_height = header;
+        invalid_height["header"]["height"] = "27th".into();
+        assert!(!validator.is_valid(&invalid_height));
+    }
+
     #[test]
     fn validate_correct() {
         sample_eh_chain_1_block_1().validate().unwrap();
@@ -736,6 +1118,20 @@ mod tests {
         eh.validate().unwrap_err();
     }
 
+    #[test]
+    fn validate_dah() {
+        let eh = sample_eh_chain_1_block_27();
+        eh.validate_dah().unwrap();
+
+        let mut mismatched = eh.clone();
+        mismatched.dah = DataAvailabilityHeader::new_unchecked(vec![], vec![]);
+        mismatched.validate_dah().unwrap_err();
+
+        let mut mismatched = eh;
+        mismatched.header.data_hash = None;
+        mismatched.validate_dah().unwrap_err();
+    }
+
     #[test]
     fn validate_commit_height_mismatch() {
         let mut eh = sample_eh_chain_1_block_27();
@@ -752,6 +1148,39 @@ mod tests {
         eh.validate().unwrap_err();
     }
 
+    #[test]
+    fn verify_commit() {
+        sample_eh_chain_1_block_27().verify_commit().unwrap();
+
+        for eh in sample_eh_chain_3_block_1_to_256() {
+            eh.verify_commit().unwrap();
+        }
+    }
+
+    #[test]
+    fn verify_commit_validator_hash_mismatch() {
+        let mut eh = sample_eh_chain_1_block_27();
+        eh.header.validators_hash = Hash::None;
+
+        eh.verify_commit().unwrap_err();
+    }
+
+    #[test]
+    fn verify_commit_votes_removed() {
+        let mut eh = sample_eh_chain_1_block_27();
+
+        for sig in &mut eh.commit.signatures {
+            *sig = CommitSig::BlockIdFlagAbsent;
+        }
+
+        assert!(matches!(
+            eh.verify_commit(),
+            Err(Error::Verification(
+                VerificationError::NotEnoughVotingPower(0, _)
+            ))
+        ));
+    }
+
     #[test]
     fn verify() {
         let eh_block_1 = sample_eh_chain_1_block_1();
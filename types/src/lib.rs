@@ -4,6 +4,7 @@
 pub mod blob;
 pub mod block;
 mod byzantine;
+pub mod clock;
 pub mod consts;
 mod data_availability_header;
 pub mod eds;
@@ -12,6 +13,7 @@ mod extended_header;
 pub mod fraud_proof;
 pub mod hash;
 mod merkle_proof;
+mod network_params;
 pub mod nmt;
 #[cfg(feature = "p2p")]
 #[cfg_attr(docsrs, doc(cfg(feature = "p2p")))]
@@ -34,11 +36,12 @@ pub use crate::blob::{Blob, Commitment};
 pub use crate::block::Height;
 pub use crate::consts::appconsts::AppVersion;
 pub use crate::data_availability_header::*;
-pub use crate::eds::{AxisType, ExtendedDataSquare};
+pub use crate::eds::{AxisType, BlobLocation, BlockContents, EdsLayout, ExtendedDataSquare};
 pub use crate::error::*;
 pub use crate::extended_header::*;
 pub use crate::fraud_proof::FraudProof;
 pub use crate::merkle_proof::MerkleProof;
+pub use crate::network_params::NetworkParams;
 pub use crate::share::*;
 pub use crate::sync::*;
 pub use crate::validate::*;
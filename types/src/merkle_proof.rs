@@ -216,6 +216,7 @@ mod tests {
     use tendermint::merkle::simple_hash_from_byte_vectors;
 
     use crate::test_utils::random_bytes;
+    use crate::Error;
 
     use super::MerkleProof;
 
@@ -239,6 +240,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn verify_swapped_aunts() {
+        let leaves: Vec<_> = (0..8).map(|_| random_bytes(64)).collect();
+        let (mut proof, root) = MerkleProof::new(3, &leaves).unwrap();
+
+        proof.verify(&leaves[3], root).unwrap();
+
+        proof.aunts.swap(0, 1);
+        let e = proof.verify(&leaves[3], root).unwrap_err();
+
+        assert!(matches!(e, Error::RootMismatch));
+    }
+
     #[test]
     fn tendermint_compatibility() {
         for _ in 0..100 {
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use crate::hash::Hash;
+use crate::{bail_verification, ExtendedHeader, Result, TrustedHeader};
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// Parameters of a Celestia network.
+///
+/// Those are needed to make sure that the headers are coming
+/// from the network we expect.
+///
+/// # Example
+///
+/// ```
+/// # use celestia_types::{ExtendedHeader, NetworkParams};
+/// # fn fetch_genesis_header() -> ExtendedHeader {
+/// #     let s = include_str!("../test_data/chain1/extended_header_block_1.json");
+/// #     serde_json::from_str(s).unwrap()
+/// # }
+/// let genesis_header = fetch_genesis_header();
+///
+/// // header doesn't come from the mainnet
+/// assert!(NetworkParams::MAINNET.verify_header(&genesis_header).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkParams {
+    /// Id of the chain.
+    pub chain_id: &'static str,
+    /// Hash of the genesis block.
+    pub genesis_hash: Hash,
+    /// Window of time in which the blocks are expected to be sampled and available.
+    pub sampling_window: Duration,
+}
+
+impl NetworkParams {
+    /// Parameters of the Celestia mainnet.
+    pub const MAINNET: NetworkParams = NetworkParams {
+        chain_id: "celestia",
+        genesis_hash: Hash::Sha256([
+            0x6b, 0xe3, 0x9e, 0xfd, 0x10, 0xba, 0x41, 0x2a, 0x9d, 0xb5, 0x28, 0x84, 0x88, 0x30,
+            0x3f, 0x5d, 0xd3, 0x2c, 0xf3, 0x86, 0x70, 0x7a, 0x5b, 0xef, 0x33, 0x61, 0x7f, 0x4c,
+            0x43, 0x30, 0x18, 0x72,
+        ]),
+        sampling_window: Duration::from_secs(30 * DAY),
+    };
+
+    /// Parameters of the Arabica testnet.
+    pub const ARABICA: NetworkParams = NetworkParams {
+        chain_id: "arabica-11",
+        genesis_hash: Hash::Sha256([
+            0x27, 0x12, 0x25, 0x93, 0x76, 0x5e, 0x07, 0x32, 0x9b, 0xc3, 0x48, 0xe8, 0xd1, 0x6e,
+            0x92, 0xdc, 0xb4, 0xc7, 0x5b, 0x34, 0xcc, 0xcb, 0x35, 0xc6, 0x40, 0xfd, 0x7a, 0x44,
+            0x84, 0xd4, 0xc7, 0x11,
+        ]),
+        sampling_window: Duration::from_secs(30 * DAY),
+    };
+
+    /// Parameters of the Mocha testnet.
+    pub const MOCHA: NetworkParams = NetworkParams {
+        chain_id: "mocha-4",
+        genesis_hash: Hash::Sha256([
+            0xb9, 0x3b, 0xbe, 0x20, 0xa0, 0xfb, 0xfd, 0xf9, 0x55, 0x81, 0x1b, 0x64, 0x20, 0xf8,
+            0x43, 0x39, 0x04, 0x66, 0x4d, 0x45, 0xdb, 0x4b, 0xf5, 0x10, 0x22, 0xbe, 0x42, 0x00,
+            0xc1, 0xa1, 0x68, 0x0d,
+        ]),
+        sampling_window: Duration::from_secs(30 * DAY),
+    };
+
+    /// Parameters of all the canonical Celestia networks.
+    pub const CANONICAL: [NetworkParams; 3] = [
+        NetworkParams::MAINNET,
+        NetworkParams::ARABICA,
+        NetworkParams::MOCHA,
+    ];
+
+    /// Get the parameters of the canonical network with the given chain id.
+    pub fn from_chain_id(chain_id: &str) -> Option<NetworkParams> {
+        NetworkParams::CANONICAL
+            .into_iter()
+            .find(|params| params.chain_id == chain_id)
+    }
+
+    /// Get the genesis header of the network as a trust anchor.
+    pub const fn genesis_anchor(&self) -> TrustedHeader {
+        TrustedHeader {
+            height: 1,
+            hash: self.genesis_hash,
+        }
+    }
+
+    /// Verify that the header belongs to the network.
+    ///
+    /// Checks that the header has a matching chain id and, if it is
+    /// a genesis header, that it has the expected hash.
+    ///
+    /// # Errors
+    ///
+    /// If verification fails, this function will return an error with a reason of failure.
+    pub fn verify_header(&self, header: &ExtendedHeader) -> Result<()> {
+        if header.chain_id().as_str() != self.chain_id {
+            bail_verification!(
+                "header has different chain {}, not {}",
+                header.chain_id(),
+                self.chain_id
+            );
+        }
+
+        if header.height().value() == 1 && header.hash() != self.genesis_hash {
+            bail_verification!(
+                "genesis header hash ({}) != expected genesis hash ({})",
+                header.hash(),
+                self.genesis_hash
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_eh_chain_1_block_1() -> ExtendedHeader {
+        let s = include_str!("../test_data/chain1/extended_header_block_1.json");
+        serde_json::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn canonical_networks_have_chain_id() {
+        for params in NetworkParams::CANONICAL {
+            assert!(!params.chain_id.is_empty());
+            assert_eq!(NetworkParams::from_chain_id(params.chain_id), Some(params));
+        }
+    }
+
+    #[test]
+    fn genesis_anchor() {
+        let genesis = sample_eh_chain_1_block_1();
+        let params = NetworkParams {
+            chain_id: "private",
+            genesis_hash: genesis.hash(),
+            ..NetworkParams::MAINNET
+        };
+        let trusting_period = Duration::from_secs(100 * 365 * DAY);
+
+        genesis
+            .verify_against_trusted(&params.genesis_anchor(), trusting_period)
+            .unwrap();
+        genesis
+            .verify_against_trusted(&NetworkParams::MAINNET.genesis_anchor(), trusting_period)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn unknown_chain_id() {
+        assert_eq!(NetworkParams::from_chain_id("private"), None);
+    }
+
+    #[test]
+    fn verify_header_chain_id() {
+        let genesis = sample_eh_chain_1_block_1();
+
+        NetworkParams::MAINNET.verify_header(&genesis).unwrap_err();
+
+        let params = NetworkParams {
+            chain_id: "private",
+            genesis_hash: genesis.hash(),
+            ..NetworkParams::MAINNET
+        };
+        params.verify_header(&genesis).unwrap();
+    }
+
+    #[test]
+    fn verify_header_genesis_hash() {
+        let genesis = sample_eh_chain_1_block_1();
+
+        let params = NetworkParams {
+            chain_id: "private",
+            ..NetworkParams::MAINNET
+        };
+        params.verify_header(&genesis).unwrap_err();
+    }
+}
@@ -156,7 +156,9 @@ impl Namespace {
     /// # Errors
     ///
     /// This function will return an error if the slice length is different than
-    /// [`NS_SIZE`] or if the namespace is invalid. If you are constructing the
+    /// [`NS_SIZE`] or if the namespace is invalid, e.g. a version `0` namespace
+    /// without the mandatory 18x`0x00` bytes id prefix, in which case
+    /// [`Error::InvalidNamespaceV0`] is returned. If you are constructing the
     /// version `0` namespace, check [`new_v0`] for more details.
     ///
     /// # Example
@@ -729,6 +731,18 @@ mod tests {
         assert_eq!(nid, expected_nid);
     }
 
+    #[test]
+    fn namespace_id_v0_from_raw_bytes_with_invalid_prefix() {
+        let e = Namespace::from_raw(&[
+            0, // version
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // prefix
+            1, 2, 3, 4, 5, 6, 7, 8, 9, 10, // id
+        ])
+        .unwrap_err();
+
+        assert!(matches!(e, Error::InvalidNamespaceV0));
+    }
+
     #[test]
     fn namespace_id_with_28_raw_bytes() {
         let e = Namespace::from_raw(&[
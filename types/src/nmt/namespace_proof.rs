@@ -97,6 +97,24 @@ impl NamespaceProof {
         }
     }
 
+    /// Create an empty proof of absence.
+    ///
+    /// This is a proof for a [`Namespace`] which is not in the range of the tree
+    /// root [`NamespacedHash`], so there are no siblings nor leaf needed to prove it.
+    ///
+    /// [`Namespace`]: crate::nmt::Namespace
+    /// [`NamespacedHash`]: crate::nmt::NamespacedHash
+    pub(crate) fn empty_absence() -> Self {
+        NamespaceProof(NmtNamespaceProof::AbsenceProof {
+            proof: NmtProof {
+                siblings: Vec::new(),
+                range: 0..0,
+            },
+            ignore_max_ns: true,
+            leaf: None,
+        })
+    }
+
     /// Returns total amount of leaves in a tree for which proof was constructed.
     ///
     /// This method only works if the proof is created for a single leaf and it
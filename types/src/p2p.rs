@@ -1,6 +1,9 @@
 //! Types related to the p2p layer of nodes in Celestia.
 
-use multiaddr::Multiaddr;
+pub mod addr;
+pub mod header_request;
+
+pub use multiaddr::Multiaddr;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::collections::HashMap;
@@ -0,0 +1,320 @@
+//! Utilities for validating and normalizing [`Multiaddr`]s.
+
+use std::future::Future;
+
+use libp2p_identity::PeerId;
+use multiaddr::{Multiaddr, Protocol};
+
+/// Representation of the errors that can occur when processing [`Multiaddr`]s.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AddrError {
+    /// Multiaddr doesn't contain a peer id.
+    #[error("Multiaddr doesn't contain peer id: {0}")]
+    MissingPeerId(Multiaddr),
+
+    /// No multiaddrs were provided.
+    #[error("No multiaddrs provided")]
+    NoAddresses,
+}
+
+/// A transport protocol used by a [`Multiaddr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// TCP, optionally with websockets on top of it.
+    Tcp,
+    /// QUIC, either draft-29 or v1.
+    Quic,
+    /// WebTransport on top of QUIC.
+    WebTransport,
+    /// WebRTC.
+    WebRtc,
+}
+
+impl Transport {
+    /// Check whether the [`Multiaddr`] uses this transport.
+    pub fn is_used_by(&self, ma: &Multiaddr) -> bool {
+        match self {
+            Transport::Tcp => ma.iter().any(|p| matches!(p, Protocol::Tcp(_))),
+            Transport::Quic => {
+                ma.iter()
+                    .any(|p| matches!(p, Protocol::Quic(_) | Protocol::QuicV1(_)))
+                    && !Transport::WebTransport.is_used_by(ma)
+            }
+            Transport::WebTransport => ma.iter().any(|p| matches!(p, Protocol::WebTransport)),
+            Transport::WebRtc => ma
+                .iter()
+                .any(|p| matches!(p, Protocol::WebRTC | Protocol::WebRTCDirect)),
+        }
+    }
+}
+
+/// Get the [`PeerId`] of the [`Multiaddr`].
+///
+/// # Errors
+///
+/// Returns [`AddrError::MissingPeerId`] if the multiaddr doesn't contain peer id.
+pub fn require_peer_id(ma: &Multiaddr) -> Result<PeerId, AddrError> {
+    ma.iter()
+        .find_map(|p| match p {
+            Protocol::P2p(peer_id) => Some(peer_id),
+            _ => None,
+        })
+        .ok_or_else(|| AddrError::MissingPeerId(ma.clone()))
+}
+
+/// Remove the peer id from the [`Multiaddr`], leaving only the transport address.
+pub fn strip_peer_id(ma: &Multiaddr) -> Multiaddr {
+    ma.iter()
+        .filter(|p| !matches!(p, Protocol::P2p(_)))
+        .collect()
+}
+
+/// Pick the first [`Multiaddr`] using given transport, or the first one if none of them does.
+///
+/// # Errors
+///
+/// Returns [`AddrError::NoAddresses`] if `addrs` is empty.
+pub fn prefer_transport(
+    addrs: &[Multiaddr],
+    transport: Transport,
+) -> Result<&Multiaddr, AddrError> {
+    addrs
+        .iter()
+        .find(|ma| transport.is_used_by(ma))
+        .or_else(|| addrs.first())
+        .ok_or(AddrError::NoAddresses)
+}
+
+/// Get the host of the `/dnsaddr` [`Multiaddr`].
+///
+/// Returns `None` if the multiaddr isn't a `/dnsaddr` one.
+pub fn dnsaddr_host(ma: &Multiaddr) -> Option<String> {
+    match ma.iter().next()? {
+        Protocol::Dnsaddr(host) => Some(host.into_owned()),
+        _ => None,
+    }
+}
+
+/// Parse a [`Multiaddr`] from the TXT record of `_dnsaddr.<host>` domain.
+///
+/// Returns `None` if the record isn't a valid `dnsaddr=<multiaddr>` entry.
+pub fn parse_dnsaddr_txt(record: &str) -> Option<Multiaddr> {
+    record.strip_prefix("dnsaddr=")?.parse().ok()
+}
+
+/// Expand the `/dnsaddr` entries using the provided resolver.
+///
+/// The resolver receives the host of each `/dnsaddr` multiaddr and should return
+/// the multiaddrs from its TXT records, see [`parse_dnsaddr_txt`]. Other multiaddrs
+/// are returned as they are, and the order is preserved.
+pub async fn resolve_dnsaddrs<F, Fut, E>(
+    addrs: Vec<Multiaddr>,
+    mut resolve: F,
+) -> Result<Vec<Multiaddr>, E>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<Vec<Multiaddr>, E>>,
+{
+    let mut resolved = Vec::with_capacity(addrs.len());
+
+    for ma in addrs {
+        match dnsaddr_host(&ma) {
+            Some(host) => resolved.extend(resolve(host).await?),
+            None => resolved.push(ma),
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Keep only the multiaddrs that can be dialed to reach the peer.
+///
+/// Multiaddrs without a peer id get it appended, while the ones with a different
+/// peer id and unresolved `/dnsaddr` ones are dropped. Duplicates are removed
+/// keeping the order of first occurrences.
+pub fn dialable_addrs<I>(addrs: I, peer_id: PeerId) -> Vec<Multiaddr>
+where
+    I: IntoIterator<Item = Multiaddr>,
+{
+    let mut dialable: Vec<Multiaddr> = Vec::new();
+
+    for mut ma in addrs {
+        if dnsaddr_host(&ma).is_some() {
+            continue;
+        }
+
+        match require_peer_id(&ma) {
+            Ok(id) if id != peer_id => continue,
+            Ok(_) => (),
+            Err(_) => ma.push(Protocol::P2p(peer_id)),
+        }
+
+        if !dialable.contains(&ma) {
+            dialable.push(ma);
+        }
+    }
+
+    dialable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEER_ID: &str = "12D3KooWGqwzdEqM54Dce6LXzfFr97Bnhvm6rN7KM7MFwdomfm4S";
+
+    fn tcp() -> Multiaddr {
+        format!("/ip4/127.0.0.1/tcp/2121/p2p/{PEER_ID}")
+            .parse()
+            .unwrap()
+    }
+
+    fn quic() -> Multiaddr {
+        format!("/ip4/127.0.0.1/udp/2121/quic-v1/p2p/{PEER_ID}")
+            .parse()
+            .unwrap()
+    }
+
+    fn webtransport() -> Multiaddr {
+        format!("/ip4/127.0.0.1/udp/2121/quic-v1/webtransport/p2p/{PEER_ID}")
+            .parse()
+            .unwrap()
+    }
+
+    fn dnsaddr() -> Multiaddr {
+        format!("/dnsaddr/da-bootstrapper-1.celestia-bootstrap.net/p2p/{PEER_ID}")
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn peer_id() {
+        let expected: PeerId = PEER_ID.parse().unwrap();
+
+        for ma in [tcp(), quic(), webtransport(), dnsaddr()] {
+            assert_eq!(require_peer_id(&ma).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn missing_peer_id() {
+        let ma: Multiaddr = "/ip4/127.0.0.1/tcp/2121".parse().unwrap();
+
+        assert_eq!(
+            require_peer_id(&ma).unwrap_err(),
+            AddrError::MissingPeerId(ma.clone())
+        );
+        assert_eq!(strip_peer_id(&ma), ma);
+    }
+
+    #[test]
+    fn strip() {
+        assert_eq!(
+            strip_peer_id(&tcp()),
+            "/ip4/127.0.0.1/tcp/2121".parse().unwrap()
+        );
+        assert_eq!(
+            strip_peer_id(&quic()),
+            "/ip4/127.0.0.1/udp/2121/quic-v1".parse().unwrap()
+        );
+        assert_eq!(
+            strip_peer_id(&dnsaddr()),
+            "/dnsaddr/da-bootstrapper-1.celestia-bootstrap.net"
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn transports() {
+        assert!(Transport::Tcp.is_used_by(&tcp()));
+        assert!(!Transport::Quic.is_used_by(&tcp()));
+
+        assert!(Transport::Quic.is_used_by(&quic()));
+        assert!(!Transport::Tcp.is_used_by(&quic()));
+
+        assert!(Transport::WebTransport.is_used_by(&webtransport()));
+        assert!(!Transport::Quic.is_used_by(&webtransport()));
+
+        // dnsaddr needs to be resolved first
+        for transport in [Transport::Tcp, Transport::Quic, Transport::WebTransport] {
+            assert!(!transport.is_used_by(&dnsaddr()));
+        }
+    }
+
+    #[test]
+    fn prefer() {
+        let addrs = [tcp(), webtransport(), quic()];
+
+        assert_eq!(prefer_transport(&addrs, Transport::Quic).unwrap(), &quic());
+        assert_eq!(prefer_transport(&addrs, Transport::Tcp).unwrap(), &tcp());
+        assert_eq!(
+            prefer_transport(&addrs, Transport::WebTransport).unwrap(),
+            &webtransport()
+        );
+        // fallback to the first one
+        assert_eq!(prefer_transport(&addrs, Transport::WebRtc).unwrap(), &tcp());
+
+        assert_eq!(
+            prefer_transport(&[], Transport::Tcp).unwrap_err(),
+            AddrError::NoAddresses
+        );
+    }
+
+    #[test]
+    fn dnsaddr_records() {
+        assert_eq!(
+            dnsaddr_host(&dnsaddr()).as_deref(),
+            Some("da-bootstrapper-1.celestia-bootstrap.net")
+        );
+        assert_eq!(dnsaddr_host(&tcp()), None);
+
+        let record = format!("dnsaddr=/ip4/127.0.0.1/tcp/2121/p2p/{PEER_ID}");
+        assert_eq!(parse_dnsaddr_txt(&record), Some(tcp()));
+        assert_eq!(parse_dnsaddr_txt("dnsaddr=invalid"), None);
+        assert_eq!(parse_dnsaddr_txt("v=spf1 -all"), None);
+    }
+
+    #[test]
+    fn resolve_and_filter() {
+        let peer_id: PeerId = PEER_ID.parse().unwrap();
+        let other = "12D3KooWSqZaLcn5Guypo2mrHr297YPJnV8KMEMXNjs3qAS8msw8";
+        let other_tcp: Multiaddr = format!("/ip4/10.0.0.1/tcp/2121/p2p/{other}")
+            .parse()
+            .unwrap();
+        let no_peer_id: Multiaddr = "/ip4/127.0.0.1/udp/2121/quic-v1".parse().unwrap();
+        let nested: Multiaddr = "/dnsaddr/nested.example.com".parse().unwrap();
+
+        let addrs = vec![dnsaddr(), no_peer_id.clone(), other_tcp.clone(), tcp()];
+
+        let resolved = futures::executor::block_on(resolve_dnsaddrs(addrs, |host| {
+            assert_eq!(host, "da-bootstrapper-1.celestia-bootstrap.net");
+            let records = [
+                format!("dnsaddr=/ip4/127.0.0.1/tcp/2121/p2p/{PEER_ID}"),
+                "dnsaddr=not-a-multiaddr".to_string(),
+                format!("dnsaddr=/ip4/10.0.0.1/tcp/2121/p2p/{other}"),
+                "dnsaddr=/dnsaddr/nested.example.com".to_string(),
+            ];
+            let addrs = records
+                .iter()
+                .filter_map(|r| parse_dnsaddr_txt(r))
+                .collect();
+            async move { Ok::<_, ()>(addrs) }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            resolved,
+            vec![
+                tcp(),
+                other_tcp.clone(),
+                nested,
+                no_peer_id,
+                other_tcp,
+                tcp()
+            ]
+        );
+
+        assert_eq!(dialable_addrs(resolved, peer_id), vec![tcp(), quic()]);
+    }
+}
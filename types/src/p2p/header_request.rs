@@ -0,0 +1,109 @@
+//! Constructors of the `header-ex` [`HeaderRequest`]s.
+
+pub use celestia_proto::p2p::pb::HeaderRequest;
+
+use celestia_proto::p2p::pb::header_request::Data;
+
+use crate::hash::Hash;
+use crate::{Error, Result};
+
+/// Extension trait for constructing valid [`HeaderRequest`]s.
+pub trait HeaderRequestExt {
+    /// Request `amount` consecutive headers, starting at the given `height`.
+    ///
+    /// Returns an error if `height` or `amount` is zero.
+    fn by_height(height: u64, amount: u64) -> Result<HeaderRequest>;
+
+    /// Request the header with the given `hash`.
+    fn by_hash(hash: Hash) -> HeaderRequest;
+
+    /// Request the `amount` of most recent headers.
+    ///
+    /// Peers serve only a single header for the head request, so any
+    /// `amount` other than 1 results in an error.
+    fn head(amount: u64) -> Result<HeaderRequest>;
+}
+
+impl HeaderRequestExt for HeaderRequest {
+    fn by_height(height: u64, amount: u64) -> Result<HeaderRequest> {
+        // Origin of 0 is reserved for the head request.
+        if height == 0 {
+            return Err(Error::ZeroBlockHeight);
+        }
+
+        if amount == 0 {
+            return Err(Error::InvalidHeaderRequestAmount(amount));
+        }
+
+        Ok(HeaderRequest {
+            amount,
+            data: Some(Data::Origin(height)),
+        })
+    }
+
+    fn by_hash(hash: Hash) -> HeaderRequest {
+        HeaderRequest {
+            amount: 1,
+            data: Some(Data::Hash(hash.as_bytes().to_vec())),
+        }
+    }
+
+    fn head(amount: u64) -> Result<HeaderRequest> {
+        if amount != 1 {
+            return Err(Error::InvalidHeaderRequestAmount(amount));
+        }
+
+        Ok(HeaderRequest {
+            amount,
+            data: Some(Data::Origin(0)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn by_height() {
+        let req = HeaderRequest::by_height(5, 10).unwrap();
+        assert_eq!(req.data, Some(Data::Origin(5)));
+        assert_eq!(req.amount, 10);
+
+        assert!(matches!(
+            HeaderRequest::by_height(5, 0).unwrap_err(),
+            Error::InvalidHeaderRequestAmount(0)
+        ));
+        assert!(matches!(
+            HeaderRequest::by_height(0, 1).unwrap_err(),
+            Error::ZeroBlockHeight
+        ));
+    }
+
+    #[test]
+    fn by_hash() {
+        let hash = Hash::Sha256([0xab; 32]);
+        let req = HeaderRequest::by_hash(hash);
+        assert_eq!(req.data, Some(Data::Hash(vec![0xab; 32])));
+        assert_eq!(req.amount, 1);
+    }
+
+    #[test]
+    fn head() {
+        let req = HeaderRequest::head(1).unwrap();
+        assert_eq!(req.data, Some(Data::Origin(0)));
+        assert_eq!(req.amount, 1);
+
+        assert!(matches!(
+            HeaderRequest::head(0).unwrap_err(),
+            Error::InvalidHeaderRequestAmount(0)
+        ));
+        assert!(matches!(
+            HeaderRequest::head(2).unwrap_err(),
+            Error::InvalidHeaderRequestAmount(2)
+        ));
+    }
+}
@@ -64,6 +64,22 @@ impl Row {
         Ok(Row { shares })
     }
 
+    /// Create Row from the shares of its left half, computing the parity shares.
+    ///
+    /// Left half of the first half of the rows contains the original data.
+    ///
+    /// # Errors
+    ///
+    /// This function will propagate errors from [`Row::from_raw`].
+    pub fn from_left_half(id: RowId, shares: Vec<Share>) -> Result<Self> {
+        let raw = RawRow {
+            shares_half: shares.into_iter().map(RawShare::from).collect(),
+            half_side: RawHalfSide::Left.into(),
+        };
+
+        Self::from_raw(id, raw)
+    }
+
     /// Verify the row against roots from DAH
     pub fn verify(&self, id: RowId, dah: &DataAvailabilityHeader) -> Result<()> {
         let row = id.index;
@@ -277,6 +293,23 @@ mod tests {
         assert_eq!(row_id, deserialized_row_id);
     }
 
+    #[test]
+    fn from_left_half() {
+        let eds = generate_eds(8, AppVersion::V2);
+        let dah = DataAvailabilityHeader::from_eds(&eds);
+
+        for index in 0..4 {
+            let id = RowId::new(index, 1).unwrap();
+            let mut shares = eds.row(index).unwrap();
+            shares.truncate(4);
+
+            let row = Row::from_left_half(id, shares).unwrap();
+
+            assert_eq!(row.shares, eds.row(index).unwrap());
+            row.verify(id, &dah).unwrap();
+        }
+    }
+
     #[test]
     fn index_calculation() {
         let shares = vec![vec![0; SHARE_SIZE]; 8 * 8];
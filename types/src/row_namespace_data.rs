@@ -8,15 +8,18 @@
 
 use blockstore::block::CidError;
 use bytes::{BufMut, BytesMut};
+use celestia_proto::proof::pb::Proof as RawProof;
 use celestia_proto::shwap::{RowNamespaceData as RawRowNamespaceData, Share as RawShare};
 use cid::CidGeneric;
 use multihash::Multihash;
+use prost::encoding::{decode_varint, encode_varint, encoded_len_varint};
 use prost::Message;
 use serde::{Deserialize, Serialize};
 
+use crate::consts::appconsts::AppVersion;
 use crate::nmt::{Namespace, NamespaceProof};
 use crate::row::{RowId, ROW_ID_SIZE};
-use crate::{bail_validation, DataAvailabilityHeader, Error, Result, Share};
+use crate::{bail_validation, Blob, DataAvailabilityHeader, Error, Result, Share};
 
 /// Number of bytes needed to represent [`RowNamespaceDataId`] in `multihash`.
 const ROW_NAMESPACE_DATA_ID_SIZE: usize = 39;
@@ -120,9 +123,7 @@ impl RowNamespaceData {
     /// This function will return error if proof is missing or invalid, shares are not in
     /// the expected namespace, and will propagate errors from [`Share`] construction.
     pub fn from_raw(id: RowNamespaceDataId, namespace_data: RawRowNamespaceData) -> Result<Self> {
-        let Some(proof) = namespace_data.proof else {
-            return Err(Error::MissingProof);
-        };
+        let proof = proof_from_raw(namespace_data.proof, namespace_data.shares.len())?;
 
         // extract all shares according to the expected namespace
         let shares: Vec<_> = namespace_data
@@ -142,10 +143,20 @@ impl RowNamespaceData {
             bail_validation!("Namespace data must have equal namespaces");
         }
 
-        Ok(RowNamespaceData {
-            shares,
-            proof: proof.try_into()?,
-        })
+        Ok(RowNamespaceData { shares, proof })
+    }
+}
+
+/// Recover the proof of the [`RowNamespaceData`].
+///
+/// Some node versions omit the proof if the namespace is not in the range of the row,
+/// in which case there are no shares and an empty proof of absence is returned.
+/// If the proof is missing for any shares, it is an error.
+fn proof_from_raw(proof: Option<RawProof>, shares_len: usize) -> Result<NamespaceProof> {
+    match proof {
+        Some(proof) => proof.try_into(),
+        None if shares_len == 0 => Ok(NamespaceProof::empty_absence()),
+        None => Err(Error::MissingProof),
     }
 }
 
@@ -159,6 +170,91 @@ pub struct NamespaceData {
     pub rows: Vec<RowNamespaceData>,
 }
 
+impl NamespaceData {
+    /// Reassemble all the [`Blob`]s of the given [`Namespace`] from the shares.
+    ///
+    /// Blobs can span over multiple rows and a single namespace can contain many
+    /// blobs. Commitment of each blob is recomputed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any of the shares doesn't belong to the
+    /// namespace or if the blobs cannot be reconstructed.
+    pub fn into_blobs(self, namespace: Namespace, app_version: AppVersion) -> Result<Vec<Blob>> {
+        let shares: Vec<_> = self.rows.into_iter().flat_map(|row| row.shares).collect();
+
+        if let Some(share) = shares.iter().find(|shr| shr.namespace() != namespace) {
+            bail_validation!(
+                "share namespace ({:?}) != expected namespace ({:?})",
+                share.namespace(),
+                namespace
+            );
+        }
+
+        Blob::reconstruct_all(&shares, app_version)
+    }
+
+    /// Encode NamespaceData into the binary representation.
+    ///
+    /// Rows are encoded as the amount of rows in a varint followed by each row as
+    /// a length delimited protobuf message, the same as in [`RowNamespaceData::encode`].
+    pub fn encode_vec(&self) -> Vec<u8> {
+        let raw_rows: Vec<_> = self
+            .rows
+            .iter()
+            .cloned()
+            .map(RawRowNamespaceData::from)
+            .collect();
+
+        let len = encoded_len_varint(raw_rows.len() as u64)
+            + raw_rows
+                .iter()
+                .map(|raw| {
+                    let len = raw.encoded_len();
+                    encoded_len_varint(len as u64) + len
+                })
+                .sum::<usize>();
+
+        let mut bytes = Vec::with_capacity(len);
+        encode_varint(raw_rows.len() as u64, &mut bytes);
+
+        for raw in raw_rows {
+            raw.encode_length_delimited(&mut bytes)
+                .expect("capacity reserved");
+        }
+
+        bytes
+    }
+
+    /// Decode NamespaceData from the binary representation.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the amount of rows doesn't match the
+    /// encoded one, if there are any trailing bytes or if protobuf deserialization
+    /// of any row fails.
+    pub fn decode(buffer: &[u8]) -> Result<Self> {
+        let mut buffer = buffer;
+        let rows_count = decode_varint(&mut buffer)?;
+        let mut rows = Vec::new();
+
+        for _ in 0..rows_count {
+            if buffer.is_empty() {
+                bail_validation!("expected {rows_count} rows, got {}", rows.len());
+            }
+
+            let raw = RawRowNamespaceData::decode_length_delimited(&mut buffer)?;
+            rows.push(raw.try_into()?);
+        }
+
+        if !buffer.is_empty() {
+            bail_validation!("{} trailing bytes after {rows_count} rows", buffer.len());
+        }
+
+        Ok(NamespaceData { rows })
+    }
+}
+
 impl From<RowNamespaceData> for RawRowNamespaceData {
     fn from(namespaced_data: RowNamespaceData) -> RawRowNamespaceData {
         RawRowNamespaceData {
@@ -176,10 +272,7 @@ impl TryFrom<RawRowNamespaceData> for RowNamespaceData {
     type Error = Error;
 
     fn try_from(value: RawRowNamespaceData) -> std::result::Result<Self, Self::Error> {
-        let Some(proof) = value.proof else {
-            return Err(Error::MissingProof);
-        };
-        let proof = proof.try_into()?;
+        let proof = proof_from_raw(value.proof, value.shares.len())?;
 
         let mut shares = Vec::with_capacity(value.shares.len());
         for raw_share in value.shares {
@@ -288,9 +381,8 @@ impl From<RowNamespaceDataId> for CidGeneric<ROW_NAMESPACE_DATA_ID_SIZE> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::consts::appconsts::AppVersion;
+    use crate::nmt::NamespacedSha2Hasher;
     use crate::test_utils::{generate_dummy_eds, generate_eds};
-    use crate::Blob;
 
     #[test]
     fn round_trip() {
@@ -392,6 +484,39 @@ mod tests {
 
         assert_eq!(ns_shares.rows[0].shares.len(), 1);
         assert!(!ns_shares.rows[0].proof.is_of_absence());
+
+        let encoded = ns_shares.encode_vec();
+        let decoded = NamespaceData::decode(&encoded).unwrap();
+        assert_eq!(decoded, ns_shares);
+    }
+
+    #[test]
+    fn namespace_data_decode_invalid_framing() {
+        let eds = generate_dummy_eds(8, AppVersion::V2);
+        let dah = DataAvailabilityHeader::from_eds(&eds);
+        let namespace = eds.share(0, 0).unwrap().namespace();
+        let rows = eds
+            .get_namespace_data(namespace, &dah, 1)
+            .unwrap()
+            .into_iter()
+            .map(|(_, row)| row)
+            .collect();
+        let encoded = NamespaceData { rows }.encode_vec();
+
+        // missing rows
+        NamespaceData::decode(&encoded[..encoded.len() - 1]).unwrap_err();
+        let mut more_rows = encoded.clone();
+        more_rows[0] += 1;
+        NamespaceData::decode(&more_rows).unwrap_err();
+
+        // trailing bytes
+        let mut trailing = encoded.clone();
+        trailing.push(0);
+        NamespaceData::decode(&trailing).unwrap_err();
+
+        // empty input
+        NamespaceData::decode(&[]).unwrap_err();
+        assert!(NamespaceData::decode(&[0]).unwrap().rows.is_empty());
     }
 
     #[test]
@@ -442,6 +567,107 @@ mod tests {
         }
     }
 
+    #[test]
+    fn absence_without_proof() {
+        let ns = Namespace::new_v0(&[1, 2, 3]).unwrap();
+        let id = RowNamespaceDataId::new(ns, 0, 1).unwrap();
+        let raw = RawRowNamespaceData {
+            shares: Vec::new(),
+            proof: None,
+        };
+
+        let row = RowNamespaceData::from_raw(id, raw.clone()).unwrap();
+        assert!(row.shares.is_empty());
+        assert!(row.proof.is_of_absence());
+        assert!(row.proof.leaf().is_none());
+
+        let row = RowNamespaceData::try_from(raw).unwrap();
+        assert!(row.shares.is_empty());
+        assert!(row.proof.is_of_absence());
+    }
+
+    #[test]
+    fn absence_without_proof_rejected_for_present_namespace() {
+        let eds = generate_eds(8, AppVersion::V2);
+        let dah = DataAvailabilityHeader::from_eds(&eds);
+
+        // namespace of a blob which is in the row
+        let ns = eds.share(1, 0).unwrap().namespace();
+        let id = RowNamespaceDataId::new(ns, 1, 1).unwrap();
+        let raw = RawRowNamespaceData {
+            shares: Vec::new(),
+            proof: None,
+        };
+
+        let row = RowNamespaceData::from_raw(id, raw).unwrap();
+        assert!(row.proof.is_of_absence());
+        row.verify(id, &dah).unwrap_err();
+    }
+
+    #[test]
+    fn absence_without_proof_outside_row_range() {
+        let eds = generate_eds(8, AppVersion::V2);
+        let dah = DataAvailabilityHeader::from_eds(&eds);
+
+        // first row holds only the pay for blob and primary padding shares,
+        // so the namespace of a blob is above the range of its root
+        let ns = eds.share(1, 0).unwrap().namespace();
+        let root = dah.row_root(0).unwrap();
+        assert!(!root.contains::<NamespacedSha2Hasher>(*ns));
+
+        let id = RowNamespaceDataId::new(ns, 0, 1).unwrap();
+        let raw = RawRowNamespaceData {
+            shares: Vec::new(),
+            proof: None,
+        };
+
+        let row = RowNamespaceData::from_raw(id, raw).unwrap();
+        row.verify(id, &dah).unwrap();
+    }
+
+    #[test]
+    fn absence_without_proof_inside_row_range() {
+        let eds = generate_eds(8, AppVersion::V2);
+        let dah = DataAvailabilityHeader::from_eds(&eds);
+
+        // namespace between pay for blob and primary padding, which has no shares
+        // in the first row but lies within the range of its root
+        let ns = Namespace::const_v0([0, 0, 0, 0, 0, 0, 0, 0, 0, 5]);
+        let root = dah.row_root(0).unwrap();
+        assert!(root.contains::<NamespacedSha2Hasher>(*ns));
+
+        let id = RowNamespaceDataId::new(ns, 0, 1).unwrap();
+        let raw = RawRowNamespaceData {
+            shares: Vec::new(),
+            proof: None,
+        };
+
+        let row = RowNamespaceData::from_raw(id, raw).unwrap();
+        row.verify(id, &dah).unwrap_err();
+    }
+
+    #[test]
+    fn missing_proof() {
+        let ns = Namespace::new_v0(&[1, 2, 3]).unwrap();
+        let id = RowNamespaceDataId::new(ns, 0, 1).unwrap();
+        let share = Share::from_raw(&[ns.as_bytes(), &[0; 483]].concat()).unwrap();
+        let raw = RawRowNamespaceData {
+            shares: vec![RawShare {
+                data: share.to_vec(),
+            }],
+            proof: None,
+        };
+
+        assert!(matches!(
+            RowNamespaceData::from_raw(id, raw.clone()),
+            Err(Error::MissingProof)
+        ));
+        assert!(matches!(
+            RowNamespaceData::try_from(raw),
+            Err(Error::MissingProof)
+        ));
+    }
+
     #[test]
     fn reconstruct_all() {
         for _ in 0..3 {
@@ -476,6 +702,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn namespace_data_into_blobs() {
+        for _ in 0..3 {
+            let eds = generate_eds(8 << (rand::random::<usize>() % 6), AppVersion::V2);
+            let dah = DataAvailabilityHeader::from_eds(&eds);
+
+            let mut namespaces: Vec<_> = eds
+                .data_square()
+                .iter()
+                .map(|shr| shr.namespace())
+                .filter(|ns| !ns.is_reserved())
+                .collect();
+            namespaces.dedup();
+
+            for ns in &namespaces {
+                let rows = eds.get_namespace_data(*ns, &dah, 1).unwrap();
+                let expected = Blob::reconstruct_all(
+                    rows.iter().flat_map(|(_, row)| row.shares.iter()),
+                    AppVersion::V2,
+                )
+                .unwrap();
+
+                let namespace_data = NamespaceData {
+                    rows: rows.into_iter().map(|(_, row)| row).collect(),
+                };
+                let blobs = namespace_data.into_blobs(*ns, AppVersion::V2).unwrap();
+
+                assert_eq!(blobs, expected);
+                for blob in &blobs {
+                    assert_eq!(blob.namespace, *ns);
+                    blob.validate(AppVersion::V2).unwrap();
+                }
+            }
+
+            // first namespace should have 2 blobs over 3 rows
+            let rows = eds.get_namespace_data(namespaces[0], &dah, 1).unwrap();
+            let namespace_data = NamespaceData {
+                rows: rows.into_iter().map(|(_, row)| row).collect(),
+            };
+            let blobs = namespace_data
+                .into_blobs(namespaces[0], AppVersion::V2)
+                .unwrap();
+            assert_eq!(blobs.len(), 2);
+        }
+    }
+
+    #[test]
+    fn namespace_data_into_blobs_wrong_namespace() {
+        let eds = generate_eds(8, AppVersion::V2);
+        let dah = DataAvailabilityHeader::from_eds(&eds);
+
+        let mut namespaces: Vec<_> = eds
+            .data_square()
+            .iter()
+            .map(|shr| shr.namespace())
+            .filter(|ns| !ns.is_reserved())
+            .collect();
+        namespaces.dedup();
+
+        let rows = eds.get_namespace_data(namespaces[0], &dah, 1).unwrap();
+        let namespace_data = NamespaceData {
+            rows: rows.into_iter().map(|(_, row)| row).collect(),
+        };
+
+        namespace_data
+            .into_blobs(namespaces[1], AppVersion::V2)
+            .unwrap_err();
+    }
+
     #[test]
     fn namespace_data_roundtrip() {
         let proof = nmt_rs::nmt_proof::NamespaceProof::<
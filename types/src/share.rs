@@ -49,7 +49,14 @@ impl Share {
     /// # Errors
     ///
     /// This function will return an error if the slice length isn't
-    /// [`SHARE_SIZE`] or if a namespace encoded in the share is invalid.
+    /// [`SHARE_SIZE`], if a namespace or [`InfoByte`] encoded in the share
+    /// is invalid, or if the share uses the [`Namespace::PARITY_SHARE`].
+    /// Parity shares should be created with [`Share::parity`].
+    ///
+    /// The combination of the namespace and the [`InfoByte`] is validated too.
+    /// The share version has to be supported and shares of reserved namespaces
+    /// must use the share version zero. Shares of the [`Namespace::PRIMARY_RESERVED_PADDING`]
+    /// and the [`Namespace::TAIL_PADDING`] must start a sequence of zero length.
     ///
     /// # Example
     ///
@@ -67,17 +74,42 @@ impl Share {
         }
 
         // validate namespace and info byte so that we can return it later without checks
-        Namespace::from_raw(&data[..NS_SIZE])?;
-        InfoByte::from_raw(data[NS_SIZE])?;
+        let namespace = Namespace::from_raw(&data[..NS_SIZE])?;
+        let info_byte = InfoByte::from_raw(data[NS_SIZE])?;
 
-        Ok(Share {
+        if namespace == Namespace::PARITY_SHARE {
+            return Err(Error::DataShareInParityNamespace);
+        }
+
+        let version = info_byte.version();
+        if version != appconsts::SHARE_VERSION_ZERO && version != appconsts::SHARE_VERSION_ONE {
+            return Err(Error::UnsupportedShareVersion(version));
+        }
+
+        // only blobs can be signed with the share version one
+        if namespace.is_reserved() && version != appconsts::SHARE_VERSION_ZERO {
+            return Err(Error::ShareVersionInReservedNamespace(version));
+        }
+
+        let share = Share {
             data: data.try_into().unwrap(),
             is_parity: false,
-        })
+        };
+
+        if [Namespace::PRIMARY_RESERVED_PADDING, Namespace::TAIL_PADDING].contains(&namespace)
+            && share.sequence_length() != Some(0)
+        {
+            return Err(Error::InvalidPaddingShare);
+        }
+
+        Ok(share)
     }
 
     /// Create a new [`Share`] within [`Namespace::PARITY_SHARE`] from raw bytes.
     ///
+    /// Parity shares consist solely of erasure coded data, so the bytes aren't
+    /// validated beyond their length.
+    ///
     /// # Errors
     ///
     /// This function will return an error if the slice length isn't [`SHARE_SIZE`].
@@ -149,6 +181,31 @@ impl Share {
         Some(&self.data[start..])
     }
 
+    /// Get the payload of the share without the trailing padding.
+    ///
+    /// `sequence_len_remaining` is the amount of payload bytes of the sequence which
+    /// weren't consumed by the preceding shares. For the first share in a sequence it
+    /// is the [`sequence_length`].
+    ///
+    /// Returns None if share is within [`Namespace::PARITY_SHARE`].
+    ///
+    /// [`sequence_length`]: Share::sequence_length
+    pub fn payload_trimmed(&self, sequence_len_remaining: usize) -> Option<&[u8]> {
+        let payload = self.payload()?;
+        let len = sequence_len_remaining.min(payload.len());
+        Some(&payload[..len])
+    }
+
+    /// Returns true if the share doesn't contain any data and only pads the square.
+    ///
+    /// Padding shares start an empty sequence and have their payload filled with zeros.
+    pub fn is_padding(&self) -> bool {
+        self.sequence_length() == Some(0)
+            && self
+                .payload()
+                .is_some_and(|payload| payload.iter().all(|byte| *byte == 0))
+    }
+
     /// Get the underlying share data.
     pub fn data(&self) -> &[u8; appconsts::SHARE_SIZE] {
         &self.data
@@ -210,9 +267,10 @@ impl From<Share> for RawShare {
 mod tests {
     use super::*;
     use crate::consts::appconsts::AppVersion;
-    use crate::nmt::{NamespaceProof, NamespacedHash, NAMESPACED_HASH_SIZE};
+    use crate::nmt::{NamespaceProof, NamespacedHash, Nmt, NAMESPACED_HASH_SIZE};
     use crate::Blob;
     use base64::prelude::*;
+    use nmt_rs::nmt_proof::NamespaceProof as NmtNamespaceProof;
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test as test;
@@ -252,6 +310,131 @@ mod tests {
         );
     }
 
+    #[test]
+    fn payload_trimmed() {
+        let ns = Namespace::new_v0(b"foo").unwrap();
+        let data = vec![7; 512];
+        let blob = Blob::new(ns, data.clone(), AppVersion::V2).unwrap();
+
+        let shares = blob.to_shares().unwrap();
+        let sequence_len = shares[0].sequence_length().unwrap() as usize;
+        assert_eq!(sequence_len, data.len());
+
+        // full share
+        let first = shares[0].payload_trimmed(sequence_len).unwrap();
+        assert_eq!(first, shares[0].payload().unwrap());
+        assert!(!shares[0].is_padding());
+
+        // partially filled final share
+        let remaining = sequence_len - first.len();
+        let last = shares[1].payload_trimmed(remaining).unwrap();
+        assert_eq!(last.len(), remaining);
+        assert!(!shares[1].is_padding());
+
+        assert_eq!([first, last].concat(), data);
+    }
+
+    #[test]
+    fn padding_share() {
+        for ns in [
+            Namespace::new_v0(b"foo").unwrap(),
+            Namespace::PRIMARY_RESERVED_PADDING,
+            Namespace::TAIL_PADDING,
+        ] {
+            let mut raw = [0; appconsts::SHARE_SIZE];
+            raw[..NS_SIZE].copy_from_slice(ns.as_bytes());
+            raw[NS_SIZE] = InfoByte::new(0, true).unwrap().as_u8();
+            let share = Share::from_raw(&raw).unwrap();
+
+            assert!(share.is_padding());
+            assert_eq!(share.payload_trimmed(0).unwrap(), &[] as &[u8]);
+        }
+
+        let parity = Share::parity(&[0; appconsts::SHARE_SIZE]).unwrap();
+        assert!(!parity.is_padding());
+        assert!(parity.payload_trimmed(0).is_none());
+    }
+
+    #[test]
+    fn data_share_validation() {
+        let raw_share = |ns: Namespace, info_byte: InfoByte| {
+            let mut raw = [0; appconsts::SHARE_SIZE];
+            raw[..NS_SIZE].copy_from_slice(ns.as_bytes());
+            raw[NS_SIZE] = info_byte.as_u8();
+            raw
+        };
+        let continuation = InfoByte::new(0, false).unwrap();
+
+        let ns = Namespace::new_v0(b"foo").unwrap();
+        let share = Share::from_raw(&raw_share(ns, continuation)).unwrap();
+        assert!(!share.is_parity());
+        assert_eq!(share.namespace(), ns);
+
+        // parity shares carry erasure coded data and aren't validated
+        let raw = raw_share(Namespace::PARITY_SHARE, continuation);
+        let parity = Share::parity(&raw).unwrap();
+        assert!(parity.is_parity());
+        assert_eq!(parity.namespace(), Namespace::PARITY_SHARE);
+
+        assert!(matches!(
+            Share::from_raw(&raw).unwrap_err(),
+            Error::DataShareInParityNamespace
+        ));
+    }
+
+    #[test]
+    fn unsupported_share_version() {
+        let mut raw = [0; appconsts::SHARE_SIZE];
+        raw[..NS_SIZE].copy_from_slice(Namespace::new_v0(b"foo").unwrap().as_bytes());
+        raw[NS_SIZE] = InfoByte::new(2, true).unwrap().as_u8();
+
+        assert!(matches!(
+            Share::from_raw(&raw).unwrap_err(),
+            Error::UnsupportedShareVersion(2)
+        ));
+    }
+
+    #[test]
+    fn share_version_one_in_reserved_namespace() {
+        let info_byte = InfoByte::new(appconsts::SHARE_VERSION_ONE, true).unwrap();
+
+        let mut raw = [0; appconsts::SHARE_SIZE];
+        raw[..NS_SIZE].copy_from_slice(Namespace::new_v0(b"foo").unwrap().as_bytes());
+        raw[NS_SIZE] = info_byte.as_u8();
+        Share::from_raw(&raw).unwrap();
+
+        raw[..NS_SIZE].copy_from_slice(Namespace::TRANSACTION.as_bytes());
+        assert!(matches!(
+            Share::from_raw(&raw).unwrap_err(),
+            Error::ShareVersionInReservedNamespace(1)
+        ));
+    }
+
+    #[test]
+    fn padding_share_not_starting_sequence() {
+        let mut raw = [0; appconsts::SHARE_SIZE];
+        raw[..NS_SIZE].copy_from_slice(Namespace::TAIL_PADDING.as_bytes());
+        raw[NS_SIZE] = InfoByte::new(0, false).unwrap().as_u8();
+
+        assert!(matches!(
+            Share::from_raw(&raw).unwrap_err(),
+            Error::InvalidPaddingShare
+        ));
+    }
+
+    #[test]
+    fn padding_share_with_sequence_length() {
+        let mut raw = [0; appconsts::SHARE_SIZE];
+        raw[..NS_SIZE].copy_from_slice(Namespace::PRIMARY_RESERVED_PADDING.as_bytes());
+        raw[NS_SIZE] = InfoByte::new(0, true).unwrap().as_u8();
+        raw[SHARE_SEQUENCE_LENGTH_OFFSET + appconsts::SEQUENCE_LEN_BYTES - 1] = 1;
+
+        assert!(matches!(
+            Share::from_raw(&raw).unwrap_err(),
+            Error::InvalidPaddingShare
+        ));
+    }
+
     #[test]
     fn share_should_have_correct_len() {
         Share::from_raw(&[0; 0]).unwrap_err();
@@ -314,6 +497,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn presence_proof_max_ns_ignored() {
+        let blob_get_proof_response = r#"{
+            "start": 1,
+            "end": 2,
+            "nodes": [
+                "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABA+poCQOx7UzVkteV9DgcA6g29ZXXOp0hYZb67hoNkFP",
+                "/////////////////////////////////////////////////////////////////////////////8PbbPgQcFSaW2J/BWiJqrCoj6K4g/UUd0Y9dadwqrz+"
+            ]
+        }"#;
+
+        let mut proof: NamespaceProof =
+            serde_json::from_str(blob_get_proof_response).expect("can not parse proof");
+        assert!(!proof.max_ns_ignored());
+
+        let siblings = proof.siblings().to_vec();
+        let namespace = Namespace::from_raw(&siblings[0].min_namespace().0).unwrap();
+        let leaf = b"leaf";
+
+        // recompute the root of the tree, with leaf placed at index 1
+        let compute_root = |ignore_max_ns| {
+            let hasher = NamespacedSha2Hasher::with_ignore_max_ns(ignore_max_ns);
+            let mut nmt = Nmt::with_hasher(NamespacedSha2Hasher::with_ignore_max_ns(ignore_max_ns));
+            nmt.push_leaf(leaf, *namespace).unwrap();
+            let leaf_hash = nmt.root();
+
+            let left = hasher.hash_nodes(&siblings[0], &leaf_hash);
+            hasher.hash_nodes(&left, &siblings[1])
+        };
+        let root = compute_root(false);
+        let root_max_ns_ignored = compute_root(true);
+
+        // right sibling is in the parity namespace, so the flag changes the max namespace
+        assert_ne!(root, root_max_ns_ignored);
+        assert_eq!(root.max_namespace(), *Namespace::PARITY_SHARE);
+        assert_eq!(root_max_ns_ignored.max_namespace(), *namespace);
+
+        proof.verify_range(&root, &[leaf], *namespace).unwrap();
+        proof
+            .verify_range(&root_max_ns_ignored, &[leaf], *namespace)
+            .unwrap_err();
+
+        if let NmtNamespaceProof::PresenceProof { ignore_max_ns, .. } = &mut *proof {
+            *ignore_max_ns = true;
+        }
+        assert!(proof.max_ns_ignored());
+
+        proof
+            .verify_range(&root_max_ns_ignored, &[leaf], *namespace)
+            .unwrap();
+        proof.verify_range(&root, &[leaf], *namespace).unwrap_err();
+    }
+
     #[test]
     fn decode_absence_proof() {
         let blob_get_proof_response = r#"{
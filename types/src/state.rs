@@ -14,7 +14,7 @@ pub use self::query_delegation::{
 };
 pub use self::tx::{
     AuthInfo, Coin, ErrorCode, Fee, ModeInfo, RawTx, RawTxBody, RawTxResponse, SignerInfo, Sum, Tx,
-    TxBody, TxResponse, BOND_DENOM,
+    TxBody, TxResponse, BOND_DENOM, PAY_FOR_BLOBS_EVENT,
 };
 
 /// A 256-bit unsigned integer.
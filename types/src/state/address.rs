@@ -162,6 +162,13 @@ impl AccAddress {
     pub fn new(id: Id) -> Self {
         AccAddress { id }
     }
+
+    /// Get the validator address of this account.
+    ///
+    /// Both addresses share the ID and differ only in the `bech32` prefix.
+    pub fn to_val_address(&self) -> ValAddress {
+        ValAddress::new(self.id)
+    }
 }
 
 impl AddressTrait for AccAddress {
@@ -218,6 +225,13 @@ impl ValAddress {
     pub fn new(id: Id) -> Self {
         ValAddress { id }
     }
+
+    /// Get the account address of this validator.
+    ///
+    /// Both addresses share the ID and differ only in the `bech32` prefix.
+    pub fn to_acc_address(&self) -> AccAddress {
+        AccAddress::new(self.id)
+    }
 }
 
 impl AddressTrait for ValAddress {
@@ -498,4 +512,13 @@ mod tests {
         let cons_addr: ConsAddress = addr.try_into().unwrap();
         let _addr: Address = cons_addr.into();
     }
+
+    #[test]
+    fn convert_acc_and_val_addr() {
+        let acc_addr: AccAddress = ADDR1_ACC_STR.parse().unwrap();
+        let val_addr = acc_addr.to_val_address();
+
+        assert_eq!(val_addr.to_string(), ADDR1_VAL_STR);
+        assert_eq!(val_addr.to_acc_address(), acc_addr);
+    }
 }
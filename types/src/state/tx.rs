@@ -31,6 +31,11 @@ pub type Signature = Vec<u8>;
 /// [`BOND_DENOM`] defines the native staking denomination
 pub const BOND_DENOM: &str = "utia";
 
+/// Type of the event emitted when processing [`MsgPayForBlobs`].
+///
+/// [`MsgPayForBlobs`]: celestia_proto::celestia::blob::v1::MsgPayForBlobs
+pub const PAY_FOR_BLOBS_EVENT: &str = "celestia.blob.v1.EventPayForBlobs";
+
 /// [`Tx`] is the standard type used for broadcasting transactions.
 #[derive(Debug, Clone)]
 pub struct Tx {
@@ -126,6 +131,61 @@ pub struct TxResponse {
     pub events: Vec<Event>,
 }
 
+impl TxResponse {
+    /// Find the value of an attribute with the given `key` in the first event
+    /// of `event_type` that has it.
+    ///
+    /// Both the [`events`] and the events in [`logs`] are searched.
+    ///
+    /// [`events`]: TxResponse::events
+    /// [`logs`]: TxResponse::logs
+    pub fn find_attribute(&self, event_type: &str, key: &str) -> Option<&str> {
+        let from_events = self
+            .events
+            .iter()
+            .filter(|event| event.r#type == event_type)
+            .flat_map(|event| &event.attributes)
+            .find_map(|attr| {
+                let attr_key = std::str::from_utf8(attr.key.as_ref()).ok()?;
+                (attr_key == key)
+                    .then(|| std::str::from_utf8(attr.value.as_ref()).ok())
+                    .flatten()
+            });
+
+        from_events.or_else(|| {
+            self.logs
+                .iter()
+                .flat_map(|log| &log.events)
+                .filter(|event| event.r#type == event_type)
+                .flat_map(|event| &event.attributes)
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.as_str())
+        })
+    }
+
+    /// Sizes of the blobs paid for by this transaction, in the order of submission.
+    ///
+    /// Parsed from the [`PAY_FOR_BLOBS_EVENT`], returns an empty list if the transaction
+    /// didn't pay for any blobs.
+    ///
+    /// Note that the event doesn't carry the share indices of the blobs, those
+    /// are only known after the block is built and can be obtained along with the blobs.
+    pub fn blob_sizes(&self) -> Vec<u64> {
+        let Some(sizes) = self.find_attribute(PAY_FOR_BLOBS_EVENT, "blob_sizes") else {
+            return Vec::new();
+        };
+
+        // Typed events have JSON encoded attributes, e.g. `[1024,512]`
+        sizes
+            .trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .filter_map(|size| size.trim().trim_matches('"').parse().ok())
+            .collect()
+    }
+}
+
 /// [`AuthInfo`] describes the fee and signer modes that are used to sign a transaction.
 #[derive(Debug, Clone)]
 pub struct AuthInfo {
@@ -613,3 +673,99 @@ impl TryFrom<RawCoin> for Coin {
 
 impl Protobuf<RawTxBody> for TxBody {}
 impl Protobuf<RawAuthInfo> for AuthInfo {}
+
+#[cfg(test)]
+mod tests {
+    use celestia_proto::cosmos::base::abci::v1beta1::{Attribute, StringEvent};
+    use tendermint_proto::v0_34::abci::EventAttribute;
+
+    use super::*;
+
+    fn event(r#type: &str, attributes: &[(&'static str, &'static str)]) -> Event {
+        Event {
+            r#type: r#type.to_owned(),
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| EventAttribute {
+                    key: (*key).into(),
+                    value: (*value).into(),
+                    index: true,
+                })
+                .collect(),
+        }
+    }
+
+    // Events as emitted by celestia-app for a transaction paying for two blobs
+    fn pay_for_blobs_response() -> TxResponse {
+        RawTxResponse {
+            height: 42,
+            txhash: "9F2A1DB5E5C2E4B1D5C56E1F5A5A2E0C6E2B53A9C3DDD4B1E6B0B08E3E0A6C0B".into(),
+            events: vec![
+                event(
+                    "tx",
+                    &[("fee", "2000utia"), ("fee_payer", "celestia1zxcv")],
+                ),
+                event(
+                    "message",
+                    &[("action", "/celestia.blob.v1.MsgPayForBlobs")],
+                ),
+                event(
+                    PAY_FOR_BLOBS_EVENT,
+                    &[
+                        ("blob_sizes", "[1024,256]"),
+                        (
+                            "namespaces",
+                            "[\"AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAEC\",\"AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAED\"]",
+                        ),
+                        ("signer", "\"celestia1zxcv\""),
+                    ],
+                ),
+            ],
+            logs: vec![AbciMessageLog {
+                msg_index: 0,
+                log: String::new(),
+                events: vec![StringEvent {
+                    r#type: "message".into(),
+                    attributes: vec![Attribute {
+                        key: "module".into(),
+                        value: "blob".into(),
+                    }],
+                }],
+            }],
+            ..Default::default()
+        }
+        .try_into()
+        .unwrap()
+    }
+
+    #[test]
+    fn find_attribute() {
+        let response = pay_for_blobs_response();
+
+        assert_eq!(response.find_attribute("tx", "fee"), Some("2000utia"));
+        assert_eq!(
+            response.find_attribute("message", "action"),
+            Some("/celestia.blob.v1.MsgPayForBlobs")
+        );
+        // found in logs
+        assert_eq!(response.find_attribute("message", "module"), Some("blob"));
+        assert_eq!(response.find_attribute("tx", "missing"), None);
+        assert_eq!(response.find_attribute("missing", "fee"), None);
+    }
+
+    #[test]
+    fn blob_sizes() {
+        let response = pay_for_blobs_response();
+        assert_eq!(response.blob_sizes(), vec![1024, 256]);
+    }
+
+    #[test]
+    fn blob_sizes_without_pay_for_blobs() {
+        let mut response = pay_for_blobs_response();
+        response
+            .events
+            .retain(|event| event.r#type != PAY_FOR_BLOBS_EVENT);
+
+        assert!(response.blob_sizes().is_empty());
+    }
+}
@@ -17,6 +17,7 @@ use crate::consts::appconsts::{
 use crate::consts::version;
 use crate::hash::{Hash, HashExt};
 use crate::nmt::{Namespace, NS_SIZE};
+use crate::share::InfoByte;
 use crate::{
     Blob, DataAvailabilityHeader, ExtendedDataSquare, ExtendedHeader, Share, ValidatorSet,
 };
@@ -412,7 +413,8 @@ pub fn generate_eds(square_width: usize, app_version: AppVersion) -> ExtendedDat
     shares.extend((pfb_shares..ods_width).map(|_| {
         [
             Namespace::PRIMARY_RESERVED_PADDING.as_bytes(),
-            &[0; SHARE_SIZE - NS_SIZE][..],
+            &[InfoByte::new(0, true).unwrap().as_u8()],
+            &[0; SHARE_SIZE - NS_SIZE - 1],
         ]
         .concat()
     }));
@@ -447,10 +449,14 @@ pub fn generate_eds(square_width: usize, app_version: AppVersion) -> ExtendedDat
         } else {
             Namespace::TAIL_PADDING
         };
-        shares.extend(
-            (blob_shares..ods_width)
-                .map(|_| [padding_ns.as_bytes(), &[0; SHARE_SIZE - NS_SIZE][..]].concat()),
-        );
+        shares.extend((blob_shares..ods_width).map(|_| {
+            [
+                padding_ns.as_bytes(),
+                &[InfoByte::new(0, true).unwrap().as_u8()],
+                &[0; SHARE_SIZE - NS_SIZE - 1],
+            ]
+            .concat()
+        }));
     }
 
     ExtendedDataSquare::from_ods(shares, app_version).unwrap()